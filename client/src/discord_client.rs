@@ -36,7 +36,10 @@ pub async fn update_message(message: UpdateMessage) -> Result<(), Error> {
 }
 
 pub async fn create_message(message: CreateMessage) -> Result<(), Error> {
-    let discord_request = DiscordRequest::new(&message.message);
+    let discord_request = DiscordRequest {
+        content: message.message.to_string(),
+        components: message.components.clone(),
+    };
     match reqwest::Client::new()
         .post(message.url())
         .headers(discord_headers(&message.authorization))
@@ -55,7 +58,7 @@ pub async fn create_message(message: CreateMessage) -> Result<(), Error> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DiscordRequest {
     content: String,
-    components: Vec<String>,
+    components: Vec<serde_json::Value>,
 }
 
 impl DiscordRequest {
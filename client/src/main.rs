@@ -5,7 +5,7 @@ mod gemini_dtos;
 mod snark;
 mod tldr;
 
-use crate::discord_client::{delete_message, update_message};
+use crate::discord_client::{create_message, delete_message, update_message};
 use crate::tldr::tldr;
 use pog_common::DiscordMessage;
 use std::sync::Arc;
@@ -33,7 +33,7 @@ pub(crate) async fn payload_router(message: DiscordMessage) -> Result<(), error:
         DiscordMessage::Delete(delete) => delete_message(delete).await?,
         DiscordMessage::Update(update) => update_message(update).await?,
         DiscordMessage::TlDr(message) => tldr(message).await?,
-        DiscordMessage::Create(_) => {}
+        DiscordMessage::Create(create) => create_message(create).await?,
     }
     Ok(())
 }
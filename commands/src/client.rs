@@ -36,6 +36,12 @@ impl DiscordClient {
             ApplicationCommand::create_bet(),
             ApplicationCommand::list_bets(),
             ApplicationCommand::settle(),
+            ApplicationCommand::edit_bet(),
+            ApplicationCommand::pool(),
+            ApplicationCommand::debts(),
+            ApplicationCommand::ledger(),
+            ApplicationCommand::bet_stats(),
+            ApplicationCommand::bet_history(),
             ApplicationCommand::attendance(),
             ApplicationCommand::help(),
             ApplicationCommand::admin(),
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use pog_common::{
-    ADD_BET_COMMAND, ADMIN_COMMAND, ATTENDANCE_BET_COMMAND, HELP_COMMAND, LIST_BET_COMMAND,
+    ADD_BET_COMMAND, ADMIN_COMMAND, ATTENDANCE_BET_COMMAND, BET_HISTORY_COMMAND, BET_STATS_COMMAND,
+    DEBTS_COMMAND, EDIT_BET_COMMAND, HELP_COMMAND, LEDGER_COMMAND, LIST_BET_COMMAND, POOL_COMMAND,
     SETTLE_BET_COMMAND, WHOIS_COMMAND,
 };
 
@@ -24,6 +25,29 @@ pub struct ApplicationCommandOptions {
     name: String,
     description: String,
     required: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    autocomplete: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    choices: Option<Vec<ApplicationCommandOptionChoice>>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationCommandOptionChoice {
+    name: String,
+    value: String,
+}
+
+impl ApplicationCommandOptionChoice {
+    fn new(value: &str) -> Self {
+        Self {
+            name: value.to_string(),
+            value: value.to_string(),
+        }
+    }
 }
 
 // https://discord.com/developers/docs/interactions/application-commands
@@ -37,8 +61,10 @@ impl ApplicationCommand {
             options: Some(vec![ApplicationCommandOptions {
                 command_type: 3,
                 name: "who".to_string(),
-                description: "Who are you betting?".to_string(),
-                required: true,
+                description: "Who are you betting? (leave empty for an open offer)".to_string(),
+                required: false,
+                autocomplete: false,
+                choices: None,
             }]),
         }
     }
@@ -48,24 +74,188 @@ impl ApplicationCommand {
             command_type: 1,
             name: LIST_BET_COMMAND.to_string(),
             description: "List bets".to_string(),
+            options: Some(vec![
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "bettor".to_string(),
+                    description: "Which bettor do you want listed?".to_string(),
+                    required: true,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "status".to_string(),
+                    description: "Which bets? (default: open)".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: Some(vec![
+                        ApplicationCommandOptionChoice::new("open"),
+                        ApplicationCommandOptionChoice::new("won"),
+                        ApplicationCommandOptionChoice::new("lost"),
+                        ApplicationCommandOptionChoice::new("all"),
+                    ]),
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "from".to_string(),
+                    description: "Bets placed on or after (mm/dd/yyyy)".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "to".to_string(),
+                    description: "Bets placed on or before (mm/dd/yyyy)".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+            ]),
+        }
+    }
+    pub fn settle() -> Self {
+        Self {
+            id: None,
+            command_type: 1,
+            name: SETTLE_BET_COMMAND.to_string(),
+            description: "Close a bet".to_string(),
+            options: Some(vec![ApplicationCommandOptions {
+                command_type: 3,
+                name: "wager".to_string(),
+                description: "Which bet? (start typing to search)".to_string(),
+                required: false,
+                autocomplete: true,
+                choices: None,
+            }]),
+        }
+    }
+
+    pub fn edit_bet() -> Self {
+        Self {
+            id: None,
+            command_type: 1,
+            name: EDIT_BET_COMMAND.to_string(),
+            description: "Fix the terms of an open bet".to_string(),
             options: Some(vec![ApplicationCommandOptions {
                 command_type: 3,
-                name: "bettor".to_string(),
-                description: "Which bettor do you want listed?".to_string(),
+                name: "wager".to_string(),
+                description: "Which bet? (start typing to search)".to_string(),
                 required: true,
+                autocomplete: true,
+                choices: None,
             }]),
         }
     }
-    pub fn settle() -> Self {
+
+    pub fn bet_history() -> Self {
         Self {
             id: None,
             command_type: 1,
-            name: SETTLE_BET_COMMAND.to_string(),
-            description: "Close a bet".to_string(),
+            name: BET_HISTORY_COMMAND.to_string(),
+            description: "Show everything that happened to a bet".to_string(),
+            options: Some(vec![ApplicationCommandOptions {
+                command_type: 3,
+                name: "wager".to_string(),
+                description: "Which bet? (start typing to search)".to_string(),
+                required: true,
+                autocomplete: true,
+                choices: None,
+            }]),
+        }
+    }
+
+    pub fn debts() -> Self {
+        Self {
+            id: None,
+            command_type: 1,
+            name: DEBTS_COMMAND.to_string(),
+            description: "List unpaid bets".to_string(),
             options: None,
         }
     }
 
+    pub fn pool() -> Self {
+        Self {
+            id: None,
+            command_type: 1,
+            name: POOL_COMMAND.to_string(),
+            description: "Start a pool anyone can buy into".to_string(),
+            options: None,
+        }
+    }
+
+    pub fn ledger() -> Self {
+        Self {
+            id: None,
+            command_type: 1,
+            name: LEDGER_COMMAND.to_string(),
+            description: "Net amounts owed between members".to_string(),
+            options: Some(vec![
+                ApplicationCommandOptions {
+                    command_type: 6,
+                    name: "user".to_string(),
+                    description: "Whose ledger?".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 6,
+                    name: "settle_all".to_string(),
+                    description: "Mark every unpaid bet with this member as paid".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+            ]),
+        }
+    }
+
+    pub fn bet_stats() -> Self {
+        Self {
+            id: None,
+            command_type: 1,
+            name: BET_STATS_COMMAND.to_string(),
+            description: "Betting records and leaderboard".to_string(),
+            options: Some(vec![
+                ApplicationCommandOptions {
+                    command_type: 6,
+                    name: "user".to_string(),
+                    description: "Whose record?".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "season".to_string(),
+                    description: "Which season (year)?".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "from".to_string(),
+                    description: "Bets placed on or after (mm/dd/yyyy)".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "to".to_string(),
+                    description: "Bets placed on or before (mm/dd/yyyy)".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+            ]),
+        }
+    }
+
     pub fn attendance() -> Self {
         Self {
             id: None,
@@ -78,12 +268,35 @@ impl ApplicationCommand {
                     name: "manager".to_string(),
                     description: "Which team manager?".to_string(),
                     required: false,
+                    autocomplete: false,
+                    choices: None,
                 },
                 ApplicationCommandOptions {
                     command_type: 3,
                     name: "week".to_string(),
                     description: "Interested in just one week?".to_string(),
                     required: false,
+                    autocomplete: true,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "season".to_string(),
+                    description: "Which season (year)?".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "view".to_string(),
+                    description: "Just one manager or the whole league?".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: Some(vec![
+                        ApplicationCommandOptionChoice::new("manager"),
+                        ApplicationCommandOptionChoice::new("league"),
+                    ]),
                 },
             ]),
         }
@@ -110,12 +323,190 @@ impl ApplicationCommand {
                     name: "set_user".to_string(),
                     description: "Add or update a user".to_string(),
                     required: false,
+                    autocomplete: false,
+                    choices: None,
                 },
                 ApplicationCommandOptions {
                     command_type: 7,
                     name: "welcome_channel".to_string(),
                     description: "Set the welcome channel".to_string(),
                     required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 7,
+                    name: "admin_channel".to_string(),
+                    description: "Set the channel disputed bets are sent to".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 7,
+                    name: "betting_channel".to_string(),
+                    description: "Set the channel overdue bet reminders are sent to".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "reminder_days".to_string(),
+                    description: "Days between reminders for overdue bets (0 to remind once)"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "offer_hours".to_string(),
+                    description: "Hours before an untaken open offer expires (0 to never expire)"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "season".to_string(),
+                    description: "The current season, like 2024 (0 to follow the calendar)"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "week".to_string(),
+                    description: "The current week of the season".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "max_stake".to_string(),
+                    description: "Most a user can put up on one bet, like $50 (none for no limit)"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "max_open_bets".to_string(),
+                    description: "Most open bets a user can have at once (0 for no limit)"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "max_exposure".to_string(),
+                    description:
+                        "Most a user can have at stake across open bets (none for no limit)"
+                            .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 6,
+                    name: "limit_exempt".to_string(),
+                    description: "Exempt a user from betting limits, or remove their exemption"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "link_name".to_string(),
+                    description: "A name used in bets before the user joined".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 6,
+                    name: "link_user".to_string(),
+                    description: "The user to link that name to".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "attendance".to_string(),
+                    description: "Record who attended on a date, like 10/20 or sunday".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "attendance_week".to_string(),
+                    description: "The week to record attendance for, defaults to the current week"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "rsvp".to_string(),
+                    description: "Ask who is coming on a game day, like sunday or 11/24"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 7,
+                    name: "rsvp_channel".to_string(),
+                    description: "Set the channel that asks who is coming".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "rsvp_day".to_string(),
+                    description: "Day of the week to ask who is coming on Sunday (none to stop)"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 7,
+                    name: "watch_channel".to_string(),
+                    description: "Set the voice channel where being there counts as attending"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "watch_window".to_string(),
+                    description: "Game days and hours for the watch channel, like sunday 13-24"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "watch_minutes".to_string(),
+                    description: "Minutes in the watch channel needed to count as attending"
+                        .to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
                 },
             ]),
         }
@@ -127,12 +518,24 @@ impl ApplicationCommand {
             command_type: 1,
             name: WHOIS_COMMAND.to_string(),
             description: "Lookup a user".to_string(),
-            options: Some(vec![ApplicationCommandOptions {
-                command_type: 6,
-                name: "whois".to_string(),
-                description: "Lookup a user".to_string(),
-                required: true,
-            }]),
+            options: Some(vec![
+                ApplicationCommandOptions {
+                    command_type: 6,
+                    name: "whois".to_string(),
+                    description: "Lookup a user".to_string(),
+                    required: false,
+                    autocomplete: false,
+                    choices: None,
+                },
+                ApplicationCommandOptions {
+                    command_type: 3,
+                    name: "name".to_string(),
+                    description: "Lookup a user by human or hash name".to_string(),
+                    required: false,
+                    autocomplete: true,
+                    choices: None,
+                },
+            ]),
         }
     }
 }
@@ -150,7 +553,7 @@ mod test {
         let command = serde_json::to_string(&ApplicationCommand::create_bet()).unwrap();
         assert_eq!(
             &command,
-            r#"{"type":1,"name":"bet","description":"Create a bet","options":[{"type":3,"name":"who","description":"Who are you betting?","required":true}]}"#
+            r#"{"type":1,"name":"bet","description":"Create a bet","options":[{"type":3,"name":"who","description":"Who are you betting? (leave empty for an open offer)","required":false}]}"#
         )
     }
 
@@ -159,7 +562,7 @@ mod test {
         let command = serde_json::to_string(&ApplicationCommand::list_bets()).unwrap();
         assert_eq!(
             &command,
-            r#"{"type":1,"name":"bets","description":"List bets","options":[{"type":3,"name":"bettor","description":"Which bettor do you want listed?","required":true}]}"#
+            r#"{"type":1,"name":"bets","description":"List bets","options":[{"type":3,"name":"bettor","description":"Which bettor do you want listed?","required":true},{"type":3,"name":"status","description":"Which bets? (default: open)","required":false,"choices":[{"name":"open","value":"open"},{"name":"won","value":"won"},{"name":"lost","value":"lost"},{"name":"all","value":"all"}]},{"type":3,"name":"from","description":"Bets placed on or after (mm/dd/yyyy)","required":false},{"type":3,"name":"to","description":"Bets placed on or before (mm/dd/yyyy)","required":false}]}"#
         )
     }
 
@@ -168,15 +571,75 @@ mod test {
         let command = serde_json::to_string(&ApplicationCommand::settle()).unwrap();
         assert_eq!(
             &command,
-            r#"{"type":1,"name":"settle","description":"Close a bet"}"#
+            r#"{"type":1,"name":"settle","description":"Close a bet","options":[{"type":3,"name":"wager","description":"Which bet? (start typing to search)","required":false,"autocomplete":true}]}"#
+        )
+    }
+    #[test]
+    fn edit_bet() {
+        let command = serde_json::to_string(&ApplicationCommand::edit_bet()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"edit_bet","description":"Fix the terms of an open bet","options":[{"type":3,"name":"wager","description":"Which bet? (start typing to search)","required":true,"autocomplete":true}]}"#
+        )
+    }
+    #[test]
+    fn bet_history() {
+        let command = serde_json::to_string(&ApplicationCommand::bet_history()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"bet_history","description":"Show everything that happened to a bet","options":[{"type":3,"name":"wager","description":"Which bet? (start typing to search)","required":true,"autocomplete":true}]}"#
+        )
+    }
+    #[test]
+    fn whois() {
+        let command = serde_json::to_string(&ApplicationCommand::whois()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"whois","description":"Lookup a user","options":[{"type":6,"name":"whois","description":"Lookup a user","required":false},{"type":3,"name":"name","description":"Lookup a user by human or hash name","required":false,"autocomplete":true}]}"#
+        )
+    }
+    #[test]
+    fn debts() {
+        let command = serde_json::to_string(&ApplicationCommand::debts()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"debts","description":"List unpaid bets"}"#
         )
     }
+
+    #[test]
+    fn pool() {
+        let command = serde_json::to_string(&ApplicationCommand::pool()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"pool","description":"Start a pool anyone can buy into"}"#
+        )
+    }
+
+    #[test]
+    fn ledger() {
+        let command = serde_json::to_string(&ApplicationCommand::ledger()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"ledger","description":"Net amounts owed between members","options":[{"type":6,"name":"user","description":"Whose ledger?","required":false},{"type":6,"name":"settle_all","description":"Mark every unpaid bet with this member as paid","required":false}]}"#
+        )
+    }
+
+    #[test]
+    fn bet_stats() {
+        let command = serde_json::to_string(&ApplicationCommand::bet_stats()).unwrap();
+        assert_eq!(
+            &command,
+            r#"{"type":1,"name":"betstats","description":"Betting records and leaderboard","options":[{"type":6,"name":"user","description":"Whose record?","required":false},{"type":3,"name":"season","description":"Which season (year)?","required":false},{"type":3,"name":"from","description":"Bets placed on or after (mm/dd/yyyy)","required":false},{"type":3,"name":"to","description":"Bets placed on or before (mm/dd/yyyy)","required":false}]}"#
+        )
+    }
+
     #[test]
     fn attendance() {
         let command = serde_json::to_string(&ApplicationCommand::attendance()).unwrap();
         assert_eq!(
             &command,
-            r#"{"type":1,"name":"attendance","description":"Check attendance","options":[{"type":3,"name":"manager","description":"Which team manager?","required":false},{"type":3,"name":"week","description":"Interested in just one week?","required":false}]}"#
+            r#"{"type":1,"name":"attendance","description":"Check attendance","options":[{"type":3,"name":"manager","description":"Which team manager?","required":false},{"type":3,"name":"week","description":"Interested in just one week?","required":false,"autocomplete":true},{"type":3,"name":"season","description":"Which season (year)?","required":false},{"type":3,"name":"view","description":"Just one manager or the whole league?","required":false,"choices":[{"name":"manager","value":"manager"},{"name":"league","value":"league"}]}]}"#
         )
     }
 
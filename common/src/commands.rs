@@ -1,7 +1,13 @@
 pub const ADD_BET_COMMAND: &str = "bet";
 pub const LIST_BET_COMMAND: &str = "bets";
 pub const SETTLE_BET_COMMAND: &str = "settle";
+pub const EDIT_BET_COMMAND: &str = "edit_bet";
 pub const ATTENDANCE_BET_COMMAND: &str = "attendance";
 pub const HELP_COMMAND: &str = "pog_help";
 pub const ADMIN_COMMAND: &str = "pog_admin";
 pub const WHOIS_COMMAND: &str = "whois";
+pub const DEBTS_COMMAND: &str = "debts";
+pub const LEDGER_COMMAND: &str = "ledger";
+pub const BET_STATS_COMMAND: &str = "betstats";
+pub const BET_HISTORY_COMMAND: &str = "bet_history";
+pub const POOL_COMMAND: &str = "pool";
@@ -0,0 +1,464 @@
+use crate::error::Error;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+pub const MAX_CUSTOM_ID_LEN: usize = 100;
+pub const CUSTOM_ID_VERSION: u8 = 1;
+// ids posted before the codec existed, only accepted while ids are unsigned
+pub const LEGACY_CUSTOM_ID_VERSION: u8 = 0;
+
+const SEPARATOR: char = ':';
+const SIGNATURE: char = '#';
+const ESCAPE: char = '\\';
+// bytes of the hmac kept in the id, hex encoded
+const SIGNATURE_BYTES: usize = 8;
+
+static CODEC: OnceLock<CustomIdCodec> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Answer,
+    Amend,
+    Offer,
+    Pool,
+    Verify,
+    Payment,
+    SettleUp,
+    Settle,
+    Outcome,
+    Remind,
+    BetPage,
+    Attendance,
+    WagerModal,
+    OpenOfferModal,
+    EditModal,
+    PoolModal,
+    SetUserModal,
+}
+
+const ROUTES: [Route; 17] = [
+    Route::Answer,
+    Route::Amend,
+    Route::Offer,
+    Route::Pool,
+    Route::Verify,
+    Route::Payment,
+    Route::SettleUp,
+    Route::Settle,
+    Route::Outcome,
+    Route::Remind,
+    Route::BetPage,
+    Route::Attendance,
+    Route::WagerModal,
+    Route::OpenOfferModal,
+    Route::EditModal,
+    Route::PoolModal,
+    Route::SetUserModal,
+];
+
+impl Route {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Route::Answer => "answer",
+            Route::Amend => "amend",
+            Route::Offer => "offer",
+            Route::Pool => "pool",
+            Route::Verify => "verify",
+            Route::Payment => "payment",
+            Route::SettleUp => "settleup",
+            Route::Settle => "settle",
+            Route::Outcome => "outcome",
+            Route::Remind => "remind",
+            Route::BetPage => "betpage",
+            Route::Attendance => "attendance",
+            Route::WagerModal => "wager",
+            Route::OpenOfferModal => "open_offer",
+            Route::EditModal => "edit",
+            Route::PoolModal => "pool_modal",
+            Route::SetUserModal => "setuser",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        ROUTES.into_iter().find(|route| route.tag() == tag)
+    }
+}
+
+// a component or modal custom_id: `tag:version:field:field` with an optional `#signature`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomId {
+    pub route: Route,
+    pub version: u8,
+    fields: Vec<String>,
+}
+
+impl CustomId {
+    pub fn new(route: Route) -> Self {
+        Self {
+            route,
+            version: CUSTOM_ID_VERSION,
+            fields: vec![],
+        }
+    }
+
+    pub fn with(mut self, field: impl ToString) -> Self {
+        self.fields.push(field.to_string());
+        self
+    }
+
+    pub fn field(&self, index: usize) -> Result<&str, Error> {
+        match self.fields.get(index) {
+            Some(field) => Ok(field),
+            None => Err(Error::CustomId(format!(
+                "{} custom id is missing field {}",
+                self.route.tag(),
+                index
+            ))),
+        }
+    }
+
+    pub fn parse_field<T: FromStr>(&self, index: usize) -> Result<T, Error> {
+        let field = self.field(index)?;
+        match field.parse::<T>() {
+            Ok(value) => Ok(value),
+            Err(_) => Err(Error::CustomId(format!(
+                "unable to parse field {} of the {} custom id: {}",
+                index,
+                self.route.tag(),
+                field
+            ))),
+        }
+    }
+
+    pub fn encode(&self) -> Result<String, Error> {
+        codec().encode(self)
+    }
+
+    pub fn decode(value: &str) -> Result<Self, Error> {
+        codec().decode(value)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CustomIdCodec {
+    key: Option<Vec<u8>>,
+}
+
+impl CustomIdCodec {
+    pub fn signed(key: &[u8]) -> Self {
+        Self {
+            key: Some(key.to_vec()),
+        }
+    }
+
+    pub fn encode(&self, id: &CustomId) -> Result<String, Error> {
+        let mut encoded = format!("{}{}{}", id.route.tag(), SEPARATOR, id.version);
+        for field in &id.fields {
+            encoded.push(SEPARATOR);
+            encoded.push_str(&escape(field));
+        }
+        if let Some(signature) = self.signature(&encoded) {
+            encoded.push(SIGNATURE);
+            encoded.push_str(&signature);
+        }
+        match encoded.chars().count() > MAX_CUSTOM_ID_LEN {
+            true => Err(Error::CustomId(format!(
+                "{} custom id is longer than {} characters",
+                id.route.tag(),
+                MAX_CUSTOM_ID_LEN
+            ))),
+            false => Ok(encoded),
+        }
+    }
+
+    pub fn decode(&self, value: &str) -> Result<CustomId, Error> {
+        let parts = split_unescaped(value, SIGNATURE, 2);
+        let (body, signature) = (parts[0], parts.get(1).copied());
+        let mut parts = split_unescaped(body, SEPARATOR, usize::MAX).into_iter();
+        let route = parts.next().and_then(Route::from_tag);
+        let version = parts.next().and_then(|version| version.parse::<u8>().ok());
+        let (route, version) = match (route, version) {
+            (Some(route), Some(CUSTOM_ID_VERSION)) => (route, CUSTOM_ID_VERSION),
+            (Some(route), Some(version)) => {
+                return Err(Error::CustomId(format!(
+                    "{} custom id has unsupported version {}",
+                    route.tag(),
+                    version
+                )))
+            }
+            _ if self.key.is_none() && signature.is_none() => return decode_legacy(value),
+            _ => {
+                return Err(Error::CustomId(format!(
+                    "unrecognized custom id: {}",
+                    value
+                )))
+            }
+        };
+        if let Some(mac) = self.mac(body) {
+            let verified = signature
+                .and_then(decode_signature)
+                .is_some_and(|bytes| mac.verify_truncated_left(&bytes).is_ok());
+            if !verified {
+                return Err(Error::CustomId(format!(
+                    "{} custom id has an invalid signature",
+                    route.tag()
+                )));
+            }
+        }
+        Ok(CustomId {
+            route,
+            version,
+            fields: parts.map(unescape).collect(),
+        })
+    }
+
+    fn signature(&self, body: &str) -> Option<String> {
+        let digest = self.mac(body)?.finalize().into_bytes();
+        Some(
+            digest[..SIGNATURE_BYTES]
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+        )
+    }
+
+    fn mac(&self, body: &str) -> Option<Hmac<Sha256>> {
+        let key = self.key.as_ref()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts keys of any length");
+        mac.update(body.as_bytes());
+        Some(mac)
+    }
+}
+
+// only a full length signature is accepted, the mac compares it in constant time
+fn decode_signature(signature: &str) -> Option<Vec<u8>> {
+    if signature.len() != SIGNATURE_BYTES * 2 || !signature.is_ascii() {
+        return None;
+    }
+    (0..signature.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&signature[i..i + 2], 16).ok())
+        .collect()
+}
+
+// set once at startup, ids are unsigned until then
+pub fn set_signing_key(key: &[u8]) -> Result<(), Error> {
+    CODEC
+        .set(CustomIdCodec::signed(key))
+        .map_err(|_| Error::CustomId("custom id codec was already in use".to_string()))
+}
+
+fn codec() -> &'static CustomIdCodec {
+    CODEC.get_or_init(CustomIdCodec::default)
+}
+
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        if c == SEPARATOR || c == SIGNATURE || c == ESCAPE {
+            escaped.push(ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+// splits on the delimiter unless it is escaped, leaving the parts escaped
+fn split_unescaped(value: &str, delimiter: char, limit: usize) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match (escaped, c) {
+            (true, _) => escaped = false,
+            (false, ESCAPE) => escaped = true,
+            (false, c) if c == delimiter && parts.len() + 1 < limit => {
+                parts.push(&value[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+// the formats posted before the codec: `wager|id|name`, `setuser|id`, `settle` and `nobet_109`
+fn decode_legacy(value: &str) -> Result<CustomId, Error> {
+    let legacy = |route: Route, fields: Vec<&str>| CustomId {
+        route,
+        version: LEGACY_CUSTOM_ID_VERSION,
+        fields: fields.into_iter().map(str::to_string).collect(),
+    };
+    if let Some(rest) = value.strip_prefix("wager|") {
+        return Ok(match rest.split_once('|') {
+            Some((user_id, name)) if user_id.parse::<u64>().is_ok() => {
+                legacy(Route::WagerModal, vec![name, user_id])
+            }
+            _ => legacy(Route::WagerModal, vec![rest]),
+        });
+    }
+    if let Some(user_id) = value.strip_prefix("setuser|") {
+        return Ok(legacy(Route::SetUserModal, vec![user_id]));
+    }
+    if value == "settle" {
+        return Ok(legacy(Route::Settle, vec![]));
+    }
+    match value.split_once('_') {
+        Some((designation @ ("offering" | "accepting" | "nobet" | "cancel"), id)) => {
+            Ok(legacy(Route::Outcome, vec![designation, id]))
+        }
+        _ => Err(Error::CustomId(format!(
+            "unrecognized custom id: {}",
+            value
+        ))),
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let codec = CustomIdCodec::default();
+    let id = CustomId::new(Route::WagerModal)
+        .with("Harx: the | best_one#1 \\o/")
+        .with(695398918694895710u64);
+    let encoded = codec.encode(&id).unwrap();
+    assert_eq!(
+        "wager:1:Harx\\: the | best_one\\#1 \\\\o/:695398918694895710",
+        encoded
+    );
+    let decoded = codec.decode(&encoded).unwrap();
+    assert_eq!(id, decoded);
+    assert_eq!("Harx: the | best_one#1 \\o/", decoded.field(0).unwrap());
+    assert_eq!(
+        695398918694895710u64,
+        decoded.parse_field::<u64>(1).unwrap()
+    );
+    assert!(decoded.field(2).is_err());
+    assert!(decoded.parse_field::<u64>(0).is_err());
+}
+
+#[test]
+fn test_empty_fields() {
+    let codec = CustomIdCodec::default();
+    let id = CustomId::new(Route::BetPage)
+        .with(1)
+        .with("all")
+        .with("")
+        .with("");
+    let encoded = codec.encode(&id).unwrap();
+    assert_eq!("betpage:1:1:all::", encoded);
+    assert_eq!(id, codec.decode(&encoded).unwrap());
+    assert_eq!(
+        CustomId::new(Route::Settle),
+        codec.decode("settle:1").unwrap()
+    );
+}
+
+#[test]
+fn test_unsupported_version() {
+    let codec = CustomIdCodec::default();
+    assert_eq!(
+        Err(Error::CustomId(
+            "answer custom id has unsupported version 2".to_string()
+        )),
+        codec.decode("answer:2:accept:109")
+    );
+    assert!(codec.decode("settle:0").is_err());
+    assert!(CustomIdCodec::signed(b"secret")
+        .decode("answer:2:accept:109")
+        .is_err());
+}
+
+#[test]
+fn test_too_long() {
+    let codec = CustomIdCodec::default();
+    let id = CustomId::new(Route::WagerModal).with("x".repeat(93));
+    assert_eq!(
+        Err(Error::CustomId(
+            "wager custom id is longer than 100 characters".to_string()
+        )),
+        codec.encode(&id)
+    );
+    let id = CustomId::new(Route::WagerModal).with("x".repeat(92));
+    assert_eq!(100, codec.encode(&id).unwrap().len());
+}
+
+#[test]
+fn test_signed() {
+    let codec = CustomIdCodec::signed(b"secret");
+    let id = CustomId::new(Route::Answer).with("accept").with(109);
+    let encoded = codec.encode(&id).unwrap();
+    assert!(encoded.starts_with("answer:1:accept:109#"));
+    assert_eq!(id, codec.decode(&encoded).unwrap());
+
+    let forged = encoded.replace("109", "110");
+    assert_eq!(
+        Err(Error::CustomId(
+            "answer custom id has an invalid signature".to_string()
+        )),
+        codec.decode(&forged)
+    );
+    assert!(codec.decode("answer:1:accept:110").is_err());
+    assert!(codec.decode("nobet_110").is_err());
+    assert!(CustomIdCodec::signed(b"other").decode(&encoded).is_err());
+
+    let (body, signature) = encoded.split_once('#').unwrap();
+    let truncated = format!("{}#{}", body, &signature[..4]);
+    assert!(codec.decode(&truncated).is_err());
+    assert!(codec.decode(&format!("{}#", body)).is_err());
+    assert!(codec
+        .decode(&format!("{}#{}", body, "zz".repeat(8)))
+        .is_err());
+}
+
+#[test]
+fn test_legacy() {
+    let codec = CustomIdCodec::default();
+    let legacy = |route: Route, fields: Vec<&str>| CustomId {
+        route,
+        version: LEGACY_CUSTOM_ID_VERSION,
+        fields: fields.into_iter().map(str::to_string).collect(),
+    };
+    assert_eq!(
+        legacy(Route::Outcome, vec!["nobet", "127"]),
+        codec.decode("nobet_127").unwrap()
+    );
+    assert_eq!(
+        legacy(Route::Outcome, vec!["accepting", "109"]),
+        codec.decode("accepting_109").unwrap()
+    );
+    assert_eq!(
+        legacy(Route::WagerModal, vec!["Cisco", "1050119194533961860"]),
+        codec.decode("wager|1050119194533961860|Cisco").unwrap()
+    );
+    assert_eq!(
+        legacy(Route::WagerModal, vec!["Cisco"]),
+        codec.decode("wager|Cisco").unwrap()
+    );
+    assert_eq!(
+        legacy(Route::SetUserModal, vec!["1166787343743725609"]),
+        codec.decode("setuser|1166787343743725609").unwrap()
+    );
+    assert_eq!(
+        legacy(Route::Settle, vec![]),
+        codec.decode("settle").unwrap()
+    );
+    assert!(codec.decode("Woody").is_err());
+    // never posted before the codec, so there's nothing to keep decoding
+    assert!(codec.decode("answer_accept_109").is_err());
+    assert!(codec.decode("betpage|1|all||||Woody").is_err());
+}
@@ -1,7 +1,7 @@
 use crate::error::Error;
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscordId(u64);
 
 impl DiscordId {
@@ -65,37 +65,3 @@ fn test_attempt_from_str() {
         DiscordId::attempt_from_str(" <@11> \n")
     );
 }
-
-pub fn combine_user_payload(user: &str, id: Option<DiscordId>) -> String {
-    match id {
-        Some(id) => format!("{}|{}", id.0, user),
-        None => user.to_string(),
-    }
-}
-pub fn split_combined_user_payload(value: &str) -> (String, Option<DiscordId>) {
-    if let Some(delim_pos) = value.find('|') {
-        let id_sec = &value[..delim_pos];
-        return match id_sec.parse::<u64>() {
-            Ok(id) => (value[delim_pos + 1..].to_string(), Some(DiscordId(id))),
-            Err(_) => (value.to_string(), None),
-        };
-    };
-    (value.to_string(), None)
-}
-#[test]
-fn test_combined_user_payload() {
-    assert_eq!("11|Harx", combine_user_payload("Harx", Some(DiscordId(11))));
-    assert_eq!("Harx", combine_user_payload("Harx", None));
-    assert_eq!(
-        ("Harx".to_string(), Some(DiscordId(11))),
-        split_combined_user_payload("11|Harx")
-    );
-    assert_eq!(
-        ("Harx".to_string(), None),
-        split_combined_user_payload("Harx")
-    );
-    assert_eq!(
-        ("Harx|orsumthin".to_string(), None),
-        split_combined_user_payload("Harx|orsumthin")
-    );
-}
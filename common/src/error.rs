@@ -1,7 +1,11 @@
+use crate::wager::Wager;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Error {
     Unexpected(String),
     Database(String),
+    Conflict(Box<Wager>),
+    CustomId(String),
 }
 
 impl From<&str> for Error {
@@ -15,6 +15,8 @@ pub struct CreateMessage {
     pub channel_id: String,
     pub message: String,
     pub message_reference: Option<MessageReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<serde_json::Value>,
 }
 
 // https://discord.com/developers/docs/resources/channel#message-reference-object-message-reference-structure
@@ -1,5 +1,6 @@
 pub use commands::{
-    ADD_BET_COMMAND, ADMIN_COMMAND, ATTENDANCE_BET_COMMAND, HELP_COMMAND, LIST_BET_COMMAND,
+    ADD_BET_COMMAND, ADMIN_COMMAND, ATTENDANCE_BET_COMMAND, BET_HISTORY_COMMAND, BET_STATS_COMMAND,
+    DEBTS_COMMAND, EDIT_BET_COMMAND, HELP_COMMAND, LEDGER_COMMAND, LIST_BET_COMMAND, POOL_COMMAND,
     SETTLE_BET_COMMAND, WHOIS_COMMAND,
 };
 pub use discord_client::{discord_api_root, discord_headers};
@@ -11,9 +12,11 @@ pub use events::{
 pub const DISCORD_API_ROOT: &str = "https://discord.com/api/v10";
 
 mod commands;
+pub mod custom_id;
 mod discord_client;
 pub mod discord_id;
 pub mod error;
 mod events;
+pub mod pool;
 pub mod repos;
 pub mod wager;
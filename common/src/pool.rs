@@ -0,0 +1,261 @@
+use std::fmt::{Display, Formatter};
+
+use crate::discord_id::DiscordId;
+use crate::wager::Stake;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pool {
+    pub pool_id: u32,
+    pub time: String,
+    pub created_by: DiscordId,
+    pub question: String,
+    pub buy_in: Stake,
+    pub options: Vec<String>,
+    pub status: PoolStatus,
+    pub winning_option: Option<String>,
+    pub entries: Vec<PoolEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolEntry {
+    pub user_id: DiscordId,
+    pub name: String,
+    pub pick: String,
+    pub buy_in: Stake,
+}
+
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum PoolStatus {
+    Open = 0,
+    Settled = 1,
+}
+
+impl PoolStatus {
+    pub fn as_i16(&self) -> i16 {
+        *self as i16
+    }
+    pub fn from_i16(val: i16) -> Self {
+        match val {
+            0 => PoolStatus::Open,
+            1 => PoolStatus::Settled,
+            v => panic!("attempt to convert {} to PoolStatus", v),
+        }
+    }
+}
+
+impl Pool {
+    pub fn entry(&self, user_id: &DiscordId) -> Option<&PoolEntry> {
+        self.entries.iter().find(|entry| &entry.user_id == user_id)
+    }
+
+    pub fn winners(&self) -> Vec<&PoolEntry> {
+        match &self.winning_option {
+            Some(winning_option) => self
+                .entries
+                .iter()
+                .filter(|entry| entry.pick.eq_ignore_ascii_case(winning_option))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    // nobody picked the winner, so everyone got their buy-in back
+    pub fn is_refunded(&self) -> bool {
+        self.status == PoolStatus::Settled && self.winners().is_empty()
+    }
+
+    pub fn pot(&self) -> Stake {
+        if self.entries.is_empty() {
+            return with_units(&self.buy_in, 0);
+        }
+        let buy_ins: Vec<&Stake> = self.entries.iter().map(|entry| &entry.buy_in).collect();
+        total(&buy_ins).unwrap_or_else(|| {
+            let buy_ins: Vec<String> = buy_ins.iter().map(|stake| stake.to_string()).collect();
+            Stake::Unparsed(buy_ins.join(" + "))
+        })
+    }
+
+    // the pot is split between the winners in proportion to their buy-ins, with any
+    // remainder handed out one unit at a time in the order the winners joined
+    pub fn payouts(&self) -> Vec<(DiscordId, Stake)> {
+        let winners = self.winners();
+        let staked: Vec<i64> = winners.iter().filter_map(|e| units(&e.buy_in)).collect();
+        let pot = self.pot();
+        let pot_units = match units(&pot) {
+            Some(pot_units) if staked.len() == winners.len() => pot_units,
+            _ => {
+                return winners
+                    .iter()
+                    .map(|entry| {
+                        let share = format!("1/{} of {}", winners.len(), pot);
+                        (entry.user_id.clone(), Stake::Unparsed(share))
+                    })
+                    .collect()
+            }
+        };
+        let total_staked: i64 = staked.iter().sum();
+        let mut shares: Vec<i64> = staked
+            .iter()
+            .map(|units| (pot_units as i128 * *units as i128 / total_staked as i128) as i64)
+            .collect();
+        let mut remainder = pot_units - shares.iter().sum::<i64>();
+        for share in shares.iter_mut() {
+            if remainder == 0 {
+                break;
+            }
+            *share += 1;
+            remainder -= 1;
+        }
+        winners
+            .iter()
+            .zip(shares)
+            .map(|(entry, share)| (entry.user_id.clone(), with_units(&pot, share)))
+            .collect()
+    }
+
+    pub fn simplified_string(&self) -> String {
+        format!(
+            "Pool {}: {}, buy-in: {}, {} in, pot: {}",
+            self.pool_id,
+            self.question,
+            self.buy_in,
+            self.entries.len(),
+            self.pot()
+        )
+    }
+}
+
+impl Display for Pool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.simplified_string())?;
+        for option in self.options.iter() {
+            let picked: Vec<String> = self
+                .entries
+                .iter()
+                .filter(|entry| entry.pick.eq_ignore_ascii_case(option))
+                .map(|entry| entry.user_id.to_string())
+                .collect();
+            match picked.is_empty() {
+                true => write!(f, "\n- {}", option)?,
+                false => write!(f, "\n- {}: {}", option, picked.join(", "))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn total(stakes: &[&Stake]) -> Option<Stake> {
+    let first = stakes.first()?;
+    let mut sum = 0;
+    for stake in stakes {
+        match (first, stake) {
+            (
+                Stake::Money { currency, .. },
+                Stake::Money {
+                    amount,
+                    currency: other,
+                },
+            ) if currency == other => sum += amount,
+            (
+                Stake::Item { item, .. },
+                Stake::Item {
+                    quantity,
+                    item: other,
+                },
+            ) if item == other => sum += *quantity as i64,
+            _ => return None,
+        }
+    }
+    Some(with_units(first, sum))
+}
+
+fn units(stake: &Stake) -> Option<i64> {
+    match stake {
+        Stake::Money { amount, .. } => Some(*amount),
+        Stake::Item { quantity, .. } => Some(*quantity as i64),
+        Stake::Unparsed(_) => None,
+    }
+}
+
+fn with_units(stake: &Stake, units: i64) -> Stake {
+    match stake {
+        Stake::Money { currency, .. } => Stake::Money {
+            amount: units,
+            currency: *currency,
+        },
+        Stake::Item { item, .. } => Stake::Item {
+            quantity: units as u32,
+            item: item.to_string(),
+        },
+        Stake::Unparsed(value) => Stake::Unparsed(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(buy_ins: &[(i64, &str)]) -> Pool {
+        Pool {
+            pool_id: 1,
+            time: "".to_string(),
+            created_by: 1.into(),
+            question: "Who wins the Super Bowl?".to_string(),
+            buy_in: "$10".into(),
+            options: vec!["Chiefs".to_string(), "Eagles".to_string()],
+            status: PoolStatus::Settled,
+            winning_option: Some("Chiefs".to_string()),
+            entries: buy_ins
+                .iter()
+                .map(|(user_id, pick)| PoolEntry {
+                    user_id: (*user_id).into(),
+                    name: format!("user {}", user_id),
+                    pick: pick.to_string(),
+                    buy_in: "$10".into(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_pot() {
+        let pool = pool(&[(1, "Chiefs"), (2, "Eagles"), (3, "Eagles")]);
+        assert_eq!(Stake::from("$30"), pool.pot());
+        assert_eq!(
+            "Pool 1: Who wins the Super Bowl?, buy-in: $10, 3 in, pot: $30\n- Chiefs: <@1>\n- Eagles: <@2>, <@3>",
+            pool.to_string()
+        );
+    }
+
+    #[test]
+    fn test_payouts() {
+        let pool = pool(&[(1, "Chiefs"), (2, "Eagles"), (3, "chiefs"), (4, "Eagles")]);
+        assert_eq!(
+            vec![
+                (1.into(), Stake::from("$20")),
+                (3.into(), Stake::from("$20"))
+            ],
+            pool.payouts()
+        );
+    }
+
+    #[test]
+    fn test_payouts_with_remainder() {
+        let pool = pool(&[(1, "Chiefs"), (2, "Chiefs"), (3, "Chiefs"), (4, "Eagles")]);
+        assert_eq!(
+            vec![
+                (1.into(), Stake::from("$13.34")),
+                (2.into(), Stake::from("$13.33")),
+                (3.into(), Stake::from("$13.33"))
+            ],
+            pool.payouts()
+        );
+    }
+
+    #[test]
+    fn test_payouts_without_winners() {
+        let pool = pool(&[(1, "Eagles"), (2, "Eagles")]);
+        assert!(pool.payouts().is_empty());
+        assert!(pool.is_refunded());
+    }
+}
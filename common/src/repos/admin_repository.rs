@@ -1,12 +1,80 @@
+use crate::discord_id::DiscordId;
 use crate::error::Error;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AdminSettings {
     pub welcome_channel: String,
     pub ff_year: u16,
     pub ff_week: u8,
+    #[serde(default)]
+    pub admin_channel: String,
+    #[serde(default)]
+    pub betting_channel: String,
+    #[serde(default = "default_reminder_days")]
+    pub reminder_days: u16,
+    #[serde(default = "default_offer_hours")]
+    pub offer_hours: u16,
+    #[serde(default)]
+    pub max_stake: Option<String>,
+    #[serde(default)]
+    pub max_open_wagers: u16,
+    #[serde(default)]
+    pub max_exposure: Option<String>,
+    #[serde(default)]
+    pub limit_exempt: Vec<String>,
+    #[serde(default)]
+    pub rsvp_channel: String,
+    // day of the week the rsvp post goes out, 1 for Monday through 7 for Sunday, 0 for never
+    #[serde(default)]
+    pub rsvp_day: u8,
+    // voice channel where being present during a game window counts as attendance
+    #[serde(default)]
+    pub watch_channel: String,
+    // game days, 1 for Monday through 7 for Sunday
+    #[serde(default)]
+    pub watch_days: Vec<u8>,
+    // local hours the game window starts and ends
+    #[serde(default)]
+    pub watch_start: u8,
+    #[serde(default)]
+    pub watch_end: u8,
+    #[serde(default = "default_watch_minutes")]
+    pub watch_minutes: u16,
+}
+
+pub const DEFAULT_REMINDER_DAYS: u16 = 7;
+pub const DEFAULT_OFFER_HOURS: u16 = 24;
+pub const DEFAULT_WATCH_MINUTES: u16 = 30;
+
+fn default_reminder_days() -> u16 {
+    DEFAULT_REMINDER_DAYS
+}
+
+fn default_offer_hours() -> u16 {
+    DEFAULT_OFFER_HOURS
+}
+
+fn default_watch_minutes() -> u16 {
+    DEFAULT_WATCH_MINUTES
+}
+
+// a season starts with the football season in September, so games after new year belong to the
+// year before
+pub fn season_of(date: NaiveDate) -> u16 {
+    match date.month() {
+        9..=12 => date.year() as u16,
+        _ => date.year() as u16 - 1,
+    }
+}
+
+// the same defaults as settings saved before a field existed, not zero
+impl Default for AdminSettings {
+    fn default() -> Self {
+        Self::new(String::new(), 0, 0)
+    }
 }
 
 impl AdminSettings {
@@ -15,8 +83,48 @@ impl AdminSettings {
             welcome_channel,
             ff_year,
             ff_week,
+            admin_channel: String::new(),
+            betting_channel: String::new(),
+            reminder_days: DEFAULT_REMINDER_DAYS,
+            offer_hours: DEFAULT_OFFER_HOURS,
+            max_stake: None,
+            max_open_wagers: 0,
+            max_exposure: None,
+            limit_exempt: vec![],
+            rsvp_channel: String::new(),
+            rsvp_day: 0,
+            watch_channel: String::new(),
+            watch_days: vec![],
+            watch_start: 0,
+            watch_end: 0,
+            watch_minutes: DEFAULT_WATCH_MINUTES,
+        }
+    }
+
+    pub fn current_season(&self, today: NaiveDate) -> u16 {
+        match self.ff_year {
+            0 => season_of(today),
+            year => year,
         }
     }
+
+    // when presence in the watch channel counts on a given day, if it is a game day
+    pub fn watch_window(&self, date: NaiveDate) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let game_day = self
+            .watch_days
+            .contains(&(date.weekday().number_from_monday() as u8));
+        if self.watch_channel.is_empty() || !game_day || self.watch_start >= self.watch_end {
+            return None;
+        }
+        Some((
+            date.and_hms_opt(self.watch_start as u32, 0, 0)?,
+            date.and_hms_opt(0, 0, 0)? + chrono::Duration::hours(self.watch_end as i64),
+        ))
+    }
+
+    pub fn is_limit_exempt(&self, user_id: &DiscordId) -> bool {
+        self.limit_exempt.contains(&user_id.str_value())
+    }
 }
 
 pub trait AdminRepository: Send + Sync {
@@ -57,14 +165,30 @@ mod tests {
 
     #[test]
     fn test_serialization() {
-        let mut settings = AdminSettings::default();
-        settings.ff_year = 2024;
-        settings.ff_week = 18;
-        settings.welcome_channel = "1234567890".to_string();
+        let settings = AdminSettings {
+            welcome_channel: "1234567890".to_string(),
+            ff_year: 2024,
+            ff_week: 18,
+            admin_channel: "9876543210".to_string(),
+            betting_channel: "5555555555".to_string(),
+            reminder_days: 3,
+            offer_hours: 12,
+            max_stake: Some("$50".to_string()),
+            max_open_wagers: 5,
+            max_exposure: None,
+            limit_exempt: vec!["1050119194533961860".to_string()],
+            rsvp_channel: "4444444444".to_string(),
+            rsvp_day: 2,
+            watch_channel: "3333333333".to_string(),
+            watch_days: vec![4, 7],
+            watch_start: 13,
+            watch_end: 24,
+            watch_minutes: 45,
+        };
         let ser = serde_json::to_string(&settings).unwrap();
         assert_eq!(
             ser,
-            r##"{"welcome_channel":"1234567890","ff_year":2024,"ff_week":18}"##
+            r##"{"welcome_channel":"1234567890","ff_year":2024,"ff_week":18,"admin_channel":"9876543210","betting_channel":"5555555555","reminder_days":3,"offer_hours":12,"max_stake":"$50","max_open_wagers":5,"max_exposure":null,"limit_exempt":["1050119194533961860"],"rsvp_channel":"4444444444","rsvp_day":2,"watch_channel":"3333333333","watch_days":[4,7],"watch_start":13,"watch_end":24,"watch_minutes":45}"##
         );
         let des: AdminSettings = serde_json::from_str(&ser).unwrap();
         assert_eq!(
@@ -72,8 +196,79 @@ mod tests {
             AdminSettings {
                 ff_week: 18,
                 ff_year: 2024,
-                welcome_channel: "1234567890".to_string()
+                welcome_channel: "1234567890".to_string(),
+                admin_channel: "9876543210".to_string(),
+                betting_channel: "5555555555".to_string(),
+                reminder_days: 3,
+                offer_hours: 12,
+                max_stake: Some("$50".to_string()),
+                max_open_wagers: 5,
+                max_exposure: None,
+                limit_exempt: vec!["1050119194533961860".to_string()],
+                rsvp_channel: "4444444444".to_string(),
+                rsvp_day: 2,
+                watch_channel: "3333333333".to_string(),
+                watch_days: vec![4, 7],
+                watch_start: 13,
+                watch_end: 24,
+                watch_minutes: 45,
             }
         )
     }
+
+    #[test]
+    fn test_default() {
+        let settings = AdminSettings::default();
+        assert_eq!(DEFAULT_REMINDER_DAYS, settings.reminder_days);
+        assert_eq!(DEFAULT_OFFER_HOURS, settings.offer_hours);
+        assert_eq!(DEFAULT_WATCH_MINUTES, settings.watch_minutes);
+        let des: AdminSettings =
+            serde_json::from_str(r#"{"welcome_channel":"","ff_year":0,"ff_week":0}"#).unwrap();
+        assert_eq!(settings, des);
+    }
+
+    #[test]
+    fn test_current_season() {
+        let season = |y, m, d| season_of(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        assert_eq!(2024, season(2024, 9, 5));
+        assert_eq!(2024, season(2025, 1, 5));
+        assert_eq!(2024, season(2025, 2, 9));
+        let mut settings = AdminSettings::default();
+        let today = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        assert_eq!(2025, settings.current_season(today));
+        settings.ff_year = 2024;
+        assert_eq!(2024, settings.current_season(today));
+    }
+
+    #[test]
+    fn test_watch_window() {
+        let mut settings = AdminSettings::new(String::new(), 2024, 12);
+        let sunday = NaiveDate::from_ymd_opt(2024, 11, 24).unwrap();
+        settings.watch_days = vec![7];
+        settings.watch_start = 13;
+        settings.watch_end = 24;
+        assert_eq!(None, settings.watch_window(sunday));
+        settings.watch_channel = "3333333333".to_string();
+        assert_eq!(
+            Some((
+                sunday.and_hms_opt(13, 0, 0).unwrap(),
+                sunday.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap()
+            )),
+            settings.watch_window(sunday)
+        );
+        assert_eq!(None, settings.watch_window(sunday.pred_opt().unwrap()));
+    }
+
+    #[test]
+    fn test_deserialization_without_admin_channel() {
+        let des: AdminSettings =
+            serde_json::from_str(r##"{"welcome_channel":"","ff_year":2024,"ff_week":18}"##)
+                .unwrap();
+        assert_eq!(des, AdminSettings::new(String::new(), 2024, 18));
+    }
+
+    #[tokio::test]
+    async fn test_in_mem_conformance() {
+        crate::repos::conformance::admin_repository(&InMemAdminRepository::default()).await;
+    }
 }
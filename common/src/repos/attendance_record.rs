@@ -13,13 +13,40 @@ pub struct AttendanceRecord {
 }
 
 impl AttendanceRecords {
-    pub fn position_and_values(&self, user_id: &DiscordId) -> Option<(u8, AttendanceRecord)> {
-        for (i, record) in self.0.iter().enumerate() {
-            if &record.owner_id == user_id {
-                return Some((i as u8, record.clone()));
+    // how many teams have a better record, so teams with the same record share a rank
+    pub fn rank_and_values(&self, user_id: &DiscordId) -> Option<(usize, AttendanceRecord)> {
+        let record = self.0.iter().find(|record| &record.owner_id == user_id)?;
+        Some((self.rank(record), record.clone()))
+    }
+
+    pub fn rank(&self, record: &AttendanceRecord) -> usize {
+        self.0
+            .iter()
+            .filter(|other| (other.weeks, other.games) > (record.weeks, record.games))
+            .count()
+    }
+}
+
+// consecutive weeks attended, where weeks without any attendance taken don't break a streak
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AttendanceStreak {
+    pub current: u8,
+    pub longest: u8,
+}
+
+impl AttendanceStreak {
+    pub fn from_weeks(taken: &[u8], attended: &[u8]) -> Self {
+        let mut streak = Self::default();
+        for week in taken {
+            match attended.contains(week) {
+                true => {
+                    streak.current += 1;
+                    streak.longest = streak.longest.max(streak.current);
+                }
+                false => streak.current = 0,
             }
         }
-        None
+        streak
     }
 }
 
@@ -54,6 +81,62 @@ impl From<&PgRow> for AttendanceRecord {
     }
 }
 
+pub const RSVP_YES: &str = "\u{2705}";
+pub const RSVP_NO: &str = "\u{274c}";
+
+// whether a reaction on an rsvp post says the manager is coming
+pub fn rsvp_response(emoji: &str) -> Option<bool> {
+    match emoji {
+        RSVP_YES => Some(true),
+        RSVP_NO => Some(false),
+        _ => None,
+    }
+}
+
+// a post asking who is coming on a game day, without a message id until it has been sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct RsvpPost {
+    pub season: u16,
+    pub date: String,
+    pub channel_id: String,
+    pub content: String,
+    pub message_id: Option<String>,
+}
+
+impl From<&PgRow> for RsvpPost {
+    fn from(row: &PgRow) -> Self {
+        let season: i32 = row.get("season");
+        Self {
+            season: season as u16,
+            date: row.get("date"),
+            channel_id: row.get("channel_id"),
+            content: row.get("content"),
+            message_id: row.get("message_id"),
+        }
+    }
+}
+
+// game days a manager said they were coming to, and how many of those they showed up for
+#[derive(Debug, Clone, PartialEq)]
+pub struct RsvpRecord {
+    pub owner_id: DiscordId,
+    pub coming: u8,
+    pub showed_up: u8,
+}
+
+impl From<&PgRow> for RsvpRecord {
+    fn from(row: &PgRow) -> Self {
+        let owner: i64 = row.get("owner");
+        let coming: i64 = row.get("coming");
+        let showed_up: i64 = row.get("showed_up");
+        Self {
+            owner_id: owner.into(),
+            coming: coming as u8,
+            showed_up: showed_up as u8,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct WeeklyAttendanceRecord {
     pub interested_owner: Option<bool>,
@@ -71,19 +154,27 @@ impl From<Vec<(String, Vec<String>)>> for WeeklyAttendanceRecord {
 
 impl WeeklyAttendanceRecord {
     pub fn from_query_result(user_id: &Option<DiscordId>, rows: Vec<PgRow>) -> Self {
+        Self::from_rows(
+            user_id,
+            rows.into_iter().map(|row| {
+                let owner: i64 = row.get("owner");
+                (row.get("date"), owner.into(), row.get("owner_name"))
+            }),
+        )
+    }
+
+    // rows of (date, owner, owner_name), ordered by date
+    pub fn from_rows(
+        user_id: &Option<DiscordId>,
+        rows: impl IntoIterator<Item = (String, DiscordId, String)>,
+    ) -> Self {
         let mut attendance: Vec<(String, Vec<String>)> = vec![];
         let mut last_date = String::new();
         let mut current_attendees: Vec<String> = Vec::new();
         let mut interested_owner = user_id.as_ref().map(|_| false);
-        for row in rows {
-            let date: String = row.get("date");
-            let owner_name: String = row.get("owner_name");
-            if let Some(manager_id) = user_id {
-                let owner: i64 = row.get("owner");
-                let owner_id = owner.into();
-                if manager_id == &owner_id {
-                    interested_owner = Some(true);
-                }
+        for (date, owner_id, owner_name) in rows {
+            if user_id.as_ref() == Some(&owner_id) {
+                interested_owner = Some(true);
             }
 
             if date != last_date {
@@ -95,10 +186,63 @@ impl WeeklyAttendanceRecord {
             }
             current_attendees.push(owner_name);
         }
-        attendance.push((last_date, current_attendees));
+        if !current_attendees.is_empty() {
+            attendance.push((last_date, current_attendees));
+        }
         Self {
             interested_owner,
             attendance,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_and_values() {
+        let record = |id: i64, weeks, games| AttendanceRecord {
+            owner_id: id.into(),
+            weeks,
+            games,
+        };
+        let records = AttendanceRecords(vec![
+            record(1, 5, 9),
+            record(2, 4, 6),
+            record(3, 4, 6),
+            record(4, 2, 2),
+        ]);
+        assert_eq!(
+            Some((0, record(1, 5, 9))),
+            records.rank_and_values(&1.into())
+        );
+        assert_eq!(
+            Some((1, record(3, 4, 6))),
+            records.rank_and_values(&3.into())
+        );
+        assert_eq!(
+            Some((3, record(4, 2, 2))),
+            records.rank_and_values(&4.into())
+        );
+        assert_eq!(None, records.rank_and_values(&5.into()));
+    }
+
+    #[test]
+    fn test_streaks() {
+        let streak = |current, longest| AttendanceStreak { current, longest };
+        assert_eq!(streak(0, 0), AttendanceStreak::from_weeks(&[1, 2, 3], &[]));
+        assert_eq!(
+            streak(2, 3),
+            AttendanceStreak::from_weeks(&[1, 2, 3, 4, 6, 7], &[1, 2, 3, 6, 7])
+        );
+        assert_eq!(
+            streak(0, 2),
+            AttendanceStreak::from_weeks(&[1, 2, 3, 5], &[2, 3])
+        );
+        assert_eq!(
+            streak(3, 3),
+            AttendanceStreak::from_weeks(&[2, 3, 5], &[1, 2, 3, 5])
+        );
+    }
+}
@@ -1,44 +1,471 @@
 use crate::discord_id::DiscordId;
 use crate::error::Error;
-use crate::repos::attendance_record::{AttendanceRecords, WeeklyAttendanceRecord};
+use crate::repos::attendance_record::{
+    AttendanceRecord, AttendanceRecords, RsvpPost, RsvpRecord, WeeklyAttendanceRecord,
+};
 use std::future::Future;
+use std::sync::{Arc, Mutex};
 
 pub trait AttendanceRepository {
-    fn combined_attendance(&self) -> impl Future<Output = Result<AttendanceRecords, Error>> + Send;
+    fn combined_attendance(
+        &self,
+        season: u16,
+    ) -> impl Future<Output = Result<AttendanceRecords, Error>> + Send;
     fn week_attendance(
         &self,
+        season: u16,
         week: u8,
         interested_owner: &Option<DiscordId>,
     ) -> impl Future<Output = Result<WeeklyAttendanceRecord, Error>> + Send;
+    fn weeks(&self, season: u16) -> impl Future<Output = Result<Vec<u8>, Error>> + Send;
+    // each week an owner attended, ordered by owner then week
+    fn attended_weeks(
+        &self,
+        season: u16,
+    ) -> impl Future<Output = Result<Vec<(DiscordId, u8)>, Error>> + Send;
+    fn date_attendance(
+        &self,
+        season: u16,
+        date: &str,
+    ) -> impl Future<Output = Result<Vec<DiscordId>, Error>> + Send;
+    // replaces who attended on the date, returning the owners recorded; anyone without a team is
+    // left out
+    fn set_attendance(
+        &self,
+        season: u16,
+        week: u8,
+        date: &str,
+        owners: &[DiscordId],
+    ) -> impl Future<Output = Result<Vec<DiscordId>, Error>> + Send;
+    // adds one owner to the date, false if they were already there or have no team
+    fn mark_attended(
+        &self,
+        season: u16,
+        week: u8,
+        date: &str,
+        owner: &DiscordId,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    // queues a post asking who is coming, false if the game day already has one; a post that was
+    // claimed but never sent is queued again
+    fn request_rsvp_post(
+        &self,
+        post: &RsvpPost,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn pending_rsvp_posts(&self) -> impl Future<Output = Result<Vec<RsvpPost>, Error>> + Send;
+    // takes a pending post to send it, false if it was already taken; a taken post is not
+    // pending again unless it is requested again after sending it failed
+    fn claim_rsvp_post(
+        &self,
+        season: u16,
+        date: &str,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn rsvp_posted(
+        &self,
+        season: u16,
+        date: &str,
+        message_id: &str,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+    // records an answer on an rsvp post, false if the message isn't one or the user has no team
+    fn record_rsvp(
+        &self,
+        message_id: &str,
+        owner: &DiscordId,
+        attending: bool,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    // takes back an answer, leaving any different answer in place
+    fn remove_rsvp(
+        &self,
+        message_id: &str,
+        owner: &DiscordId,
+        attending: bool,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    // only counts game days where attendance was taken
+    fn rsvp_reliability(
+        &self,
+        season: u16,
+    ) -> impl Future<Output = Result<Vec<RsvpRecord>, Error>> + Send;
 }
 
-#[derive(Clone, Debug)]
+// season, week, date and owner, like a row of ff_attendance
+type AttendanceRow = (u16, u8, String, DiscordId);
+
+#[derive(Clone, Debug, Default)]
 pub struct InMemoryAttendanceRepository {
-    pub combined_attendance: AttendanceRecords,
-    pub weekly_attendance: WeeklyAttendanceRecord,
+    teams: Arc<Mutex<Vec<(DiscordId, String)>>>,
+    attendance: Arc<Mutex<Vec<AttendanceRow>>>,
+    rsvp_posts: Arc<Mutex<Vec<RsvpPost>>>,
+    claimed_rsvp_posts: Arc<Mutex<Vec<(u16, String)>>>,
+    rsvps: Arc<Mutex<Vec<(String, DiscordId, bool)>>>,
 }
 
-impl Default for InMemoryAttendanceRepository {
-    fn default() -> Self {
-        let attendance = AttendanceRecords(vec![]);
-        let weekly_attendance = Default::default();
-        Self {
-            combined_attendance: attendance,
-            weekly_attendance,
-        }
+impl InMemoryAttendanceRepository {
+    pub fn add_team(&self, owner: DiscordId, owner_name: &str) {
+        self.teams
+            .lock()
+            .unwrap()
+            .push((owner, owner_name.to_string()));
+    }
+
+    pub fn add_attendance(&self, season: u16, week: u8, date: &str, owner: DiscordId) {
+        self.attendance
+            .lock()
+            .unwrap()
+            .push((season, week, date.to_string(), owner));
     }
 }
 
 impl AttendanceRepository for InMemoryAttendanceRepository {
-    async fn combined_attendance(&self) -> Result<AttendanceRecords, Error> {
-        Ok(self.combined_attendance.clone())
+    async fn combined_attendance(&self, season: u16) -> Result<AttendanceRecords, Error> {
+        let attendance = self.attendance.lock().unwrap();
+        let mut result: Vec<AttendanceRecord> = vec![];
+        for (owner_id, _) in self.teams.lock().unwrap().iter() {
+            let mut weeks: Vec<u8> = attendance
+                .iter()
+                .filter(|(attended_season, _, _, owner)| {
+                    *attended_season == season && owner == owner_id
+                })
+                .map(|(_, week, _, _)| *week)
+                .collect();
+            let games = weeks.len() as u8;
+            weeks.sort();
+            weeks.dedup();
+            result.push(AttendanceRecord {
+                owner_id: owner_id.clone(),
+                weeks: weeks.len() as u8,
+                games,
+            });
+        }
+        result.sort_by_key(|record| {
+            (
+                std::cmp::Reverse((record.weeks, record.games)),
+                record.owner_id.value(),
+            )
+        });
+        Ok(AttendanceRecords(result))
     }
 
     async fn week_attendance(
         &self,
-        _week: u8,
-        _interested_owner: &Option<DiscordId>,
+        season: u16,
+        week: u8,
+        interested_owner: &Option<DiscordId>,
     ) -> Result<WeeklyAttendanceRecord, Error> {
-        Ok(self.weekly_attendance.clone())
+        let teams = self.teams.lock().unwrap();
+        let mut rows: Vec<(String, DiscordId, String)> = vec![];
+        for (_, _, date, owner) in
+            self.attendance
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(attended_season, attended, _, _)| {
+                    *attended_season == season && *attended == week
+                })
+        {
+            let owner_name = match teams.iter().find(|(owner_id, _)| owner_id == owner) {
+                Some((_, owner_name)) => owner_name,
+                None => continue,
+            };
+            let row = (date.to_string(), owner.clone(), owner_name.to_string());
+            if !rows.contains(&row) {
+                rows.push(row);
+            }
+        }
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(WeeklyAttendanceRecord::from_rows(interested_owner, rows))
+    }
+
+    async fn weeks(&self, season: u16) -> Result<Vec<u8>, Error> {
+        let mut weeks: Vec<u8> = self
+            .attendance
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(attended_season, _, _, _)| *attended_season == season)
+            .map(|(_, week, _, _)| *week)
+            .collect();
+        weeks.sort();
+        weeks.dedup();
+        Ok(weeks)
+    }
+
+    async fn attended_weeks(&self, season: u16) -> Result<Vec<(DiscordId, u8)>, Error> {
+        let mut attended: Vec<(DiscordId, u8)> = self
+            .attendance
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(attended_season, _, _, _)| *attended_season == season)
+            .map(|(_, week, _, owner)| (owner.clone(), *week))
+            .collect();
+        attended.sort_by_key(|(owner, week)| (owner.value(), *week));
+        attended.dedup();
+        Ok(attended)
+    }
+
+    async fn date_attendance(&self, season: u16, date: &str) -> Result<Vec<DiscordId>, Error> {
+        let mut owners: Vec<DiscordId> = vec![];
+        for (_, _, _, owner) in self.attendance.lock().unwrap().iter().filter(
+            |(attended_season, _, attended_date, _)| {
+                *attended_season == season && attended_date == date
+            },
+        ) {
+            if !owners.contains(owner) {
+                owners.push(owner.clone());
+            }
+        }
+        owners.sort_by_key(|owner| owner.value());
+        Ok(owners)
+    }
+
+    async fn set_attendance(
+        &self,
+        season: u16,
+        week: u8,
+        date: &str,
+        owners: &[DiscordId],
+    ) -> Result<Vec<DiscordId>, Error> {
+        let teams = self.teams.lock().unwrap();
+        let mut attendance = self.attendance.lock().unwrap();
+        attendance.retain(|(attended_season, _, attended_date, _)| {
+            *attended_season != season || attended_date != date
+        });
+        let mut recorded: Vec<DiscordId> = vec![];
+        for owner in owners {
+            if recorded.contains(owner) || !teams.iter().any(|(team, _)| team == owner) {
+                continue;
+            }
+            attendance.push((season, week, date.to_string(), owner.clone()));
+            recorded.push(owner.clone());
+        }
+        Ok(recorded)
+    }
+
+    async fn mark_attended(
+        &self,
+        season: u16,
+        week: u8,
+        date: &str,
+        owner: &DiscordId,
+    ) -> Result<bool, Error> {
+        if !self
+            .teams
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(team, _)| team == owner)
+        {
+            return Ok(false);
+        }
+        let mut attendance = self.attendance.lock().unwrap();
+        if attendance
+            .iter()
+            .any(|(attended_season, _, attended_date, attended)| {
+                *attended_season == season && attended_date == date && attended == owner
+            })
+        {
+            return Ok(false);
+        }
+        attendance.push((season, week, date.to_string(), owner.clone()));
+        Ok(true)
+    }
+
+    async fn request_rsvp_post(&self, post: &RsvpPost) -> Result<bool, Error> {
+        let mut posts = self.rsvp_posts.lock().unwrap();
+        let mut claimed = self.claimed_rsvp_posts.lock().unwrap();
+        if let Some(existing) = posts
+            .iter_mut()
+            .find(|existing| existing.season == post.season && existing.date == post.date)
+        {
+            let claim = (post.season, post.date.clone());
+            if existing.message_id.is_some() || !claimed.contains(&claim) {
+                return Ok(false);
+            }
+            claimed.retain(|other| *other != claim);
+            *existing = RsvpPost {
+                message_id: None,
+                ..post.clone()
+            };
+            return Ok(true);
+        }
+        posts.push(RsvpPost {
+            message_id: None,
+            ..post.clone()
+        });
+        Ok(true)
+    }
+
+    async fn pending_rsvp_posts(&self) -> Result<Vec<RsvpPost>, Error> {
+        let claimed = self.claimed_rsvp_posts.lock().unwrap();
+        let mut pending: Vec<RsvpPost> = self
+            .rsvp_posts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|post| post.message_id.is_none())
+            .filter(|post| !claimed.contains(&(post.season, post.date.clone())))
+            .cloned()
+            .collect();
+        pending.sort_by(|a, b| a.date.cmp(&b.date));
+        Ok(pending)
+    }
+
+    async fn claim_rsvp_post(&self, season: u16, date: &str) -> Result<bool, Error> {
+        let requested = self
+            .rsvp_posts
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|post| post.season == season && post.date == date);
+        let mut claimed = self.claimed_rsvp_posts.lock().unwrap();
+        let claim = (season, date.to_string());
+        if !requested || claimed.contains(&claim) {
+            return Ok(false);
+        }
+        claimed.push(claim);
+        Ok(true)
+    }
+
+    async fn rsvp_posted(&self, season: u16, date: &str, message_id: &str) -> Result<(), Error> {
+        for post in self.rsvp_posts.lock().unwrap().iter_mut() {
+            if post.season == season && post.date == date {
+                post.message_id = Some(message_id.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_rsvp(
+        &self,
+        message_id: &str,
+        owner: &DiscordId,
+        attending: bool,
+    ) -> Result<bool, Error> {
+        let is_post = self
+            .rsvp_posts
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|post| post.message_id.as_deref() == Some(message_id));
+        let has_team = self
+            .teams
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(team, _)| team == owner);
+        if !is_post || !has_team {
+            return Ok(false);
+        }
+        let mut rsvps = self.rsvps.lock().unwrap();
+        rsvps.retain(|(post, answered, _)| post != message_id || answered != owner);
+        rsvps.push((message_id.to_string(), owner.clone(), attending));
+        Ok(true)
+    }
+
+    async fn remove_rsvp(
+        &self,
+        message_id: &str,
+        owner: &DiscordId,
+        attending: bool,
+    ) -> Result<bool, Error> {
+        let mut rsvps = self.rsvps.lock().unwrap();
+        let before = rsvps.len();
+        rsvps.retain(|(post, answered, answer)| {
+            post != message_id || answered != owner || *answer != attending
+        });
+        Ok(rsvps.len() < before)
+    }
+
+    async fn rsvp_reliability(&self, season: u16) -> Result<Vec<RsvpRecord>, Error> {
+        let attendance = self.attendance.lock().unwrap();
+        let posts = self.rsvp_posts.lock().unwrap();
+        let mut result: Vec<RsvpRecord> = vec![];
+        for (message_id, owner, _) in self
+            .rsvps
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, _, attending)| *attending)
+        {
+            let post = match posts.iter().find(|post| {
+                post.season == season && post.message_id.as_deref() == Some(message_id)
+            }) {
+                Some(post) => post,
+                None => continue,
+            };
+            let taken = attendance.iter().any(|(attended_season, _, date, _)| {
+                *attended_season == season && date == &post.date
+            });
+            if !taken {
+                continue;
+            }
+            let showed_up = attendance
+                .iter()
+                .any(|(attended_season, _, date, attended)| {
+                    *attended_season == season && date == &post.date && attended == owner
+                });
+            match result.iter_mut().find(|record| &record.owner_id == owner) {
+                Some(record) => {
+                    record.coming += 1;
+                    record.showed_up += u8::from(showed_up);
+                }
+                None => result.push(RsvpRecord {
+                    owner_id: owner.clone(),
+                    coming: 1,
+                    showed_up: u8::from(showed_up),
+                }),
+            }
+        }
+        result.sort_by_key(|record| record.owner_id.value());
+        Ok(result)
     }
 }
+
+#[tokio::test]
+async fn test_in_mem_attendance() {
+    let repo = InMemoryAttendanceRepository::default();
+    crate::repos::conformance::attendance_repository(&repo, 2024).await;
+
+    let (dave, steve, shawn): (DiscordId, DiscordId, DiscordId) = (1.into(), 2.into(), 3.into());
+    repo.add_team(dave.clone(), "Dave");
+    repo.add_team(steve.clone(), "Steve");
+    repo.add_team(shawn.clone(), "Shawn");
+    repo.add_attendance(2024, 1, "2024-09-08", steve.clone());
+    repo.add_attendance(2024, 1, "2024-09-05", dave.clone());
+    repo.add_attendance(2024, 1, "2024-09-08", dave.clone());
+    repo.add_attendance(2024, 2, "2024-09-15", dave.clone());
+    repo.add_attendance(2024, 2, "2024-09-15", 4.into());
+    repo.add_attendance(2023, 5, "2023-10-08", shawn.clone());
+    crate::repos::conformance::attendance_repository(&repo, 2024).await;
+    crate::repos::conformance::attendance_repository(&repo, 2023).await;
+
+    assert_eq!(vec![1, 2], repo.weeks(2024).await.unwrap());
+    assert_eq!(vec![5], repo.weeks(2023).await.unwrap());
+    assert_eq!(
+        AttendanceRecords(vec![(1, 2, 3).into(), (2, 1, 1).into(), (3, 0, 0).into()]),
+        repo.combined_attendance(2024).await.unwrap()
+    );
+    assert_eq!(
+        AttendanceRecords(vec![(3, 1, 1).into(), (1, 0, 0).into(), (2, 0, 0).into()]),
+        repo.combined_attendance(2023).await.unwrap()
+    );
+    assert_eq!(
+        WeeklyAttendanceRecord {
+            interested_owner: Some(true),
+            attendance: vec![
+                ("2024-09-05".to_string(), vec!["Dave".to_string()]),
+                (
+                    "2024-09-08".to_string(),
+                    vec!["Steve".to_string(), "Dave".to_string()]
+                ),
+            ],
+        },
+        repo.week_attendance(2024, 1, &Some(steve)).await.unwrap()
+    );
+    assert_eq!(
+        Some(false),
+        repo.week_attendance(2024, 2, &Some(shawn))
+            .await
+            .unwrap()
+            .interested_owner
+    );
+}
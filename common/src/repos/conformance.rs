@@ -0,0 +1,614 @@
+// Scenarios every repository backend has to pass. They only rely on the repository traits and use
+// unique names and ids, so they can run against a shared database alongside existing data.
+use crate::discord_id::DiscordId;
+use crate::error::Error;
+use crate::repos::attendance_record::{AttendanceRecord, RsvpPost, RsvpRecord};
+use crate::repos::{
+    AdminRepository, AttendanceRepository, WagerRepository, WhoisPerson, WhoisRepository,
+};
+use crate::wager::{
+    DateRange, RevisionStatus, Wager, WagerAction, WagerEvent, WagerRevision, WagerStatus,
+    OPEN_OFFER_ACCEPTING,
+};
+use chrono::NaiveDate;
+
+pub async fn wager_repository<R: WagerRepository>(repo: &R) {
+    let seed = unique_seed();
+    let (name_a, name_b) = (
+        format!("conformance-{}-a", seed),
+        format!("conformance-{}-b", seed),
+    );
+    let (user_a, user_b): (DiscordId, DiscordId) = (seed.into(), (seed + 1).into());
+
+    let open = wager(&name_a, &name_b, WagerStatus::Open);
+    let first = repo.insert(open.clone()).await.unwrap();
+    let second = repo
+        .insert(wager(&name_b, &name_a, WagerStatus::Open))
+        .await
+        .unwrap();
+    let paid = repo
+        .insert(wager(&name_a, &name_b, WagerStatus::Paid))
+        .await
+        .unwrap();
+    assert_ne!(0, first, "insert should assign a wager id");
+    assert_ne!(first, second, "insert should assign unique wager ids");
+    assert_eq!(
+        Some(Wager {
+            wager_id: first,
+            ..open
+        }),
+        repo.get(first as i32).await
+    );
+    assert_eq!(None, repo.get(-1).await);
+
+    let mut found = ids(repo.search_by_user(&name_a).await.unwrap());
+    found.sort();
+    assert_eq!(vec![first, second], found, "only open wagers match a name");
+
+    assert_eq!(3, repo.link_user(&name_a, &user_a).await.unwrap());
+    assert_eq!(3, repo.link_user(&name_b, &user_b).await.unwrap());
+    assert_eq!(0, repo.link_user(&name_a, &user_a).await.unwrap());
+    let mut found = ids(repo.search_by_user_id(&user_a).await.unwrap());
+    found.sort();
+    assert_eq!(vec![first, second], found, "only open wagers match a user");
+    let mut found = ids(repo.search_by_user_id(&user_b).await.unwrap());
+    found.sort();
+    assert_eq!(vec![first, second], found, "only open wagers match a user");
+
+    let history = repo
+        .search_history_by_user_id(&user_a, &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(vec![paid, second, first], ids(history));
+
+    let mut won = repo.get(first as i32).await.unwrap();
+    won.status = WagerStatus::OfferingWon;
+    let settled = event(
+        first,
+        &user_a,
+        WagerAction::Settled,
+        WagerStatus::Open,
+        &won,
+    );
+    repo.update_status(
+        first as i32,
+        WagerStatus::Open,
+        &won,
+        std::slice::from_ref(&settled),
+    )
+    .await
+    .unwrap();
+    let mut overwrite = won.clone();
+    overwrite.status = WagerStatus::AcceptingWon;
+    let overwritten = event(
+        first,
+        &user_b,
+        WagerAction::Settled,
+        WagerStatus::Open,
+        &overwrite,
+    );
+    assert_eq!(
+        Err(Error::Conflict(Box::new(won.clone()))),
+        repo.update_status(first as i32, WagerStatus::Open, &overwrite, &[overwritten])
+            .await
+    );
+    assert_eq!(
+        vec![settled.clone()],
+        repo.events(first as i32).await.unwrap(),
+        "only the status change that happened is recorded"
+    );
+    let found = ids(repo.search_unpaid_by_user_id(&user_b).await.unwrap());
+    assert_eq!(vec![first], found);
+    let found = ids(repo
+        .search_by_status(WagerStatus::OfferingWon)
+        .await
+        .unwrap());
+    assert!(found.contains(&first));
+
+    let paid = repo.settle_all(&user_b, &user_a).await.unwrap();
+    assert_eq!(vec![first], ids(paid.clone()));
+    assert_eq!(
+        untimed(vec![settled, WagerEvent::paid(&paid[0], &user_b)]),
+        untimed(repo.events(first as i32).await.unwrap()),
+        "settling up records who paid"
+    );
+
+    let mut awaiting = wager(&name_a, &name_b, WagerStatus::AwaitingAcceptance);
+    awaiting.wager_id = repo.insert(awaiting.clone()).await.unwrap();
+    let accepted = WagerEvent::new(
+        awaiting.wager_id,
+        Some(user_b.clone()),
+        WagerAction::Accepted,
+        Some(WagerStatus::AwaitingAcceptance),
+        WagerStatus::Open,
+    );
+    let declined = WagerEvent::new(
+        awaiting.wager_id,
+        Some(user_b.clone()),
+        WagerAction::Declined,
+        Some(WagerStatus::AwaitingAcceptance),
+        WagerStatus::Declined,
+    );
+    let awaiting = awaiting.wager_id as i32;
+    assert!(repo
+        .respond_to_offer(awaiting, WagerStatus::Open, std::slice::from_ref(&accepted))
+        .await
+        .unwrap());
+    assert!(!repo
+        .respond_to_offer(awaiting, WagerStatus::Declined, &[declined])
+        .await
+        .unwrap());
+    assert_eq!(WagerStatus::Open, repo.get(awaiting).await.unwrap().status);
+    assert_eq!(vec![accepted], repo.events(awaiting).await.unwrap());
+
+    let created = WagerEvent::new(
+        0,
+        Some(user_a.clone()),
+        WagerAction::Created,
+        None,
+        WagerStatus::OpenOffer,
+    );
+    let offer = repo
+        .insert_with_event(
+            wager(&name_a, OPEN_OFFER_ACCEPTING, WagerStatus::OpenOffer),
+            created.clone(),
+        )
+        .await
+        .unwrap();
+    let created = WagerEvent {
+        wager_id: offer,
+        ..created
+    };
+    let taken = WagerEvent::new(
+        offer,
+        Some(user_b.clone()),
+        WagerAction::Accepted,
+        Some(WagerStatus::OpenOffer),
+        WagerStatus::Open,
+    )
+    .with_detail("taken");
+    let taken_again = WagerEvent::new(
+        offer,
+        Some(user_a.clone()),
+        WagerAction::Accepted,
+        Some(WagerStatus::OpenOffer),
+        WagerStatus::Open,
+    );
+    assert_eq!(
+        0,
+        repo.link_user(OPEN_OFFER_ACCEPTING, &user_b).await.unwrap(),
+        "an open offer isn't with anyone yet"
+    );
+    assert_eq!(0, repo.link_user("anyone", &user_b).await.unwrap());
+    assert!(!repo
+        .search_history_by_user(OPEN_OFFER_ACCEPTING, &DateRange::default())
+        .await
+        .unwrap()
+        .iter()
+        .any(|wager| wager.wager_id == offer));
+    assert!(repo
+        .take_offer(offer as i32, &name_b, &user_b, std::slice::from_ref(&taken))
+        .await
+        .unwrap());
+    assert!(!repo
+        .take_offer(offer as i32, "Woody", &user_a, &[taken_again])
+        .await
+        .unwrap());
+    assert_eq!(
+        vec![created, taken.clone()],
+        repo.events(offer as i32).await.unwrap()
+    );
+    let taken_offer = repo.get(offer as i32).await.unwrap();
+    assert_eq!(WagerStatus::Open, taken_offer.status);
+    assert_eq!(name_b, taken_offer.accepting);
+    assert_eq!(Some(user_b.clone()), taken_offer.resolved_accepting_user);
+
+    let mut revised = taken_offer.terms();
+    revised.wager = "$25".into();
+    revised.expected_settle_date = NaiveDate::from_ymd_opt(2024, 10, 1);
+    let revision = WagerRevision {
+        revision_id: 0,
+        wager_id: offer,
+        time: chrono::Utc::now().to_rfc3339(),
+        proposed_by: user_a.clone(),
+        status: RevisionStatus::Proposed,
+        previous: taken_offer.terms(),
+        revised: revised.clone(),
+    };
+    let revision_event = |actor: &DiscordId, action: WagerAction| {
+        WagerEvent::new(
+            offer,
+            Some(actor.clone()),
+            action,
+            Some(WagerStatus::Open),
+            WagerStatus::Open,
+        )
+        .with_detail("$25")
+    };
+    let edit_rejected = revision_event(&user_b, WagerAction::EditRejected);
+    let edited = revision_event(&user_b, WagerAction::Edited);
+    let rejected = repo
+        .propose_revision(revision.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        None,
+        repo.propose_revision(revision.clone()).await.unwrap(),
+        "only one change can wait on a response"
+    );
+    assert!(repo
+        .resolve_revision(
+            rejected as i32,
+            RevisionStatus::Rejected,
+            std::slice::from_ref(&edit_rejected)
+        )
+        .await
+        .unwrap());
+    assert!(repo.amended(&[offer]).await.unwrap().is_empty());
+    let accepted = repo.propose_revision(revision).await.unwrap().unwrap();
+    assert_ne!(rejected, accepted);
+    assert!(repo
+        .resolve_revision(
+            accepted as i32,
+            RevisionStatus::Accepted,
+            std::slice::from_ref(&edited)
+        )
+        .await
+        .unwrap());
+    assert!(!repo
+        .resolve_revision(
+            accepted as i32,
+            RevisionStatus::Rejected,
+            &[revision_event(&user_a, WagerAction::EditWithdrawn)]
+        )
+        .await
+        .unwrap());
+    assert_eq!(revised, repo.get(offer as i32).await.unwrap().terms());
+    assert_eq!(vec![offer], repo.amended(&[offer, first]).await.unwrap());
+    assert_eq!(
+        vec![edit_rejected, edited],
+        repo.events(offer as i32).await.unwrap()[2..].to_vec(),
+        "a revision that was already resolved records nothing"
+    );
+}
+
+pub async fn whois_repository<R: WhoisRepository>(repo: &R) {
+    let discord_id = unique_seed() as u64;
+    let person = |human_name: &str, hash_name: &str| WhoisPerson {
+        discord_id,
+        human_name: human_name.to_string(),
+        hash_name: hash_name.to_string(),
+    };
+    assert_eq!(Ok(None), repo.get_by_discord_id(discord_id).await);
+
+    repo.update(discord_id, "missing", "missing").await.unwrap();
+    assert_eq!(
+        Ok(None),
+        repo.get_by_discord_id(discord_id).await,
+        "update should not create a person"
+    );
+
+    repo.add(discord_id, "Conformance", "Tester").await.unwrap();
+    assert!(repo.add(discord_id, "Again", "Again").await.is_err());
+    assert!(repo
+        .get_by_discord_id(discord_id + 1)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        Ok(Some(person("Conformance", "Tester"))),
+        repo.get_by_discord_id(discord_id).await
+    );
+
+    repo.update(discord_id, "Conformance", "Updated")
+        .await
+        .unwrap();
+    assert_eq!(
+        Ok(Some(person("Conformance", "Updated"))),
+        repo.get_by_discord_id(discord_id).await
+    );
+    repo.set_user(discord_id + 1, "Other", &format!("Other {}", discord_id))
+        .await
+        .unwrap();
+    repo.set_user(discord_id, "Conformance", &format!("Set {}", discord_id))
+        .await
+        .unwrap();
+    assert_eq!(
+        Ok(Some(person("Conformance", &format!("Set {}", discord_id)))),
+        repo.get_by_discord_id(discord_id).await
+    );
+
+    let found = repo.search(&format!("SET {}", discord_id)).await.unwrap();
+    assert_eq!(
+        vec![person("Conformance", &format!("Set {}", discord_id))],
+        found
+    );
+    assert!(repo.search("100%_match").await.unwrap().is_empty());
+}
+
+pub async fn admin_repository<R: AdminRepository>(repo: &R) {
+    let original = repo.get().await.unwrap();
+    let mut settings = original.clone();
+    settings.reminder_days = original.reminder_days.wrapping_add(1);
+    settings.limit_exempt.push(unique_seed().to_string());
+    repo.update(settings.clone()).await.unwrap();
+    assert_eq!(settings, repo.get().await.unwrap());
+
+    repo.update(original.clone()).await.unwrap();
+    assert_eq!(original, repo.get().await.unwrap());
+}
+
+pub async fn attendance_repository<R: AttendanceRepository>(repo: &R, season: u16) {
+    let weeks = repo.weeks(season).await.unwrap();
+    assert!(
+        weeks.windows(2).all(|pair| pair[0] < pair[1]),
+        "weeks should be distinct and ascending"
+    );
+
+    let combined = repo.combined_attendance(season).await.unwrap();
+    assert!(
+        combined
+            .0
+            .windows(2)
+            .all(|pair| (pair[0].weeks, pair[0].games) >= (pair[1].weeks, pair[1].games)),
+        "combined attendance should be ranked by weeks then games"
+    );
+    for record in combined.0.iter() {
+        assert!(record.weeks as usize <= weeks.len());
+        assert!(record.games >= record.weeks);
+    }
+
+    for week in weeks.iter() {
+        let attended = repo.week_attendance(season, *week, &None).await.unwrap();
+        assert_eq!(None, attended.interested_owner);
+        assert!(attended
+            .attendance
+            .iter()
+            .all(|(date, attendees)| !date.is_empty() && !attendees.is_empty()));
+        assert!(
+            attended
+                .attendance
+                .windows(2)
+                .all(|pair| pair[0].0 < pair[1].0),
+            "week {} should be grouped by date",
+            week
+        );
+    }
+
+    let unrecorded = (1..=u8::MAX).find(|week| !weeks.contains(week)).unwrap();
+    let owner = combined
+        .0
+        .first()
+        .map(|record| record.owner_id.clone())
+        .unwrap_or_else(|| unique_seed().into());
+    let attended = repo
+        .week_attendance(season, unrecorded, &Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(Some(false), attended.interested_owner);
+    assert!(attended.attendance.is_empty());
+
+    if let Some(record) = combined.0.iter().find(|record| record.weeks > 0) {
+        let mut attended_weeks = 0;
+        for week in weeks.iter() {
+            let attended = repo
+                .week_attendance(season, *week, &Some(record.owner_id.clone()))
+                .await
+                .unwrap();
+            if attended.interested_owner == Some(true) {
+                attended_weeks += 1;
+            }
+        }
+        assert_eq!(record.weeks, attended_weeks);
+    }
+    let attended_weeks = repo.attended_weeks(season).await.unwrap();
+    for record in combined.0.iter() {
+        let owner_weeks: Vec<u8> = attended_weeks
+            .iter()
+            .filter(|(owner, _)| owner == &record.owner_id)
+            .map(|(_, week)| *week)
+            .collect();
+        assert_eq!(record.weeks as usize, owner_weeks.len());
+        assert!(owner_weeks.iter().all(|week| weeks.contains(week)));
+    }
+
+    // writes go to a season of their own so they can't disturb the reported one
+    let reported = season;
+    let season = 3000 + (unique_seed() % 60000) as u16;
+    let date = "2024-09-08";
+    let (owner, outsider): (DiscordId, DiscordId) = match combined.0.first() {
+        Some(record) => (record.owner_id.clone(), (unique_seed() + 2).into()),
+        None => return,
+    };
+    assert!(repo.date_attendance(season, date).await.unwrap().is_empty());
+    let recorded = repo
+        .set_attendance(
+            season,
+            1,
+            date,
+            &[owner.clone(), outsider.clone(), owner.clone()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        vec![owner.clone()],
+        recorded,
+        "only league members are recorded"
+    );
+    assert_eq!(
+        vec![owner.clone()],
+        repo.date_attendance(season, date).await.unwrap()
+    );
+    assert!(repo
+        .date_attendance(season + 1, date)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(vec![1], repo.weeks(season).await.unwrap());
+    assert_eq!(
+        vec![(owner.clone(), 1)],
+        repo.attended_weeks(season).await.unwrap()
+    );
+    let written = repo.combined_attendance(season).await.unwrap();
+    assert_eq!(
+        combined.0.len(),
+        written.0.len(),
+        "every team is ranked in a season, attended or not"
+    );
+    assert_eq!(
+        Some(&AttendanceRecord {
+            owner_id: owner.clone(),
+            weeks: 1,
+            games: 1
+        }),
+        written.0.first()
+    );
+    assert_eq!(
+        combined,
+        repo.combined_attendance(reported).await.unwrap(),
+        "writes to one season leave the others alone"
+    );
+
+    let post = RsvpPost {
+        season,
+        date: date.to_string(),
+        channel_id: "555555555".to_string(),
+        content: "Who's coming?".to_string(),
+        message_id: None,
+    };
+    let message_id = format!("rsvp-{}", unique_seed());
+    assert!(repo.request_rsvp_post(&post).await.unwrap());
+    assert!(
+        !repo.request_rsvp_post(&post).await.unwrap(),
+        "one post per game day"
+    );
+    assert!(repo.pending_rsvp_posts().await.unwrap().contains(&post));
+    assert!(
+        !repo.record_rsvp(&message_id, &owner, true).await.unwrap(),
+        "answers are only taken on rsvp posts"
+    );
+    assert!(repo.claim_rsvp_post(season, date).await.unwrap());
+    assert!(
+        !repo.claim_rsvp_post(season, date).await.unwrap(),
+        "a post is only sent once"
+    );
+    assert!(!repo.pending_rsvp_posts().await.unwrap().contains(&post));
+    assert!(
+        repo.request_rsvp_post(&post).await.unwrap(),
+        "a post that was claimed but never sent can be requested again"
+    );
+    assert!(repo.pending_rsvp_posts().await.unwrap().contains(&post));
+    assert!(repo.claim_rsvp_post(season, date).await.unwrap());
+    repo.rsvp_posted(season, date, &message_id).await.unwrap();
+    assert!(
+        !repo.request_rsvp_post(&post).await.unwrap(),
+        "a sent post is never requested again"
+    );
+    assert!(
+        !repo
+            .record_rsvp(&message_id, &outsider, true)
+            .await
+            .unwrap(),
+        "only league members answer"
+    );
+    assert!(repo.record_rsvp(&message_id, &owner, false).await.unwrap());
+    assert!(repo.rsvp_reliability(season).await.unwrap().is_empty());
+    assert!(repo.record_rsvp(&message_id, &owner, true).await.unwrap());
+    assert!(
+        !repo.remove_rsvp(&message_id, &owner, false).await.unwrap(),
+        "taking back an old answer leaves the new one"
+    );
+    assert_eq!(
+        vec![RsvpRecord {
+            owner_id: owner.clone(),
+            coming: 1,
+            showed_up: 1
+        }],
+        repo.rsvp_reliability(season).await.unwrap()
+    );
+    assert!(repo.rsvp_reliability(season + 1).await.unwrap().is_empty());
+    assert!(repo.remove_rsvp(&message_id, &owner, true).await.unwrap());
+    assert!(repo.rsvp_reliability(season).await.unwrap().is_empty());
+
+    let watched = "2024-09-15";
+    assert!(
+        !repo
+            .mark_attended(season, 2, watched, &outsider)
+            .await
+            .unwrap(),
+        "only league members are recorded"
+    );
+    assert!(repo
+        .mark_attended(season, 2, watched, &owner)
+        .await
+        .unwrap());
+    assert!(
+        !repo
+            .mark_attended(season, 2, watched, &owner)
+            .await
+            .unwrap(),
+        "an owner attends a date once"
+    );
+    assert_eq!(
+        vec![owner.clone()],
+        repo.date_attendance(season, watched).await.unwrap()
+    );
+    repo.set_attendance(season, 2, watched, &[]).await.unwrap();
+
+    assert_eq!(
+        vec![owner.clone()],
+        repo.set_attendance(season, 2, date, &[owner])
+            .await
+            .unwrap()
+    );
+    assert!(repo
+        .set_attendance(season, 2, date, &[])
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(repo.date_attendance(season, date).await.unwrap().is_empty());
+}
+
+fn unique_seed() -> i64 {
+    // keeps the seed within a postgres bigint while spreading concurrent runs apart
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() >> 1
+}
+
+fn wager(offering: &str, accepting: &str, status: WagerStatus) -> Wager {
+    Wager {
+        expected_settle_date: NaiveDate::from_ymd_opt(2024, 9, 15),
+        ..Wager::fixture(offering, accepting, status)
+    }
+}
+
+fn event(
+    wager_id: u32,
+    actor: &DiscordId,
+    action: WagerAction,
+    previous_status: WagerStatus,
+    wager: &Wager,
+) -> WagerEvent {
+    WagerEvent::new(
+        wager_id,
+        Some(actor.clone()),
+        action,
+        Some(previous_status),
+        wager.status,
+    )
+}
+
+// events recorded by the repository itself are stamped when they're written
+fn untimed(events: Vec<WagerEvent>) -> Vec<WagerEvent> {
+    events
+        .into_iter()
+        .map(|event| WagerEvent {
+            time: String::new(),
+            ..event
+        })
+        .collect()
+}
+
+fn ids(wagers: Vec<Wager>) -> Vec<u32> {
+    wagers.iter().map(|wager| wager.wager_id).collect()
+}
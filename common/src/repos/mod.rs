@@ -1,8 +1,12 @@
 mod admin_repository;
 pub mod attendance_record;
 pub mod attendance_repository;
+#[cfg(any(test, feature = "conformance"))]
+pub mod conformance;
+mod pool_repository;
 mod postgres_admin_repository;
 pub mod postgres_attendance_repo;
+mod postgres_pool_repository;
 pub mod postgres_wager_repository;
 mod postgres_whois_repository;
 pub mod wager_repository;
@@ -10,8 +14,10 @@ mod whois_repository;
 
 pub use admin_repository::*;
 pub use attendance_repository::*;
+pub use pool_repository::*;
 pub use postgres_admin_repository::*;
 pub use postgres_attendance_repo::*;
+pub use postgres_pool_repository::*;
 pub use postgres_wager_repository::*;
 pub use postgres_whois_repository::*;
 use sqlx::postgres::PgPoolOptions;
@@ -0,0 +1,134 @@
+use crate::discord_id::DiscordId;
+use crate::error::Error;
+use crate::pool::{Pool, PoolEntry, PoolStatus};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+pub trait PoolRepository: std::fmt::Debug {
+    fn insert(&self, pool: Pool) -> impl Future<Output = Result<u32, Error>> + Send;
+    fn get(&self, pool_id: i32) -> impl Future<Output = Option<Pool>> + Send;
+    fn join(
+        &self,
+        pool_id: i32,
+        entry: PoolEntry,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn settle(
+        &self,
+        pool_id: i32,
+        winning_option: &str,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn search_by_user_id(
+        &self,
+        user_id: &DiscordId,
+    ) -> impl Future<Output = Result<Vec<Pool>, Error>> + Send;
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct InMemPoolRepository {
+    pools: Arc<Mutex<Vec<Pool>>>,
+}
+
+impl PoolRepository for InMemPoolRepository {
+    async fn insert(&self, mut pool: Pool) -> Result<u32, Error> {
+        let mut pools = self.pools.lock().unwrap();
+        pool.pool_id = pools.len() as u32 + 1;
+        let pool_id = pool.pool_id;
+        pools.push(pool);
+        Ok(pool_id)
+    }
+
+    async fn get(&self, pool_id: i32) -> Option<Pool> {
+        self.pools
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|pool| pool.pool_id == pool_id as u32)
+            .cloned()
+    }
+
+    async fn join(&self, pool_id: i32, entry: PoolEntry) -> Result<bool, Error> {
+        for pool in self.pools.lock().unwrap().iter_mut() {
+            if pool.pool_id == pool_id as u32
+                && pool.status == PoolStatus::Open
+                && pool.entry(&entry.user_id).is_none()
+            {
+                pool.entries.push(entry);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn settle(&self, pool_id: i32, winning_option: &str) -> Result<bool, Error> {
+        for pool in self.pools.lock().unwrap().iter_mut() {
+            if pool.pool_id == pool_id as u32 && pool.status == PoolStatus::Open {
+                pool.status = PoolStatus::Settled;
+                pool.winning_option = Some(winning_option.to_string());
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn search_by_user_id(&self, user_id: &DiscordId) -> Result<Vec<Pool>, Error> {
+        let mut result: Vec<Pool> = self
+            .pools
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|pool| pool.entry(user_id).is_some())
+            .cloned()
+            .collect();
+        result.sort_by_key(|pool| std::cmp::Reverse(pool.pool_id));
+        Ok(result)
+    }
+}
+
+#[tokio::test]
+async fn test_in_mem_pool_repo() {
+    let repo = InMemPoolRepository::default();
+    let harx: DiscordId = 695398918694895710.into();
+    let woody: DiscordId = 1050119194533961860.into();
+    let pool_id = repo
+        .insert(Pool {
+            pool_id: 0,
+            time: chrono::Utc::now().to_rfc3339(),
+            created_by: harx.clone(),
+            question: "Who wins the Super Bowl?".to_string(),
+            buy_in: "$10".into(),
+            options: vec!["Chiefs".to_string(), "Eagles".to_string()],
+            status: PoolStatus::Open,
+            winning_option: None,
+            entries: vec![],
+        })
+        .await
+        .unwrap();
+    let entry = PoolEntry {
+        user_id: harx.clone(),
+        name: "Harx".to_string(),
+        pick: "Chiefs".to_string(),
+        buy_in: "$10".into(),
+    };
+    assert!(repo.join(pool_id as i32, entry.clone()).await.unwrap());
+    assert!(!repo.join(pool_id as i32, entry).await.unwrap());
+    assert_eq!(1, repo.search_by_user_id(&harx).await.unwrap().len());
+    assert!(repo.search_by_user_id(&woody).await.unwrap().is_empty());
+
+    assert!(repo.settle(pool_id as i32, "Chiefs").await.unwrap());
+    assert!(!repo.settle(pool_id as i32, "Eagles").await.unwrap());
+    let pool = repo.get(pool_id as i32).await.unwrap();
+    assert_eq!(PoolStatus::Settled, pool.status);
+    assert_eq!(Some("Chiefs".to_string()), pool.winning_option);
+    assert!(!repo
+        .join(
+            pool_id as i32,
+            PoolEntry {
+                user_id: woody,
+                name: "Woody".to_string(),
+                pick: "Eagles".to_string(),
+                buy_in: "$10".into(),
+            }
+        )
+        .await
+        .unwrap());
+}
@@ -1,33 +1,147 @@
 use crate::discord_id::DiscordId;
 use crate::error::Error;
-use crate::wager::{Wager, WagerStatus};
+use crate::wager::{
+    DateRange, LedgerBalance, RevisionStatus, Stake, Wager, WagerEvent, WagerRevision, WagerStatus,
+    OPEN_OFFER_ACCEPTING,
+};
+use chrono::NaiveDate;
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::{Arc, Mutex};
 
 pub trait WagerRepository: std::fmt::Debug {
-    fn insert(&self, wager: Wager) -> impl Future<Output = Result<(), Error>> + Send;
+    fn insert(&self, wager: Wager) -> impl Future<Output = Result<u32, Error>> + Send;
+    // the event is recorded against the new wager's id, in the same transaction as the insert
+    fn insert_with_event(
+        &self,
+        wager: Wager,
+        event: WagerEvent,
+    ) -> impl Future<Output = Result<u32, Error>> + Send;
     fn get(&self, wager_id: i32) -> impl Future<Output = Option<Wager>> + Send;
     fn search_by_user_id(
         &self,
         user_id: &DiscordId,
     ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
     fn search_by_user(&self, user: &str) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_by_status(
+        &self,
+        status: WagerStatus,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_unpaid_by_user_id(
+        &self,
+        user_id: &DiscordId,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_history_by_user_id(
+        &self,
+        user_id: &DiscordId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_history_by_user(
+        &self,
+        user: &str,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_settled(
+        &self,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_settled_by_user_id(
+        &self,
+        user_id: &DiscordId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn search_overdue(
+        &self,
+        today: NaiveDate,
+        reminder_days: u16,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    // true for only one caller per wager and day, so concurrent runners can't both remind
+    fn claim_reminder(
+        &self,
+        wager_id: i32,
+        date: NaiveDate,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn ledger(
+        &self,
+        user_id: &DiscordId,
+    ) -> impl Future<Output = Result<Vec<LedgerBalance>, Error>> + Send;
+    // records a Paid event by user_id for every wager it settles
+    fn settle_all(
+        &self,
+        user_id: &DiscordId,
+        counterparty: &DiscordId,
+    ) -> impl Future<Output = Result<Vec<Wager>, Error>> + Send;
+    fn link_user(
+        &self,
+        user: &str,
+        user_id: &DiscordId,
+    ) -> impl Future<Output = Result<u64, Error>> + Send;
+    // the events are only recorded if the status changes, in the same transaction
     fn update_status(
         &self,
         wager_id: i32,
+        expected: WagerStatus,
         wager: &Wager,
+        events: &[WagerEvent],
     ) -> impl Future<Output = Result<(), Error>> + Send;
+    fn respond_to_offer(
+        &self,
+        wager_id: i32,
+        status: WagerStatus,
+        events: &[WagerEvent],
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn take_offer(
+        &self,
+        wager_id: i32,
+        accepting: &str,
+        user_id: &DiscordId,
+        events: &[WagerEvent],
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    // None when another change to the wager is still waiting on a response
+    fn propose_revision(
+        &self,
+        revision: WagerRevision,
+    ) -> impl Future<Output = Result<Option<u32>, Error>> + Send;
+    fn get_revision(&self, revision_id: i32) -> impl Future<Output = Option<WagerRevision>> + Send;
+    fn resolve_revision(
+        &self,
+        revision_id: i32,
+        status: RevisionStatus,
+        events: &[WagerEvent],
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn amended(&self, wager_ids: &[u32]) -> impl Future<Output = Result<Vec<u32>, Error>> + Send;
+    fn record_event(&self, event: WagerEvent) -> impl Future<Output = Result<(), Error>> + Send;
+    fn events(&self, wager_id: i32) -> impl Future<Output = Result<Vec<WagerEvent>, Error>> + Send;
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct InMemWagerRepository {
     wagers: Arc<Mutex<Vec<Wager>>>,
+    reminders: Arc<Mutex<HashMap<u32, NaiveDate>>>,
+    revisions: Arc<Mutex<Vec<WagerRevision>>>,
+    events: Arc<Mutex<Vec<WagerEvent>>>,
 }
 
 impl WagerRepository for InMemWagerRepository {
-    async fn insert(&self, wager: Wager) -> Result<(), Error> {
-        self.wagers.lock().unwrap().push(wager);
-        Ok(())
+    // like the database sequence, wagers without an id get the next one; an explicit id is kept
+    // so tests can seed known wagers
+    async fn insert(&self, mut wager: Wager) -> Result<u32, Error> {
+        let mut wagers = self.wagers.lock().unwrap();
+        if wager.wager_id == 0 {
+            wager.wager_id = wagers.iter().map(|w| w.wager_id).max().unwrap_or_default() + 1;
+        }
+        let wager_id = wager.wager_id;
+        wagers.push(wager);
+        Ok(wager_id)
+    }
+
+    async fn insert_with_event(&self, wager: Wager, event: WagerEvent) -> Result<u32, Error> {
+        let wager_id = self.insert(wager).await?;
+        self.events
+            .lock()
+            .unwrap()
+            .push(WagerEvent { wager_id, ..event });
+        Ok(wager_id)
     }
 
     async fn get(&self, wager_id: i32) -> Option<Wager> {
@@ -42,27 +156,9 @@ impl WagerRepository for InMemWagerRepository {
     async fn search_by_user_id(&self, user_id: &DiscordId) -> Result<Vec<Wager>, Error> {
         let mut result = Vec::new();
         for wager in self.wagers.lock().unwrap().iter() {
-            match (
-                &wager.resolved_offering_user,
-                &wager.resolved_accepting_user,
-            ) {
-                (Some(user_a), Some(user_b)) => {
-                    if user_a == user_id || user_b == user_id && wager.status == WagerStatus::Open {
-                        result.push(wager.clone());
-                    }
-                }
-                (Some(user_a), None) => {
-                    if user_a == user_id && wager.status == WagerStatus::Open {
-                        result.push(wager.clone());
-                    }
-                }
-                (None, Some(user_b)) => {
-                    if user_b == user_id && wager.status == WagerStatus::Open {
-                        result.push(wager.clone());
-                    }
-                }
-                (_, _) => {}
-            };
+            if wager.is_participant(user_id) && wager.status == WagerStatus::Open {
+                result.push(wager.clone());
+            }
         }
         Ok(result)
     }
@@ -70,85 +166,734 @@ impl WagerRepository for InMemWagerRepository {
     async fn search_by_user(&self, user: &str) -> Result<Vec<Wager>, Error> {
         let mut result = Vec::new();
         for wager in self.wagers.lock().unwrap().iter() {
-            if (wager.offering == user || wager.accepting == user)
-                && wager.status == WagerStatus::Open
-            {
+            let accepting = wager.accepting != OPEN_OFFER_ACCEPTING && wager.accepting == user;
+            if (wager.offering == user || accepting) && wager.status == WagerStatus::Open {
+                result.push(wager.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn search_by_status(&self, status: WagerStatus) -> Result<Vec<Wager>, Error> {
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            if wager.status == status {
+                result.push(wager.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn search_unpaid_by_user_id(&self, user_id: &DiscordId) -> Result<Vec<Wager>, Error> {
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            let won = matches!(
+                wager.status,
+                WagerStatus::OfferingWon | WagerStatus::AcceptingWon
+            );
+            if won && wager.is_participant(user_id) {
+                result.push(wager.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn search_history_by_user_id(
+        &self,
+        user_id: &DiscordId,
+        range: &DateRange,
+    ) -> Result<Vec<Wager>, Error> {
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            if wager.is_participant(user_id) && range.contains(&wager.time) {
+                result.push(wager.clone());
+            }
+        }
+        result.sort_by_key(|wager| std::cmp::Reverse(wager.wager_id));
+        Ok(result)
+    }
+
+    async fn search_history_by_user(
+        &self,
+        user: &str,
+        range: &DateRange,
+    ) -> Result<Vec<Wager>, Error> {
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            let named = wager.offering.eq_ignore_ascii_case(user) || wager.accepting_named(user);
+            if named && range.contains(&wager.time) {
+                result.push(wager.clone());
+            }
+        }
+        result.sort_by_key(|wager| std::cmp::Reverse(wager.wager_id));
+        Ok(result)
+    }
+
+    async fn search_settled(&self, range: &DateRange) -> Result<Vec<Wager>, Error> {
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            let resolved =
+                wager.resolved_offering_user.is_some() || wager.resolved_accepting_user.is_some();
+            if resolved && is_settled(wager) && range.contains(&wager.time) {
                 result.push(wager.clone());
             }
         }
         Ok(result)
     }
 
-    async fn update_status(&self, wager_id: i32, updated_wager: &Wager) -> Result<(), Error> {
-        let status = updated_wager.status;
+    async fn search_settled_by_user_id(
+        &self,
+        user_id: &DiscordId,
+        range: &DateRange,
+    ) -> Result<Vec<Wager>, Error> {
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            if wager.is_participant(user_id) && is_settled(wager) && range.contains(&wager.time) {
+                result.push(wager.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    async fn search_overdue(
+        &self,
+        today: NaiveDate,
+        reminder_days: u16,
+    ) -> Result<Vec<Wager>, Error> {
+        let reminders = self.reminders.lock().unwrap();
+        let mut result = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            let overdue = wager.status == WagerStatus::Open
+                && wager.expected_settle_date.is_some_and(|date| date < today);
+            let due = match reminders.get(&wager.wager_id) {
+                None => true,
+                Some(_) if reminder_days == 0 => false,
+                Some(last) => *last + chrono::Days::new(reminder_days as u64) <= today,
+            };
+            if overdue && due {
+                result.push(wager.clone());
+            }
+        }
+        result.sort_by_key(|wager| wager.wager_id);
+        Ok(result)
+    }
+
+    async fn claim_reminder(&self, wager_id: i32, date: NaiveDate) -> Result<bool, Error> {
+        let mut reminders = self.reminders.lock().unwrap();
+        if reminders
+            .get(&(wager_id as u32))
+            .is_some_and(|last| *last >= date)
+        {
+            return Ok(false);
+        }
+        reminders.insert(wager_id as u32, date);
+        Ok(true)
+    }
+
+    async fn ledger(&self, user_id: &DiscordId) -> Result<Vec<LedgerBalance>, Error> {
+        let mut result: Vec<LedgerBalance> = Vec::new();
+        for wager in self.wagers.lock().unwrap().iter() {
+            let (counterparty, amount, currency) = match (
+                ledger_counterparty(wager, user_id),
+                wager.stake_owed(wager.won_status()),
+                wager.winner(),
+            ) {
+                (Some(counterparty), Some(Stake::Money { amount, currency }), Some(winner)) => {
+                    let amount = match winner == user_id {
+                        true => *amount,
+                        false => -amount,
+                    };
+                    (counterparty, amount, *currency)
+                }
+                _ => continue,
+            };
+            match result
+                .iter_mut()
+                .find(|b| &b.counterparty == counterparty && b.currency == currency)
+            {
+                Some(balance) => balance.amount += amount,
+                None => result.push(LedgerBalance {
+                    counterparty: counterparty.clone(),
+                    currency,
+                    amount,
+                }),
+            }
+        }
+        result.sort_by_key(|b| (b.counterparty.value(), b.currency.code()));
+        Ok(result)
+    }
+
+    async fn settle_all(
+        &self,
+        user_id: &DiscordId,
+        counterparty: &DiscordId,
+    ) -> Result<Vec<Wager>, Error> {
+        let mut settled = vec![];
+        for wager in self.wagers.lock().unwrap().iter_mut() {
+            let matches = ledger_counterparty(wager, user_id) == Some(counterparty)
+                && matches!(
+                    wager.stake_owed(wager.won_status()),
+                    Some(Stake::Money { .. })
+                );
+            if matches {
+                wager.paid_outcome = Some(wager.status);
+                wager.status = WagerStatus::Paid;
+                settled.push(wager.clone());
+            }
+        }
+        self.events
+            .lock()
+            .unwrap()
+            .extend(settled.iter().map(|wager| WagerEvent::paid(wager, user_id)));
+        Ok(settled)
+    }
+
+    async fn link_user(&self, user: &str, user_id: &DiscordId) -> Result<u64, Error> {
+        let mut linked = 0;
+        for wager in self.wagers.lock().unwrap().iter_mut() {
+            let mut changed = false;
+            if wager.resolved_offering_user.is_none() && wager.offering.eq_ignore_ascii_case(user) {
+                wager.resolved_offering_user = Some(user_id.clone());
+                changed = true;
+            }
+            if wager.resolved_accepting_user.is_none() && wager.accepting_named(user) {
+                wager.resolved_accepting_user = Some(user_id.clone());
+                changed = true;
+            }
+            if changed {
+                linked += 1;
+            }
+        }
+        Ok(linked)
+    }
+
+    async fn update_status(
+        &self,
+        wager_id: i32,
+        expected: WagerStatus,
+        updated_wager: &Wager,
+        events: &[WagerEvent],
+    ) -> Result<(), Error> {
         for wager in self.wagers.lock().unwrap().iter_mut() {
             if wager.wager_id == wager_id as u32 {
+                if wager.status != expected {
+                    return Err(Error::Conflict(Box::new(wager.clone())));
+                }
+                wager.status = updated_wager.status;
+                wager.proposal = updated_wager.proposal.clone();
+                wager.paid_outcome = updated_wager.paid_outcome;
+                self.events.lock().unwrap().extend_from_slice(events);
+                return Ok(());
+            }
+        }
+        Err(Error::Unexpected(format!("wager {} not found", wager_id)))
+    }
+
+    async fn respond_to_offer(
+        &self,
+        wager_id: i32,
+        status: WagerStatus,
+        events: &[WagerEvent],
+    ) -> Result<bool, Error> {
+        for wager in self.wagers.lock().unwrap().iter_mut() {
+            if wager.wager_id == wager_id as u32 && wager.status == WagerStatus::AwaitingAcceptance
+            {
                 wager.status = status;
+                self.events.lock().unwrap().extend_from_slice(events);
+                return Ok(true);
             }
         }
+        Ok(false)
+    }
+
+    async fn take_offer(
+        &self,
+        wager_id: i32,
+        accepting: &str,
+        user_id: &DiscordId,
+        events: &[WagerEvent],
+    ) -> Result<bool, Error> {
+        for wager in self.wagers.lock().unwrap().iter_mut() {
+            if wager.wager_id == wager_id as u32 && wager.status == WagerStatus::OpenOffer {
+                wager.status = WagerStatus::Open;
+                wager.accepting = accepting.to_string();
+                wager.resolved_accepting_user = Some(user_id.clone());
+                self.events.lock().unwrap().extend_from_slice(events);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn propose_revision(&self, mut revision: WagerRevision) -> Result<Option<u32>, Error> {
+        let mut revisions = self.revisions.lock().unwrap();
+        if revisions.iter().any(|pending| {
+            pending.wager_id == revision.wager_id && pending.status == RevisionStatus::Proposed
+        }) {
+            return Ok(None);
+        }
+        revision.revision_id = revisions.len() as u32 + 1;
+        revision.status = RevisionStatus::Proposed;
+        revisions.push(revision.clone());
+        Ok(Some(revision.revision_id))
+    }
+
+    async fn get_revision(&self, revision_id: i32) -> Option<WagerRevision> {
+        self.revisions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|revision| revision.revision_id == revision_id as u32)
+            .cloned()
+    }
+
+    async fn resolve_revision(
+        &self,
+        revision_id: i32,
+        status: RevisionStatus,
+        events: &[WagerEvent],
+    ) -> Result<bool, Error> {
+        let mut revisions = self.revisions.lock().unwrap();
+        let revision = match revisions.iter_mut().find(|revision| {
+            revision.revision_id == revision_id as u32
+                && revision.status == RevisionStatus::Proposed
+        }) {
+            Some(revision) => revision,
+            None => return Ok(false),
+        };
+        if status == RevisionStatus::Accepted {
+            for wager in self.wagers.lock().unwrap().iter_mut() {
+                if wager.wager_id == revision.wager_id {
+                    if wager.status != WagerStatus::Open {
+                        return Err(Error::Conflict(Box::new(wager.clone())));
+                    }
+                    *wager = wager.with_terms(&revision.revised);
+                }
+            }
+        }
+        revision.status = status;
+        self.events.lock().unwrap().extend_from_slice(events);
+        Ok(true)
+    }
+
+    async fn amended(&self, wager_ids: &[u32]) -> Result<Vec<u32>, Error> {
+        let mut result: Vec<u32> = self
+            .revisions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|revision| revision.status == RevisionStatus::Accepted)
+            .map(|revision| revision.wager_id)
+            .filter(|wager_id| wager_ids.contains(wager_id))
+            .collect();
+        result.sort();
+        result.dedup();
+        Ok(result)
+    }
+
+    async fn record_event(&self, event: WagerEvent) -> Result<(), Error> {
+        self.events.lock().unwrap().push(event);
         Ok(())
     }
+
+    async fn events(&self, wager_id: i32) -> Result<Vec<WagerEvent>, Error> {
+        Ok(self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.wager_id == wager_id as u32)
+            .cloned()
+            .collect())
+    }
+}
+
+fn is_settled(wager: &Wager) -> bool {
+    matches!(
+        wager.status,
+        WagerStatus::Paid
+            | WagerStatus::OfferingWon
+            | WagerStatus::AcceptingWon
+            | WagerStatus::NoBet
+    )
+}
+
+fn ledger_counterparty<'a>(wager: &'a Wager, user_id: &DiscordId) -> Option<&'a DiscordId> {
+    match wager.status {
+        WagerStatus::OfferingWon | WagerStatus::AcceptingWon => wager.counterparty(user_id),
+        _ => None,
+    }
 }
 
 #[tokio::test]
 async fn test_in_mem_wager_repo() {
     let repo = InMemWagerRepository::default();
     repo.insert(Wager {
-        wager_id: 0,
-        time: chrono::Utc::now().to_rfc3339(),
-        offering: "Harx".to_string(),
-        resolved_offering_user: None,
-        accepting: "Woody".to_string(),
-        resolved_accepting_user: None,
-        wager: "$100".to_string(),
+        wager: "$100".into(),
         outcome: "Rangers take the Phillies, should they meet".to_string(),
-        status: WagerStatus::Open,
-        expected_settle_date: None,
+        ..Wager::fixture("Harx", "Woody", WagerStatus::Open)
     })
     .await
     .unwrap();
     repo.insert(Wager {
-        wager_id: 0,
-        time: chrono::Utc::now().to_rfc3339(),
-        offering: "Shawn".to_string(),
-        resolved_offering_user: None,
-        accepting: "Todd".to_string(),
-        resolved_accepting_user: None,
-        wager: "$40".to_string(),
+        wager: "$40".into(),
         outcome: "Jax has a losing season".to_string(),
-        status: WagerStatus::Open,
-        expected_settle_date: None,
+        ..Wager::fixture("Shawn", "Todd", WagerStatus::Open)
     })
     .await
     .unwrap();
     repo.insert(Wager {
-        wager_id: 0,
-        time: chrono::Utc::now().to_rfc3339(),
-        offering: "Woody".to_string(),
-        resolved_offering_user: None,
-        accepting: "Todd".to_string(),
-        resolved_accepting_user: None,
-        wager: "$30".to_string(),
-        outcome: "Jets beat the Oilers".to_string(),
-        status: WagerStatus::Open,
-        expected_settle_date: None,
+        wager: "$30".into(),
+        ..Wager::fixture("Woody", "Todd", WagerStatus::Open)
     })
     .await
     .unwrap();
     repo.insert(Wager {
-        wager_id: 0,
-        time: chrono::Utc::now().to_rfc3339(),
-        offering: "Woody".to_string(),
-        resolved_offering_user: None,
-        accepting: "Todd".to_string(),
-        resolved_accepting_user: None,
-        wager: "$30".to_string(),
+        wager: "$30".into(),
         outcome: "Something that already happened".to_string(),
-        status: WagerStatus::Paid,
-        expected_settle_date: None,
+        ..Wager::fixture("Woody", "Todd", WagerStatus::Paid)
     })
     .await
     .unwrap();
     let found = repo.search_by_user("Woody").await.unwrap();
     assert_eq!(2, found.len());
 }
+
+#[tokio::test]
+async fn test_in_mem_search_unpaid() {
+    let repo = InMemWagerRepository::default();
+    let user_id: DiscordId = 695398918694895710.into();
+    for (wager_id, status) in [
+        (1, WagerStatus::Open),
+        (2, WagerStatus::OfferingWon),
+        (3, WagerStatus::AcceptingWon),
+        (4, WagerStatus::Paid),
+    ] {
+        repo.insert(Wager {
+            wager_id,
+            resolved_offering_user: Some(user_id.clone()),
+            wager: "$30".into(),
+            ..Wager::fixture("Harx", "Woody", status)
+        })
+        .await
+        .unwrap();
+    }
+    let found: Vec<u32> = repo
+        .search_unpaid_by_user_id(&user_id)
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![2, 3], found);
+}
+
+#[tokio::test]
+async fn test_in_mem_ledger() {
+    let repo = InMemWagerRepository::default();
+    let harx: DiscordId = 695398918694895710.into();
+    let cisco: DiscordId = 1050119194533961860.into();
+    for (wager_id, stake, accepting_wager, status) in [
+        (1, "$20", None, WagerStatus::OfferingWon),
+        (2, "$5", None, WagerStatus::AcceptingWon),
+        (3, "10 euro", None, WagerStatus::AcceptingWon),
+        (4, "a beer", None, WagerStatus::OfferingWon),
+        (5, "$100", None, WagerStatus::Open),
+        (6, "$30", Some("$10"), WagerStatus::OfferingWon),
+        (7, "$30", Some("$10"), WagerStatus::AcceptingWon),
+    ] {
+        repo.insert(Wager {
+            wager_id,
+            resolved_offering_user: Some(harx.clone()),
+            resolved_accepting_user: Some(cisco.clone()),
+            wager: stake.into(),
+            accepting_wager: accepting_wager.map(Stake::from),
+            ..Wager::fixture("Harx", "Cisco", status)
+        })
+        .await
+        .unwrap();
+    }
+    assert_eq!(
+        vec![
+            LedgerBalance {
+                counterparty: cisco.clone(),
+                currency: crate::wager::Currency::Eur,
+                amount: -1000,
+            },
+            LedgerBalance {
+                counterparty: cisco.clone(),
+                currency: crate::wager::Currency::Usd,
+                amount: -500,
+            },
+        ],
+        repo.ledger(&harx).await.unwrap()
+    );
+
+    assert_eq!(5, repo.settle_all(&cisco, &harx).await.unwrap().len());
+    assert!(repo.ledger(&harx).await.unwrap().is_empty());
+    let wager = repo.get(1).await.unwrap();
+    assert_eq!(WagerStatus::Paid, wager.status);
+    assert_eq!(Some(WagerStatus::OfferingWon), wager.paid_outcome);
+    assert_eq!(WagerStatus::OfferingWon, repo.get(4).await.unwrap().status);
+}
+
+#[tokio::test]
+async fn test_in_mem_search_settled() {
+    let repo = InMemWagerRepository::default();
+    let user_id: DiscordId = 695398918694895710.into();
+    for (wager_id, time, status) in [
+        (1, "2023-10-01T00:00:00+00:00", WagerStatus::OfferingWon),
+        (2, "2023-10-02T00:00:00+00:00", WagerStatus::NoBet),
+        (3, "2023-10-03T00:00:00+00:00", WagerStatus::Open),
+        (4, "2024-10-01T00:00:00+00:00", WagerStatus::Paid),
+    ] {
+        repo.insert(Wager {
+            wager_id,
+            time: time.to_string(),
+            resolved_offering_user: Some(user_id.clone()),
+            ..Wager::fixture("Harx", "Woody", status)
+        })
+        .await
+        .unwrap();
+    }
+    let range = DateRange {
+        from: chrono::NaiveDate::from_ymd_opt(2023, 9, 1),
+        to: chrono::NaiveDate::from_ymd_opt(2023, 10, 1),
+    };
+    let found: Vec<u32> = repo
+        .search_settled(&DateRange::default())
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![1, 2, 4], found);
+    let found = repo
+        .search_settled_by_user_id(&user_id, &range)
+        .await
+        .unwrap();
+    assert_eq!(1, found.len());
+}
+
+#[tokio::test]
+async fn test_in_mem_search_history() {
+    let repo = InMemWagerRepository::default();
+    let user_id: DiscordId = 695398918694895710.into();
+    for (wager_id, time, status) in [
+        (1, "2023-10-01T00:00:00+00:00", WagerStatus::OfferingWon),
+        (3, "2024-10-03T00:00:00+00:00", WagerStatus::Open),
+        (2, "2024-10-02T00:00:00+00:00", WagerStatus::Declined),
+    ] {
+        repo.insert(Wager {
+            wager_id,
+            time: time.to_string(),
+            resolved_offering_user: Some(user_id.clone()),
+            ..Wager::fixture("Harx", "Woody", status)
+        })
+        .await
+        .unwrap();
+    }
+    let range = DateRange {
+        from: chrono::NaiveDate::from_ymd_opt(2024, 1, 1),
+        to: None,
+    };
+    let found: Vec<u32> = repo
+        .search_history_by_user_id(&user_id, &range)
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![3, 2], found);
+}
+
+#[tokio::test]
+async fn test_in_mem_link_user() {
+    let repo = InMemWagerRepository::default();
+    let harx: DiscordId = 695398918694895710.into();
+    let woody: DiscordId = 1050119194533961860.into();
+    for (wager_id, offering, accepting) in [
+        (1, "Harx", "Woody"),
+        (2, "woody", "Harx"),
+        (3, "Harx", "Todd"),
+    ] {
+        repo.insert(Wager {
+            wager_id,
+            ..Wager::fixture(offering, accepting, WagerStatus::Open)
+        })
+        .await
+        .unwrap();
+    }
+    let found: Vec<u32> = repo
+        .search_history_by_user("Woody", &DateRange::default())
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![2, 1], found);
+
+    assert_eq!(2, repo.link_user("Woody", &woody).await.unwrap());
+    assert_eq!(3, repo.link_user("Harx", &harx).await.unwrap());
+    let wager = repo.get(2).await.unwrap();
+    assert_eq!(Some(woody.clone()), wager.resolved_offering_user);
+    assert_eq!(Some(harx), wager.resolved_accepting_user);
+    assert_eq!(0, repo.link_user("Woody", &woody).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_in_mem_search_overdue() {
+    let repo = InMemWagerRepository::default();
+    let today = NaiveDate::from_ymd_opt(2024, 10, 10).unwrap();
+    for (wager_id, settle_date, status) in [
+        (1, NaiveDate::from_ymd_opt(2024, 10, 1), WagerStatus::Open),
+        (2, NaiveDate::from_ymd_opt(2024, 10, 10), WagerStatus::Open),
+        (3, None, WagerStatus::Open),
+        (
+            4,
+            NaiveDate::from_ymd_opt(2024, 10, 1),
+            WagerStatus::OfferingWon,
+        ),
+    ] {
+        repo.insert(Wager {
+            wager_id,
+            expected_settle_date: settle_date,
+            ..Wager::fixture("Harx", "Woody", status)
+        })
+        .await
+        .unwrap();
+    }
+    let found: Vec<u32> = repo
+        .search_overdue(today, 3)
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![1], found);
+
+    assert!(repo.claim_reminder(1, today).await.unwrap());
+    assert!(!repo.claim_reminder(1, today).await.unwrap());
+    assert!(repo.search_overdue(today, 3).await.unwrap().is_empty());
+    let later = today + chrono::Days::new(3);
+    let found: Vec<u32> = repo
+        .search_overdue(later, 3)
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![1, 2], found);
+    let found: Vec<u32> = repo
+        .search_overdue(later, 0)
+        .await
+        .unwrap()
+        .iter()
+        .map(|wager| wager.wager_id)
+        .collect();
+    assert_eq!(vec![2], found);
+}
+
+#[tokio::test]
+async fn test_in_mem_revisions() {
+    let repo = InMemWagerRepository::default();
+    let harx: DiscordId = 695398918694895710.into();
+    let wager = Wager {
+        wager_id: 1,
+        resolved_offering_user: Some(harx.clone()),
+        ..Wager::fixture("Harx", "Woody", WagerStatus::Open)
+    };
+    repo.insert(wager.clone()).await.unwrap();
+    let mut revised = wager.terms();
+    revised.wager = "$25".into();
+    let revision = WagerRevision {
+        revision_id: 0,
+        wager_id: 1,
+        time: chrono::Utc::now().to_rfc3339(),
+        proposed_by: harx,
+        status: RevisionStatus::Proposed,
+        previous: wager.terms(),
+        revised: revised.clone(),
+    };
+    let rejected = repo
+        .propose_revision(revision.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(None, repo.propose_revision(revision.clone()).await.unwrap());
+
+    assert!(repo
+        .resolve_revision(rejected as i32, RevisionStatus::Rejected, &[])
+        .await
+        .unwrap());
+    assert_eq!(wager, repo.get(1).await.unwrap());
+    assert!(repo.amended(&[1]).await.unwrap().is_empty());
+
+    let accepted = repo.propose_revision(revision).await.unwrap().unwrap();
+    assert_ne!(rejected, accepted);
+
+    assert!(repo
+        .resolve_revision(accepted as i32, RevisionStatus::Accepted, &[])
+        .await
+        .unwrap());
+    assert!(!repo
+        .resolve_revision(accepted as i32, RevisionStatus::Rejected, &[])
+        .await
+        .unwrap());
+    assert_eq!(revised, repo.get(1).await.unwrap().terms());
+    assert_eq!(vec![1], repo.amended(&[1, 2]).await.unwrap());
+    assert_eq!(
+        Some(RevisionStatus::Accepted),
+        repo.get_revision(accepted as i32)
+            .await
+            .map(|revision| revision.status)
+    );
+}
+
+#[tokio::test]
+async fn test_in_mem_update_status_conflict() {
+    let repo = InMemWagerRepository::default();
+    let mut wager = Wager {
+        wager_id: 1,
+        ..Wager::fixture("Harx", "Woody", WagerStatus::Open)
+    };
+    repo.insert(wager.clone()).await.unwrap();
+
+    wager.status = WagerStatus::OfferingWon;
+    repo.update_status(1, WagerStatus::Open, &wager, &[])
+        .await
+        .unwrap();
+    let mut overwrite = wager.clone();
+    overwrite.status = WagerStatus::AcceptingWon;
+    assert_eq!(
+        Err(Error::Conflict(Box::new(wager.clone()))),
+        repo.update_status(1, WagerStatus::Open, &overwrite, &[])
+            .await
+    );
+    assert_eq!(Some(wager), repo.get(1).await);
+}
+
+#[tokio::test]
+async fn test_in_mem_take_offer() {
+    let repo = InMemWagerRepository::default();
+    let harx: DiscordId = 695398918694895710.into();
+    let woody: DiscordId = 1050119194533961860.into();
+    repo.insert(Wager {
+        wager_id: 1,
+        ..Wager::fixture("Cisco", OPEN_OFFER_ACCEPTING, WagerStatus::OpenOffer)
+    })
+    .await
+    .unwrap();
+
+    assert!(repo.take_offer(1, "Harx", &harx, &[]).await.unwrap());
+    assert!(!repo.take_offer(1, "Woody", &woody, &[]).await.unwrap());
+    let wager = repo.get(1).await.unwrap();
+    assert_eq!(WagerStatus::Open, wager.status);
+    assert_eq!("Harx", wager.accepting);
+    assert_eq!(Some(harx), wager.resolved_accepting_user);
+}
+
+#[tokio::test]
+async fn test_in_mem_conformance() {
+    crate::repos::conformance::wager_repository(&InMemWagerRepository::default()).await;
+}
@@ -1,4 +1,5 @@
 use crate::error::Error;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::sync::{Arc, Mutex};
 
@@ -14,6 +15,7 @@ pub trait WhoisRepository: Send + Sync {
         &self,
         discord_id: u64,
     ) -> impl Future<Output = Result<Option<WhoisPerson>, Error>> + Send;
+    fn search(&self, name: &str) -> impl Future<Output = Result<Vec<WhoisPerson>, Error>> + Send;
     fn add(
         &self,
         discord_id: u64,
@@ -34,26 +36,51 @@ pub trait WhoisRepository: Send + Sync {
     ) -> impl Future<Output = Result<(), Error>> + Send;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct InMemWhoisRepository {
-    person: Arc<Mutex<Result<Option<WhoisPerson>, Error>>>,
+    people: Arc<Mutex<BTreeMap<u64, WhoisPerson>>>,
 }
 
-impl Default for InMemWhoisRepository {
-    fn default() -> Self {
-        Self {
-            person: Arc::new(Mutex::new(Ok(None))),
-        }
+impl WhoisRepository for InMemWhoisRepository {
+    async fn get_by_discord_id(&self, discord_id: u64) -> Result<Option<WhoisPerson>, Error> {
+        Ok(self.people.lock().unwrap().get(&discord_id).cloned())
     }
-}
 
-impl WhoisRepository for InMemWhoisRepository {
-    async fn get_by_discord_id(&self, _: u64) -> Result<Option<WhoisPerson>, Error> {
-        self.person.lock().unwrap().clone()
+    async fn search(&self, name: &str) -> Result<Vec<WhoisPerson>, Error> {
+        let name = name.to_lowercase();
+        let mut found: Vec<WhoisPerson> = self
+            .people
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|person| {
+                person.human_name.to_lowercase().contains(&name)
+                    || person.hash_name.to_lowercase().contains(&name)
+            })
+            .cloned()
+            .collect();
+        found.sort_by(|a, b| a.human_name.cmp(&b.human_name));
+        found.truncate(25);
+        Ok(found)
     }
 
     async fn add(&self, discord_id: u64, human_name: &str, hash_name: &str) -> Result<(), Error> {
-        self.update(discord_id, human_name, hash_name).await
+        let mut people = self.people.lock().unwrap();
+        if people.contains_key(&discord_id) {
+            return Err(Error::Database(format!(
+                "whois entry for {} already exists",
+                discord_id
+            )));
+        }
+        people.insert(
+            discord_id,
+            WhoisPerson {
+                discord_id,
+                human_name: human_name.to_string(),
+                hash_name: hash_name.to_string(),
+            },
+        );
+        Ok(())
     }
 
     async fn update(
@@ -62,11 +89,10 @@ impl WhoisRepository for InMemWhoisRepository {
         human_name: &str,
         hash_name: &str,
     ) -> Result<(), Error> {
-        *self.person.lock().unwrap() = Ok(Some(WhoisPerson {
-            discord_id,
-            human_name: human_name.to_string(),
-            hash_name: hash_name.to_string(),
-        }));
+        if let Some(person) = self.people.lock().unwrap().get_mut(&discord_id) {
+            person.human_name = human_name.to_string();
+            person.hash_name = hash_name.to_string();
+        }
         Ok(())
     }
 
@@ -76,6 +102,15 @@ impl WhoisRepository for InMemWhoisRepository {
         human_name: &str,
         hash_name: &str,
     ) -> Result<(), Error> {
-        self.update(discord_id, human_name, hash_name).await
+        match self.add(discord_id, human_name, hash_name).await {
+            Ok(()) => Ok(()),
+            Err(Error::Database(_)) => self.update(discord_id, human_name, hash_name).await,
+            Err(err) => Err(err),
+        }
     }
 }
+
+#[tokio::test]
+async fn test_in_mem_conformance() {
+    crate::repos::conformance::whois_repository(&InMemWhoisRepository::default()).await;
+}
@@ -1,8 +1,14 @@
 use chrono::NaiveDate;
+use sha2::{Digest, Sha256};
 use std::fmt::{Display, Formatter};
 
 use crate::discord_id::DiscordId;
 
+// who an open offer is with until someone takes it; nobody goes by an empty name, so name
+// searches and linking can't mistake it for a member
+pub const OPEN_OFFER_ACCEPTING: &str = "";
+const OPEN_OFFER_NAME: &str = "anyone";
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Wager {
     pub wager_id: u32,
@@ -11,46 +17,456 @@ pub struct Wager {
     pub resolved_offering_user: Option<DiscordId>,
     pub accepting: String,
     pub resolved_accepting_user: Option<DiscordId>,
-    pub wager: String,
+    // what the offering side puts up, and what the accepting side puts up when the bet has odds
+    pub wager: Stake,
+    pub accepting_wager: Option<Stake>,
     pub outcome: String,
     pub status: WagerStatus,
     pub expected_settle_date: Option<NaiveDate>,
+    pub proposal: Option<SettlementProposal>,
+    pub paid_outcome: Option<WagerStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementProposal {
+    pub outcome: WagerStatus,
+    pub proposed_by: DiscordId,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WagerTerms {
+    pub wager: Stake,
+    pub accepting_wager: Option<Stake>,
+    pub outcome: String,
+    pub expected_settle_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WagerRevision {
+    pub revision_id: u32,
+    pub wager_id: u32,
+    pub time: String,
+    pub proposed_by: DiscordId,
+    pub status: RevisionStatus,
+    pub previous: WagerTerms,
+    pub revised: WagerTerms,
+}
+
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum RevisionStatus {
+    Proposed = 0,
+    Accepted = 1,
+    Rejected = 2,
+}
+
+impl RevisionStatus {
+    pub fn as_i16(&self) -> i16 {
+        *self as i16
+    }
+    pub fn from_i16(val: i16) -> Self {
+        match val {
+            0 => RevisionStatus::Proposed,
+            1 => RevisionStatus::Accepted,
+            2 => RevisionStatus::Rejected,
+            v => panic!("attempt to convert {} to RevisionStatus", v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WagerEvent {
+    pub wager_id: u32,
+    pub time: String,
+    pub actor: Option<DiscordId>,
+    pub action: WagerAction,
+    pub previous_status: Option<WagerStatus>,
+    pub status: WagerStatus,
+    pub detail: String,
+}
+
+impl WagerEvent {
+    pub fn new(
+        wager_id: u32,
+        actor: Option<DiscordId>,
+        action: WagerAction,
+        previous_status: Option<WagerStatus>,
+        status: WagerStatus,
+    ) -> Self {
+        Self {
+            wager_id,
+            time: chrono::Utc::now().to_rfc3339(),
+            actor,
+            action,
+            previous_status,
+            status,
+            detail: "".to_string(),
+        }
+    }
+
+    pub fn with_detail(self, detail: &str) -> Self {
+        Self {
+            detail: detail.to_string(),
+            ..self
+        }
+    }
+
+    // for a wager that has just been marked paid, from the outcome it was paid on
+    pub fn paid(wager: &Wager, actor: &DiscordId) -> Self {
+        Self::new(
+            wager.wager_id,
+            Some(actor.clone()),
+            WagerAction::Paid,
+            wager.paid_outcome,
+            wager.status,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum WagerAction {
+    Created = 0,
+    Accepted = 1,
+    Declined = 2,
+    Withdrawn = 3,
+    SettlementProposed = 4,
+    Settled = 5,
+    Disputed = 6,
+    Paid = 7,
+    Voided = 8,
+    Edited = 9,
+    Expired = 10,
+    PaymentSent = 11,
+    EditRejected = 12,
+    EditWithdrawn = 13,
+}
+
+impl WagerAction {
+    pub fn as_i16(&self) -> i16 {
+        *self as i16
+    }
+    pub fn from_i16(val: i16) -> Self {
+        match val {
+            0 => WagerAction::Created,
+            1 => WagerAction::Accepted,
+            2 => WagerAction::Declined,
+            3 => WagerAction::Withdrawn,
+            4 => WagerAction::SettlementProposed,
+            5 => WagerAction::Settled,
+            6 => WagerAction::Disputed,
+            7 => WagerAction::Paid,
+            8 => WagerAction::Voided,
+            9 => WagerAction::Edited,
+            10 => WagerAction::Expired,
+            11 => WagerAction::PaymentSent,
+            12 => WagerAction::EditRejected,
+            13 => WagerAction::EditWithdrawn,
+            v => panic!("attempt to convert {} to WagerAction", v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerBalance {
+    pub counterparty: DiscordId,
+    pub currency: Currency,
+    // positive when the counterparty owes the user, in minor units of the currency
+    pub amount: i64,
+}
+
+impl LedgerBalance {
+    // short enough to carry in a custom id, so a request to settle balances can tell they changed
+    pub fn digest(balances: &[LedgerBalance]) -> String {
+        let mut hasher = Sha256::new();
+        for balance in balances {
+            hasher.update(format!(
+                "{}:{}:{};",
+                balance.counterparty.value(),
+                balance.currency.code(),
+                balance.amount
+            ));
+        }
+        hasher.finalize()[..4]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    // wager times are rfc3339 strings, so the bounds are compared as text: [from, to + 1 day)
+    pub fn bounds(&self) -> (Option<String>, Option<String>) {
+        (
+            self.from.map(|from| from.to_string()),
+            self.to
+                .and_then(|to| to.succ_opt())
+                .map(|until| until.to_string()),
+        )
+    }
+
+    pub fn contains(&self, time: &str) -> bool {
+        let (from, until) = self.bounds();
+        from.is_none_or(|from| time >= from.as_str())
+            && until.is_none_or(|until| time < until.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BettorRecord {
+    pub user: DiscordId,
+    pub won: u32,
+    pub lost: u32,
+    pub no_bet: u32,
+    pub winnings: Vec<(Currency, i64)>,
+}
+
+impl BettorRecord {
+    fn new(user: &DiscordId) -> Self {
+        Self {
+            user: user.clone(),
+            won: 0,
+            lost: 0,
+            no_bet: 0,
+            winnings: vec![],
+        }
+    }
+
+    pub fn tally(wagers: &[Wager]) -> Vec<BettorRecord> {
+        let mut records: Vec<BettorRecord> = vec![];
+        for wager in wagers {
+            for user in [
+                &wager.resolved_offering_user,
+                &wager.resolved_accepting_user,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let record = match records.iter().position(|r| &r.user == user) {
+                    Some(pos) => &mut records[pos],
+                    None => {
+                        records.push(BettorRecord::new(user));
+                        records.last_mut().unwrap()
+                    }
+                };
+                record.add(wager);
+            }
+        }
+        records.sort_by(|a, b| {
+            (b.won as i64 - b.lost as i64, b.won, a.user.value()).cmp(&(
+                a.won as i64 - a.lost as i64,
+                a.won,
+                b.user.value(),
+            ))
+        });
+        records
+    }
+
+    fn add(&mut self, wager: &Wager) {
+        if wager.status == WagerStatus::NoBet {
+            self.no_bet += 1;
+            return;
+        }
+        let won = match wager.won_status() {
+            Some(WagerStatus::OfferingWon) => {
+                wager.resolved_offering_user.as_ref() == Some(&self.user)
+            }
+            Some(WagerStatus::AcceptingWon) => {
+                wager.resolved_accepting_user.as_ref() == Some(&self.user)
+            }
+            _ => return,
+        };
+        match won {
+            true => self.won += 1,
+            false => self.lost += 1,
+        }
+        if let Some(Stake::Money { amount, currency }) = wager.stake_owed(wager.won_status()) {
+            let amount = if won { *amount } else { -amount };
+            let currency = *currency;
+            match self.winnings.iter_mut().find(|(c, _)| *c == currency) {
+                Some((_, total)) => *total += amount,
+                None => self.winnings.push((currency, amount)),
+            }
+        }
+    }
 }
 
 impl Display for Wager {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let offering = match &self.resolved_offering_user {
-            Some(id) => id.to_string(),
-            None => self.offering.to_string(),
-        };
-        let accepting = match &self.resolved_accepting_user {
-            Some(id) => id.to_string(),
-            None => self.accepting.to_string(),
-        };
         write!(
             f,
             "{} vs {}, wager: {} - {}{}",
-            offering,
-            accepting,
-            self.wager,
+            self.offering_mention(),
+            self.accepting_mention(),
+            self.stakes_string(),
             self.outcome,
             self.settlement_tail()
         )
     }
 }
 
+// a $20 bet on the Jets for tests, override whatever else matters with `..Wager::fixture(..)`
+#[cfg(any(test, feature = "conformance"))]
 impl Wager {
+    pub fn fixture(offering: &str, accepting: &str, status: WagerStatus) -> Wager {
+        Wager {
+            wager_id: 0,
+            time: chrono::Utc::now().to_rfc3339(),
+            offering: offering.to_string(),
+            resolved_offering_user: None,
+            accepting: accepting.to_string(),
+            resolved_accepting_user: None,
+            wager: "$20".into(),
+            accepting_wager: None,
+            outcome: "Jets beat the Oilers".to_string(),
+            status,
+            expected_settle_date: None,
+            proposal: None,
+            paid_outcome: None,
+        }
+    }
+}
+
+impl Wager {
+    pub fn is_participant(&self, user_id: &DiscordId) -> bool {
+        self.resolved_offering_user.as_ref() == Some(user_id)
+            || self.resolved_accepting_user.as_ref() == Some(user_id)
+    }
+
+    pub fn counterparty(&self, user_id: &DiscordId) -> Option<&DiscordId> {
+        if self.resolved_offering_user.as_ref() == Some(user_id) {
+            self.resolved_accepting_user.as_ref()
+        } else if self.resolved_accepting_user.as_ref() == Some(user_id) {
+            self.resolved_offering_user.as_ref()
+        } else {
+            None
+        }
+    }
+
+    pub fn won_status(&self) -> Option<WagerStatus> {
+        match self.status {
+            WagerStatus::OfferingWon | WagerStatus::AcceptingWon => Some(self.status),
+            WagerStatus::Paid => self.paid_outcome,
+            _ => None,
+        }
+    }
+
+    pub fn winner(&self) -> Option<&DiscordId> {
+        match self.won_status() {
+            Some(WagerStatus::OfferingWon) => self.resolved_offering_user.as_ref(),
+            Some(WagerStatus::AcceptingWon) => self.resolved_accepting_user.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn loser(&self) -> Option<&DiscordId> {
+        match self.won_status() {
+            Some(WagerStatus::OfferingWon) => self.resolved_accepting_user.as_ref(),
+            Some(WagerStatus::AcceptingWon) => self.resolved_offering_user.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn accepting_stake(&self) -> &Stake {
+        self.accepting_wager.as_ref().unwrap_or(&self.wager)
+    }
+
+    // the loser pays the winner what the losing side put up
+    pub fn stake_owed(&self, outcome: Option<WagerStatus>) -> Option<&Stake> {
+        match outcome {
+            Some(WagerStatus::OfferingWon) => Some(self.accepting_stake()),
+            Some(WagerStatus::AcceptingWon) => Some(&self.wager),
+            _ => None,
+        }
+    }
+
+    // what the user stands to lose on the bet
+    pub fn stake_at_risk(&self, user_id: &DiscordId) -> Option<&Stake> {
+        if self.resolved_offering_user.as_ref() == Some(user_id) {
+            Some(&self.wager)
+        } else if self.resolved_accepting_user.as_ref() == Some(user_id) {
+            Some(self.accepting_stake())
+        } else {
+            None
+        }
+    }
+
+    pub fn debt_string(&self, outcome: WagerStatus) -> Option<String> {
+        let stake = self.stake_owed(Some(outcome))?;
+        let (winner, loser) = match outcome {
+            WagerStatus::OfferingWon => (self.offering_mention(), self.accepting_mention()),
+            _ => (self.accepting_mention(), self.offering_mention()),
+        };
+        Some(format!("{} owes {} {}", loser, winner, stake))
+    }
+
+    pub fn terms(&self) -> WagerTerms {
+        WagerTerms {
+            wager: self.wager.clone(),
+            accepting_wager: self.accepting_wager.clone(),
+            outcome: self.outcome.to_string(),
+            expected_settle_date: self.expected_settle_date,
+        }
+    }
+
+    pub fn with_terms(&self, terms: &WagerTerms) -> Wager {
+        Wager {
+            wager: terms.wager.clone(),
+            accepting_wager: terms.accepting_wager.clone(),
+            outcome: terms.outcome.to_string(),
+            expected_settle_date: terms.expected_settle_date,
+            ..self.clone()
+        }
+    }
+
     pub fn simplified_string(&self) -> String {
         format!(
             "{} vs {}, wager: {} - {}{}",
             self.offering,
-            self.accepting,
-            self.wager,
+            self.accepting_name(),
+            self.stakes_string(),
             self.outcome,
             self.settlement_tail()
         )
     }
 
+    fn stakes_string(&self) -> String {
+        match &self.accepting_wager {
+            Some(accepting_wager) => format!("{} to {}", self.wager, accepting_wager),
+            None => self.wager.to_string(),
+        }
+    }
+
+    fn offering_mention(&self) -> String {
+        match &self.resolved_offering_user {
+            Some(id) => id.to_string(),
+            None => self.offering.to_string(),
+        }
+    }
+
+    fn accepting_mention(&self) -> String {
+        match &self.resolved_accepting_user {
+            Some(id) => id.to_string(),
+            None => self.accepting_name().to_string(),
+        }
+    }
+
+    pub fn accepting_name(&self) -> &str {
+        match self.accepting.as_str() {
+            OPEN_OFFER_ACCEPTING => OPEN_OFFER_NAME,
+            accepting => accepting,
+        }
+    }
+
+    // whether the accepting side goes by this name, never true for an untaken open offer
+    pub fn accepting_named(&self, user: &str) -> bool {
+        self.accepting != OPEN_OFFER_ACCEPTING && self.accepting.eq_ignore_ascii_case(user)
+    }
+
     fn settlement_tail(&self) -> String {
         match (self.expected_settle_date, self.status) {
             (Some(date), WagerStatus::Open) => format!(" (settles: {})", date.format("%b %e, %Y")),
@@ -66,6 +482,11 @@ pub enum WagerStatus {
     OfferingWon = 2,
     AcceptingWon = 3,
     NoBet = 4,
+    AwaitingAcceptance = 5,
+    Declined = 6,
+    SettlementProposed = 7,
+    Disputed = 8,
+    OpenOffer = 9,
 }
 
 impl WagerStatus {
@@ -79,9 +500,242 @@ impl WagerStatus {
             2 => WagerStatus::OfferingWon,
             3 => WagerStatus::AcceptingWon,
             4 => WagerStatus::NoBet,
+            5 => WagerStatus::AwaitingAcceptance,
+            6 => WagerStatus::Declined,
+            7 => WagerStatus::SettlementProposed,
+            8 => WagerStatus::Disputed,
+            9 => WagerStatus::OpenOffer,
             v => panic!("attempt to convert {} to WagerStatus", v),
         }
     }
+
+    // still has stakes riding on it, whether or not it has been answered yet
+    pub fn is_unsettled(&self) -> bool {
+        matches!(
+            self,
+            WagerStatus::Open
+                | WagerStatus::AwaitingAcceptance
+                | WagerStatus::OpenOffer
+                | WagerStatus::SettlementProposed
+                | WagerStatus::Disputed
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stake {
+    // amount is held in minor units (cents, pence) of the currency
+    Money { amount: i64, currency: Currency },
+    Item { quantity: u32, item: String },
+    Unparsed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl Currency {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_uppercase().as_str() {
+            "USD" => Some(Currency::Usd),
+            "EUR" => Some(Currency::Eur),
+            "GBP" => Some(Currency::Gbp),
+            _ => None,
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            '$' => Some(Currency::Usd),
+            '€' => Some(Currency::Eur),
+            '£' => Some(Currency::Gbp),
+            _ => None,
+        }
+    }
+
+    fn from_word(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "buck" | "bucks" | "dollar" | "dollars" => Some(Currency::Usd),
+            "euro" | "euros" => Some(Currency::Eur),
+            "pound" | "pounds" | "quid" => Some(Currency::Gbp),
+            code => Self::from_code(code),
+        }
+    }
+}
+
+impl Stake {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        match parse_money(value) {
+            Some((amount, currency)) if amount > 0 => Some(Stake::Money { amount, currency }),
+            Some(_) => None,
+            None => parse_item(value),
+        }
+    }
+
+    pub fn is_parsed(&self) -> bool {
+        !matches!(self, Stake::Unparsed(_))
+    }
+
+    // the other side's stake when this side gives `odds` (3-1 means 3 of this stake for every 1),
+    // money is rounded to the nearest cent, items are None when they don't divide evenly,
+    // and either is None when it rounds to nothing or is too big to hold
+    pub fn at_odds(&self, odds: (u32, u32)) -> Option<Stake> {
+        let (given, taken) = odds;
+        match self {
+            Stake::Money { amount, currency } => {
+                let amount = amount
+                    .checked_mul(taken as i64)?
+                    .checked_add(given as i64 / 2)?
+                    / given as i64;
+                match amount > 0 {
+                    true => Some(Stake::Money {
+                        amount,
+                        currency: *currency,
+                    }),
+                    false => None,
+                }
+            }
+            Stake::Item { quantity, item } => match quantity.checked_mul(taken)? {
+                total if total % given == 0 => Some(Stake::Item {
+                    quantity: total / given,
+                    item: item.to_string(),
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_odds(value: &str) -> Option<(u32, u32)> {
+    let value = value.trim().to_lowercase();
+    let (given, taken) = ["-to-", " to ", ":", "-", "/"]
+        .iter()
+        .find_map(|separator| value.split_once(separator))?;
+    match (given.trim().parse::<u32>(), taken.trim().parse::<u32>()) {
+        (Ok(given), Ok(taken)) if given > 0 && taken > 0 => Some((given, taken)),
+        _ => None,
+    }
+}
+
+impl From<&str> for Stake {
+    fn from(value: &str) -> Self {
+        Stake::parse(value).unwrap_or(Stake::Unparsed(value.to_string()))
+    }
+}
+
+impl Display for Stake {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stake::Money { amount, currency } => {
+                if amount % 100 == 0 {
+                    write!(f, "{}{}", currency.symbol(), amount / 100)
+                } else {
+                    write!(
+                        f,
+                        "{}{}.{:02}",
+                        currency.symbol(),
+                        amount / 100,
+                        amount % 100
+                    )
+                }
+            }
+            Stake::Item { quantity, item } => write!(f, "{} {}", quantity, item),
+            Stake::Unparsed(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn parse_money(value: &str) -> Option<(i64, Currency)> {
+    let first = value.chars().next()?;
+    if let Some(currency) = Currency::from_symbol(first) {
+        return parse_amount(value[first.len_utf8()..].trim()).map(|amount| (amount, currency));
+    }
+    let last = value.chars().last()?;
+    if let Some(currency) = Currency::from_symbol(last) {
+        let end = value.len() - last.len_utf8();
+        return parse_amount(value[..end].trim()).map(|amount| (amount, currency));
+    }
+    let words: Vec<&str> = value.split_whitespace().collect();
+    match words.as_slice() {
+        [amount, word] => match (parse_amount(amount), Currency::from_word(word)) {
+            (Some(amount), Some(currency)) => Some((amount, currency)),
+            _ => match (Currency::from_code(amount), parse_amount(word)) {
+                (Some(currency), Some(amount)) => Some((amount, currency)),
+                _ => None,
+            },
+        },
+        _ => None,
+    }
+}
+
+fn parse_amount(value: &str) -> Option<i64> {
+    let value = value.replace(',', "");
+    let (whole, fraction) = match value.split_once('.') {
+        Some((whole, fraction)) => (whole.to_string(), fraction.to_string()),
+        None => (value, String::new()),
+    };
+    if whole.is_empty()
+        || fraction.len() > 2
+        || !whole.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole = whole.parse::<i64>().ok()?;
+    let fraction = match fraction.len() {
+        0 => 0,
+        1 => fraction.parse::<i64>().ok()? * 10,
+        _ => fraction.parse::<i64>().ok()?,
+    };
+    whole.checked_mul(100)?.checked_add(fraction)
+}
+
+fn parse_item(value: &str) -> Option<Stake> {
+    let (quantity, item) = value.split_once(char::is_whitespace)?;
+    let quantity = match quantity.to_lowercase().as_str() {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        "dozen" => 12,
+        number => number.parse::<u32>().ok()?,
+    };
+    let item = item.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let is_item_name = |c: char| !c.is_ascii_digit() && Currency::from_symbol(c).is_none();
+    if quantity == 0 || item.is_empty() || !item.chars().all(is_item_name) {
+        return None;
+    }
+    Some(Stake::Item { quantity, item })
 }
 
 #[test]
@@ -92,6 +746,11 @@ fn test_wager_status() {
         WagerStatus::OfferingWon,
         WagerStatus::AcceptingWon,
         WagerStatus::NoBet,
+        WagerStatus::AwaitingAcceptance,
+        WagerStatus::Declined,
+        WagerStatus::SettlementProposed,
+        WagerStatus::Disputed,
+        WagerStatus::OpenOffer,
     ];
     for case in test_cases {
         let i16_value = case.as_i16();
@@ -99,6 +758,20 @@ fn test_wager_status() {
     }
 }
 
+#[test]
+fn test_ledger_digest() {
+    let balance = |amount| LedgerBalance {
+        counterparty: 1050119194533961860.into(),
+        currency: Currency::Usd,
+        amount,
+    };
+    assert_eq!("11bf3b34", LedgerBalance::digest(&[balance(-1500)]));
+    assert_ne!(
+        LedgerBalance::digest(&[balance(-1500)]),
+        LedgerBalance::digest(&[balance(-2500)])
+    );
+}
+
 #[test]
 fn test_format() {
     let wager = Wager {
@@ -108,10 +781,13 @@ fn test_format() {
         resolved_offering_user: Some(1234567890.into()),
         accepting: "Woody".to_string(),
         resolved_accepting_user: None,
-        wager: "$20".to_string(),
+        wager: "$20".into(),
+        accepting_wager: None,
         outcome: "Cowboys over the Raiders".to_string(),
         status: WagerStatus::Open,
         expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
+        proposal: None,
+        paid_outcome: None,
     };
     assert_eq!(
         wager.to_string(),
@@ -128,10 +804,13 @@ fn test_format_no_expected_settlement_date() {
         resolved_offering_user: Some(1234567890.into()),
         accepting: "Woody".to_string(),
         resolved_accepting_user: None,
-        wager: "$20".to_string(),
+        wager: "$20".into(),
+        accepting_wager: None,
         outcome: "Cowboys over the Raiders".to_string(),
         status: WagerStatus::Open,
         expected_settle_date: None,
+        proposal: None,
+        paid_outcome: None,
     };
     assert_eq!(
         wager.to_string(),
@@ -139,6 +818,82 @@ fn test_format_no_expected_settlement_date() {
     );
 }
 
+#[test]
+fn test_winner_after_payment() {
+    let mut wager = Wager {
+        resolved_offering_user: Some(1234567890.into()),
+        resolved_accepting_user: Some(987654321.into()),
+        ..Wager::fixture("Harx", "Woody", WagerStatus::AcceptingWon)
+    };
+    assert_eq!(Some(&987654321.into()), wager.winner());
+    assert_eq!(Some(&1234567890.into()), wager.loser());
+
+    wager.status = WagerStatus::Paid;
+    wager.paid_outcome = Some(WagerStatus::AcceptingWon);
+    assert_eq!(Some(WagerStatus::AcceptingWon), wager.won_status());
+    assert_eq!(Some(&987654321.into()), wager.winner());
+
+    wager.status = WagerStatus::NoBet;
+    assert_eq!(None, wager.winner());
+}
+
+#[test]
+fn test_tally_records() {
+    let wager = |offering: i64, accepting: i64, stake: &str, status: WagerStatus| Wager {
+        resolved_offering_user: Some(offering.into()),
+        resolved_accepting_user: Some(accepting.into()),
+        wager: stake.into(),
+        ..Wager::fixture("Harx", "Woody", status)
+    };
+    let mut paid = wager(1, 2, "$5", WagerStatus::Paid);
+    paid.paid_outcome = Some(WagerStatus::AcceptingWon);
+    let records = BettorRecord::tally(&[
+        wager(1, 2, "$20", WagerStatus::OfferingWon),
+        wager(3, 1, "a beer", WagerStatus::OfferingWon),
+        wager(2, 3, "$10", WagerStatus::NoBet),
+        paid,
+    ]);
+    assert_eq!(
+        vec![
+            BettorRecord {
+                user: 3.into(),
+                won: 1,
+                lost: 0,
+                no_bet: 1,
+                winnings: vec![],
+            },
+            BettorRecord {
+                user: 2.into(),
+                won: 1,
+                lost: 1,
+                no_bet: 1,
+                winnings: vec![(Currency::Usd, -1500)],
+            },
+            BettorRecord {
+                user: 1.into(),
+                won: 1,
+                lost: 2,
+                no_bet: 0,
+                winnings: vec![(Currency::Usd, 1500)],
+            },
+        ],
+        records
+    );
+}
+
+#[test]
+fn test_date_range() {
+    let range = DateRange {
+        from: NaiveDate::from_ymd_opt(2024, 9, 1),
+        to: NaiveDate::from_ymd_opt(2024, 12, 31),
+    };
+    assert!(range.contains("2024-09-01T00:00:00+00:00"));
+    assert!(range.contains("2024-12-31T23:59:59+00:00"));
+    assert!(!range.contains("2025-01-01T00:00:00+00:00"));
+    assert!(!range.contains("2024-08-31T23:59:59+00:00"));
+    assert!(DateRange::default().contains(""));
+}
+
 #[test]
 fn test_format_paid() {
     let wager = Wager {
@@ -148,13 +903,146 @@ fn test_format_paid() {
         resolved_offering_user: Some(1234567890.into()),
         accepting: "Woody".to_string(),
         resolved_accepting_user: None,
-        wager: "$20".to_string(),
+        wager: "$20".into(),
+        accepting_wager: None,
         outcome: "Cowboys over the Raiders".to_string(),
         status: WagerStatus::Paid,
         expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
+        proposal: None,
+        paid_outcome: None,
     };
     assert_eq!(
         wager.to_string(),
         "<@1234567890> vs Woody, wager: $20 - Cowboys over the Raiders"
     );
 }
+
+#[test]
+fn test_parse_odds() {
+    assert_eq!(Some((3, 1)), parse_odds("3-1"));
+    assert_eq!(Some((3, 1)), parse_odds("3 to 1"));
+    assert_eq!(Some((5, 2)), parse_odds(" 5:2 "));
+    assert_eq!(Some((3, 1)), parse_odds("3-to-1"));
+    assert_eq!(None, parse_odds("0-1"));
+    assert_eq!(None, parse_odds("$10"));
+    assert_eq!(None, parse_odds("a beer"));
+}
+
+#[test]
+fn test_stake_at_odds() {
+    assert_eq!(Some(Stake::from("$10")), Stake::from("$30").at_odds((3, 1)));
+    assert_eq!(
+        Some(Stake::from("$8.33")),
+        Stake::from("$25").at_odds((3, 1))
+    );
+    assert_eq!(Some(Stake::from("$50")), Stake::from("$20").at_odds((2, 5)));
+    assert_eq!(
+        Some(Stake::from("2 beers")),
+        Stake::from("6 beers").at_odds((3, 1))
+    );
+    assert_eq!(None, Stake::from("a beer").at_odds((3, 1)));
+    assert_eq!(
+        None,
+        Stake::from("$90000000000000000").at_odds((1, 4000000000))
+    );
+    assert_eq!(
+        None,
+        Stake::from("4000000000 beers").at_odds((1, 4000000000))
+    );
+    assert_eq!(None, Stake::from("bragging rights").at_odds((3, 1)));
+}
+
+#[test]
+fn test_format_with_odds() {
+    let wager = Wager {
+        resolved_offering_user: Some(1234567890.into()),
+        wager: "$30".into(),
+        accepting_wager: Some("$10".into()),
+        ..Wager::fixture("Harx", "Woody", WagerStatus::OfferingWon)
+    };
+    assert_eq!(
+        "<@1234567890> vs Woody, wager: $30 to $10 - Jets beat the Oilers",
+        wager.to_string()
+    );
+    assert_eq!(
+        Some("Woody owes <@1234567890> $10".to_string()),
+        wager.debt_string(WagerStatus::OfferingWon)
+    );
+    assert_eq!(
+        Some("<@1234567890> owes Woody $30".to_string()),
+        wager.debt_string(WagerStatus::AcceptingWon)
+    );
+    assert_eq!(None, wager.debt_string(WagerStatus::NoBet));
+}
+
+#[test]
+fn test_parse_money_stake() {
+    let usd = |amount| {
+        Some(Stake::Money {
+            amount,
+            currency: Currency::Usd,
+        })
+    };
+    assert_eq!(usd(2000), Stake::parse("$20"));
+    assert_eq!(usd(2000), Stake::parse(" $ 20 "));
+    assert_eq!(usd(2050), Stake::parse("$20.50"));
+    assert_eq!(usd(2050), Stake::parse("$20.5"));
+    assert_eq!(usd(100000), Stake::parse("$1,000"));
+    assert_eq!(usd(2000), Stake::parse("20 bucks"));
+    assert_eq!(usd(2000), Stake::parse("20 Dollars"));
+    assert_eq!(usd(2000), Stake::parse("USD 20"));
+    assert_eq!(
+        Some(Stake::Money {
+            amount: 2000,
+            currency: Currency::Eur
+        }),
+        Stake::parse("20€")
+    );
+    assert_eq!(
+        Some(Stake::Money {
+            amount: 500,
+            currency: Currency::Gbp
+        }),
+        Stake::parse("5 quid")
+    );
+    assert_eq!(None, Stake::parse("$0"));
+    assert_eq!(None, Stake::parse("$20.505"));
+    assert_eq!(None, Stake::parse("+1000 $50"));
+}
+
+#[test]
+fn test_parse_item_stake() {
+    assert_eq!(
+        Some(Stake::Item {
+            quantity: 1,
+            item: "beer".to_string()
+        }),
+        Stake::parse("a beer")
+    );
+    assert_eq!(
+        Some(Stake::Item {
+            quantity: 6,
+            item: "craft beers".to_string()
+        }),
+        Stake::parse("6  craft beers")
+    );
+    assert_eq!(None, Stake::parse("beer"));
+    assert_eq!(None, Stake::parse("0 beers"));
+    assert_eq!(None, Stake::parse("2 $5 beers"));
+    assert_eq!(None, Stake::parse(""));
+}
+
+#[test]
+fn test_unparsed_stake() {
+    let stake: Stake = "+1000 $50".into();
+    assert_eq!(Stake::Unparsed("+1000 $50".to_string()), stake);
+    assert!(!stake.is_parsed());
+    assert_eq!("+1000 $50", stake.to_string());
+}
+
+#[test]
+fn test_format_stake() {
+    assert_eq!("$20", Stake::from("20 bucks").to_string());
+    assert_eq!("€20.05", Stake::from("20.05 euro").to_string());
+    assert_eq!("1 beer", Stake::from("a beer").to_string());
+}
@@ -1,6 +1,9 @@
 use crate::interaction_request::user::User;
 use serde::{Deserialize, Serialize};
 
+// https://discord.com/developers/docs/topics/permissions#permissions-bitwise-permission-flags
+const ADMINISTRATOR: u64 = 1 << 3;
+
 // https://discord.com/developers/docs/resources/guild#guild-member-object
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct GuildMember {
@@ -26,4 +29,29 @@ impl GuildMember {
             None => Err(("GuildMember", "member").into()),
         }
     }
+
+    pub fn is_administrator(&self) -> bool {
+        match self.permissions.as_ref().map(|p| p.parse::<u64>()) {
+            Some(Ok(permissions)) => permissions & ADMINISTRATOR == ADMINISTRATOR,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interaction_request::GuildMember;
+
+    #[test]
+    fn is_administrator() {
+        let mut member: GuildMember = serde_json::from_str(
+            r#"{"joined_at":"2023-09-11T22:36:24.955000+00:00","deaf":false,"mute":false,"flags":0,"permissions":"562949953421311"}"#,
+        )
+        .unwrap();
+        assert!(member.is_administrator());
+        member.permissions = Some("559520526224960".to_string());
+        assert!(!member.is_administrator());
+        member.permissions = None;
+        assert!(!member.is_administrator());
+    }
 }
@@ -34,7 +34,10 @@ impl InteractionDataPayload {
             1 => Ok(InteractionData::Ping),
             2 => self.command_transform(),
             3 => self.message_transform(),
-            4 => self.command_transform(),
+            4 => match self.command_transform()? {
+                InteractionData::Command(data) => Ok(InteractionData::CommandAutocomplete(data)),
+                other => Ok(other),
+            },
             5 => self.modal_submit_transform(),
             _ => Err("unknown type".into()),
         }
@@ -117,6 +120,28 @@ impl ApplicationCommandInteractionData {
         ApplicationCommandInteractionData::recursive_option_key_values(&self.options)
     }
 
+    pub fn focused_option(&self) -> Option<&InteractionDataOption> {
+        ApplicationCommandInteractionData::recursive_focused_option(&self.options)
+    }
+
+    fn recursive_focused_option(
+        options: &[InteractionDataOption],
+    ) -> Option<&InteractionDataOption> {
+        for option in options {
+            if option.focused == Some(true) {
+                return Some(option);
+            }
+            if let Some(inner_options) = &option.options {
+                if let Some(focused) =
+                    ApplicationCommandInteractionData::recursive_focused_option(inner_options)
+                {
+                    return Some(focused);
+                }
+            }
+        }
+        None
+    }
+
     fn recursive_option_key_values(
         options: &Vec<InteractionDataOption>,
     ) -> HashMap<String, String> {
@@ -229,6 +254,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn autocomplete() {
+        let payload = r#"{
+  "id": "1165494543471353916",
+  "name": "settle",
+  "type": 1,
+  "options": [
+    {
+      "type": 3,
+      "name": "wager",
+      "value": "jets",
+      "focused": true
+    }
+  ]
+}"#;
+        let data = serde_json::from_str::<InteractionDataPayload>(payload)
+            .unwrap()
+            .transform_data(4)
+            .unwrap();
+        let data = match data {
+            InteractionData::CommandAutocomplete(data) => data,
+            other => panic!("expected autocomplete data, found {:?}", other),
+        };
+        let focused = data.focused_option().unwrap();
+        assert_eq!("wager", focused.name);
+        assert_eq!("jets", focused.value);
+    }
+
     #[test]
     fn application_command_missing_name() {
         let payload = r#"{
@@ -7,6 +7,8 @@ pub struct InteractionDataOption {
     pub option_type: u8,
     pub name: String,
     pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<Vec<InteractionDataOption>>,
 }
@@ -10,6 +10,7 @@ pub enum Component {
     SelectMenu(SelectMenuComponent),
     SelectOption(SelectOptionComponent),
     TextInput(TextInputComponent),
+    UserSelect(UserSelectComponent),
 }
 
 // https://discord.com/developers/docs/interactions/message-components#action-rows
@@ -68,6 +69,29 @@ pub struct TextInputComponent {
     pub max_length: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+// https://discord.com/developers/docs/interactions/message-components#select-menu-object-select-menu-structure
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct UserSelectComponent {
+    #[serde(rename = "type")]
+    pub response_type: u8,
+    pub custom_id: String,
+    pub placeholder: Option<String>,
+    pub min_values: u8,
+    pub max_values: u8,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_values: Vec<SelectDefaultValue>,
+}
+
+// https://discord.com/developers/docs/interactions/message-components#select-menu-object-select-default-value-structure
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct SelectDefaultValue {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub value_type: String,
 }
 
 impl Component {
@@ -101,6 +125,29 @@ impl Component {
         })
     }
 
+    pub fn user_select(
+        custom_id: &str,
+        placeholder: &str,
+        min_values: u8,
+        max_values: u8,
+        selected: Vec<String>,
+    ) -> Self {
+        Self::UserSelect(UserSelectComponent {
+            response_type: 5,
+            custom_id: custom_id.to_string(),
+            placeholder: Some(placeholder.to_string()),
+            min_values,
+            max_values,
+            default_values: selected
+                .into_iter()
+                .map(|id| SelectDefaultValue {
+                    id,
+                    value_type: "user".to_string(),
+                })
+                .collect(),
+        })
+    }
+
     pub fn text_input(
         custom_id: &str,
         label: &str,
@@ -120,6 +167,27 @@ impl Component {
             min_length,
             max_length,
             required: Some(required),
+            value: None,
         })
     }
+
+    pub fn disabled(self) -> Self {
+        match self {
+            Self::Button(mut button) => {
+                button.disabled = true;
+                Self::Button(button)
+            }
+            other => other,
+        }
+    }
+
+    pub fn with_value(self, value: &str) -> Self {
+        match self {
+            Self::TextInput(mut input) => {
+                input.value = Some(value.to_string());
+                Self::TextInput(input)
+            }
+            other => other,
+        }
+    }
 }
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 pub enum InteractionCallbackData {
     Message(MessageCallbackData),
     Modal(ModalCallbackData),
+    Autocomplete(AutocompleteCallbackData),
 }
 
 // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object-interaction-callback-data-structure
@@ -34,7 +35,28 @@ pub struct MessageCallbackData {
 
 // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object-autocomplete
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
-pub struct AutocompleteCallbackData {}
+pub struct AutocompleteCallbackData {
+    pub choices: Vec<AutocompleteChoice>,
+}
+
+// https://discord.com/developers/docs/interactions/application-commands#application-command-object-application-command-option-choice-structure
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct AutocompleteChoice {
+    pub name: String,
+    pub value: String,
+}
+
+impl AutocompleteChoice {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.chars().take(MAX_CHOICE_LENGTH).collect(),
+            value: value.chars().take(MAX_CHOICE_LENGTH).collect(),
+        }
+    }
+}
+
+pub const MAX_CHOICES: usize = 25;
+const MAX_CHOICE_LENGTH: usize = 100;
 
 // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object-modal
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
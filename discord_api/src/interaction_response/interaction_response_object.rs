@@ -1,5 +1,7 @@
 use crate::interaction_response::allowed_mention::AllowedMention;
-use crate::interaction_response::{Component, InteractionCallbackData};
+use crate::interaction_response::{
+    AutocompleteCallbackData, AutocompleteChoice, Component, InteractionCallbackData, MAX_CHOICES,
+};
 use serde::{Deserialize, Serialize};
 
 // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object
@@ -60,6 +62,23 @@ impl InteractionResponse {
         }
     }
 
+    pub fn update_message(data: InteractionCallbackData) -> InteractionResponse {
+        InteractionResponse {
+            response_type: 7,
+            data: Some(data),
+        }
+    }
+
+    pub fn autocomplete_result(mut choices: Vec<AutocompleteChoice>) -> Self {
+        choices.truncate(MAX_CHOICES);
+        Self {
+            response_type: 8,
+            data: Some(InteractionCallbackData::Autocomplete(
+                AutocompleteCallbackData { choices },
+            )),
+        }
+    }
+
     pub fn modal(modal_collback_data: InteractionCallbackData) -> Self {
         Self {
             response_type: 9,
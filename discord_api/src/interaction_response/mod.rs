@@ -1,5 +1,6 @@
 pub use component::{
-    ActionRowComponent, Component, SelectMenuComponent, SelectOptionComponent, TextInputComponent,
+    ActionRowComponent, Component, SelectDefaultValue, SelectMenuComponent, SelectOptionComponent,
+    TextInputComponent, UserSelectComponent,
 };
 pub use select_menu_option::SelectMenuOption;
 
@@ -15,7 +16,8 @@ mod select_menu_option;
 pub use allowed_mention::AllowedMention;
 pub use embed::*;
 pub use interaction_callback_data::{
-    AutocompleteCallbackData, InteractionCallbackData, MessageCallbackData, ModalCallbackData,
+    AutocompleteCallbackData, AutocompleteChoice, InteractionCallbackData, MessageCallbackData,
+    ModalCallbackData, MAX_CHOICES,
 };
 pub use interaction_response_object::InteractionResponse;
 
@@ -3,6 +3,13 @@ pub enum Error {
     ClientFailure(String),
     Gemini(String),
     NoGeminiCandidatesReceived,
+    Database(String),
+}
+
+impl From<pog_common::error::Error> for Error {
+    fn from(value: pog_common::error::Error) -> Self {
+        Self::Database(format!("{:?}", value))
+    }
 }
 
 impl From<reqwest::Error> for Error {
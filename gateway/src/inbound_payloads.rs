@@ -66,6 +66,11 @@ impl InboundPayload {
                             .expect("deserialize a message update event");
                         InboundEvent::MessageUpdate(payload)
                     }
+                    "VOICE_STATE_UPDATE" => {
+                        let payload = serde_json::from_value(self.d)
+                            .expect("deserialize a voice state update event");
+                        InboundEvent::VoiceStateUpdate(payload)
+                    }
                     "GUILD_MEMBER_ADD" => {
                         let payload = serde_json::from_value(self.d)
                             .expect("deserialize a message update event");
@@ -99,6 +104,7 @@ pub enum InboundEvent {
     MessageUpdate(MessageUpdateEvent),
     MemberAdd(MemberAddEvent),
     TypingStart(TypingStartEvent),
+    VoiceStateUpdate(VoiceState),
     Unknown,
 }
 
@@ -120,6 +126,9 @@ pub struct ReadyEvent {
 pub struct GuildCreateEvent {
     // TODO: missing most of these fields
     pub channels: Vec<Channel>,
+    // who is already in a voice channel when the bot connects
+    #[serde(default)]
+    pub voice_states: Vec<VoiceState>,
 }
 
 // https://discord.com/developers/docs/resources/channel#channel-object
@@ -160,12 +169,6 @@ pub struct MemberAddEvent {
     pub user: Option<User>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct ChannelMessage {
-    pub id: String,
-    pub content: String,
-    pub author: User,
-}
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TypingStartEvent {
     pub channel_id: String,
@@ -193,6 +196,18 @@ pub struct MessageReactionRemoveEvent {
     pub emoji: Emoji,
 }
 
+// https://discord.com/developers/docs/resources/voice#voice-state-object
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoiceState {
+    // TODO: missing most of these fields
+    pub guild_id: Option<String>,
+    // none once the user leaves voice
+    pub channel_id: Option<String>,
+    pub user_id: String,
+    pub member: Option<GuildMember>,
+    pub session_id: String,
+}
+
 // https://discord.com/developers/docs/resources/emoji#emoji-object
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Emoji {
@@ -211,7 +226,8 @@ mod test {
         let payload: InboundPayload = serde_json::from_str(&contents).unwrap();
         match payload.event() {
             InboundEvent::GuildCreate(guild_create) => {
-                assert_eq!(5, guild_create.channels.len())
+                assert_eq!(5, guild_create.channels.len());
+                assert!(guild_create.voice_states.is_empty());
             }
             _ => panic!("fail"),
         }
@@ -330,4 +346,20 @@ mod test {
             _ => panic!("fail"),
         }
     }
+
+    #[test]
+    fn voice_state_update() {
+        let contents = fs::read_to_string("dto_payloads/voice_state_update.json").unwrap();
+        let payload: InboundPayload = serde_json::from_str(&contents).unwrap();
+        match payload.event() {
+            InboundEvent::VoiceStateUpdate(voice_state) => {
+                assert_eq!("695398918694895710", voice_state.user_id);
+                assert_eq!(
+                    Some("1045748883499765822".to_string()),
+                    voice_state.channel_id
+                );
+            }
+            _ => panic!("fail"),
+        }
+    }
 }
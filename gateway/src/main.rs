@@ -3,7 +3,9 @@ use crate::inbound_payloads::GetGateway;
 use crate::message_processor::MessageProcessor;
 use chrono::Local;
 use futures_util::{future, pin_mut, StreamExt};
-use pog_common::repos::{new_db_pool, AdminRepository, PostgresAdminRepository};
+use pog_common::repos::{
+    new_db_pool, AdminRepository, PostgresAdminRepository, PostgresAttendanceRepository,
+};
 use pog_common::Authorization;
 use std::sync::{Arc, Mutex};
 
@@ -14,10 +16,14 @@ mod heartbeat;
 mod inbound_payloads;
 mod message_processor;
 mod payloads;
+mod rsvp;
 mod snark;
 mod tldr;
+mod voice;
 
 const TLDR_MESSAGE_LENGTH: usize = 700;
+const RSVP_POST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const WATCH_PARTY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
 
 #[tokio::main]
 async fn main() {
@@ -61,6 +67,40 @@ async fn main() {
         }
     });
 
+    let attendance_repo = PostgresAttendanceRepository::new(db_pool.clone());
+    let rsvp_repo = attendance_repo.clone();
+    let rsvp_authorization = authorization.clone();
+    tokio::spawn(async move {
+        loop {
+            match rsvp::send_pending_posts(&rsvp_repo, &rsvp_authorization).await {
+                Ok(0) => {}
+                Ok(sent) => println!("sent {} rsvp posts", sent),
+                Err(err) => println!("error sending rsvp posts: {:?}", err),
+            }
+            tokio::time::sleep(RSVP_POST_INTERVAL).await;
+        }
+    });
+
+    let watch_party = Arc::new(Mutex::new(voice::WatchParty::default()));
+    let watch_party_copy = watch_party.clone();
+    let watch_repo = attendance_repo.clone();
+    let watch_settings = settings.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(WATCH_PARTY_INTERVAL).await;
+            let settings = watch_settings
+                .lock()
+                .expect("can't get settings mutex")
+                .clone();
+            let now = Local::now().naive_local();
+            match voice::record_attendance(&watch_repo, &watch_party_copy, &settings, now).await {
+                Ok(0) => {}
+                Ok(recorded) => println!("recorded {} watch party attendees", recorded),
+                Err(err) => println!("error recording watch party attendance: {:?}", err),
+            }
+        }
+    });
+
     println!(
         "started at {}, welcome channel - {}",
         Local::now().format("%Y-%m-%dT%H:%M:%S"),
@@ -72,12 +112,14 @@ async fn main() {
     let (stdin_tx, stdin_rx) = futures_channel::mpsc::unbounded();
     let stdin_tx_heartbeat = stdin_tx.clone();
     tokio::spawn(heartbeat(internal_rx, stdin_tx_heartbeat));
-    let message_processor = Mutex::new(MessageProcessor::new(
+    let message_processor = tokio::sync::Mutex::new(MessageProcessor::new(
         resume_gateway.clone(),
         discord_token,
         authorization,
         gemini_token,
         settings,
+        attendance_repo,
+        watch_party,
         stdin_tx,
         internal_tx,
     ));
@@ -92,11 +134,7 @@ async fn main() {
     let stdin_to_ws = stdin_rx.map(Ok).forward(write);
     let ws_to_stdout = {
         read.for_each(|message| async {
-            message_processor
-                .lock()
-                .expect("unlock message processor mutex")
-                .process(message)
-                .await;
+            message_processor.lock().await.process(message).await;
         })
     };
 
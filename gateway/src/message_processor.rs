@@ -1,12 +1,15 @@
 use crate::error::Error;
 use crate::heartbeat::WebsocketUpdate;
-use crate::inbound_payloads::{InboundEvent, InboundPayload};
+use crate::inbound_payloads::{InboundEvent, InboundPayload, VoiceState};
 use crate::payloads::DiscordGatewayResponse;
+use crate::rsvp;
 use crate::tldr;
 use crate::tldr::create_message;
+use crate::voice::WatchParty;
 use crate::TLDR_MESSAGE_LENGTH;
+use chrono::Local;
 use futures_channel::mpsc::UnboundedSender;
-use pog_common::repos::AdminSettings;
+use pog_common::repos::{AdminSettings, PostgresAttendanceRepository};
 use pog_common::{Authorization, CreateMessage, TlDrMessage};
 use std::sync::{Arc, Mutex};
 use tokio_tungstenite::tungstenite::Message;
@@ -18,17 +21,22 @@ pub struct MessageProcessor {
     authorization: Authorization,
     gemini_token: String,
     settings: Arc<Mutex<AdminSettings>>,
+    attendance_repo: PostgresAttendanceRepository,
+    watch_party: Arc<Mutex<WatchParty>>,
     sender: UnboundedSender<Message>,
     internal_tx: UnboundedSender<WebsocketUpdate>,
 }
 
 impl MessageProcessor {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         resume_gateway: String,
         discord_token: String,
         authorization: Authorization,
         gemini_token: String,
         settings: Arc<Mutex<AdminSettings>>,
+        attendance_repo: PostgresAttendanceRepository,
+        watch_party: Arc<Mutex<WatchParty>>,
         sender: UnboundedSender<Message>,
         internal_tx: UnboundedSender<WebsocketUpdate>,
     ) -> Self {
@@ -39,6 +47,8 @@ impl MessageProcessor {
             authorization,
             gemini_token,
             settings,
+            attendance_repo,
+            watch_party,
             sender,
             internal_tx,
         }
@@ -78,7 +88,11 @@ impl MessageProcessor {
                             self.session_id = Some(ready.session_id);
                         }
                         InboundEvent::Ack => {}
-                        InboundEvent::GuildCreate(_) => {}
+                        InboundEvent::GuildCreate(guild_create) => {
+                            for voice_state in guild_create.voice_states.iter() {
+                                self.voice_state_update(voice_state);
+                            }
+                        }
                         InboundEvent::MessageCreate(message_create) => {
                             if message_create.content.len() > TLDR_MESSAGE_LENGTH
                                 && message_create.author.bot != Some(true)
@@ -106,6 +120,9 @@ impl MessageProcessor {
                                         Error::NoGeminiCandidatesReceived => {
                                             println!("no gemini candidates found")
                                         }
+                                        Error::Database(msg) => {
+                                            println!("database error: {:?}", msg)
+                                        }
                                     },
                                 }
                             } else {
@@ -143,6 +160,7 @@ impl MessageProcessor {
                                 channel_id,
                                 message,
                                 message_reference: None,
+                                components: vec![],
                             })
                             .await
                             {
@@ -150,11 +168,38 @@ impl MessageProcessor {
                             };
                         }
                         InboundEvent::MessageDelete(_) => {}
-                        InboundEvent::MessageReactionAdd(_) => {}
-                        InboundEvent::MessageReactionRemove(_) => {}
+                        InboundEvent::MessageReactionAdd(reaction) => {
+                            if let Err(err) = rsvp::record_reaction(
+                                &self.attendance_repo,
+                                &reaction.message_id,
+                                &reaction.user_id,
+                                &reaction.emoji,
+                                true,
+                            )
+                            .await
+                            {
+                                println!("error recording rsvp: {:?}", err);
+                            }
+                        }
+                        InboundEvent::MessageReactionRemove(reaction) => {
+                            if let Err(err) = rsvp::record_reaction(
+                                &self.attendance_repo,
+                                &reaction.message_id,
+                                &reaction.user_id,
+                                &reaction.emoji,
+                                false,
+                            )
+                            .await
+                            {
+                                println!("error removing rsvp: {:?}", err);
+                            }
+                        }
                         InboundEvent::MessageUpdate(_) => {}
                         InboundEvent::Resumed => {}
                         InboundEvent::TypingStart(_) => {}
+                        InboundEvent::VoiceStateUpdate(voice_state) => {
+                            self.voice_state_update(&voice_state)
+                        }
                         InboundEvent::Reconnect => println!("TODO: reconnect cleanly"),
                         InboundEvent::Unknown => println!("unknown event: {}", text),
                     }
@@ -170,4 +215,21 @@ impl MessageProcessor {
             },
         }
     }
+
+    fn voice_state_update(&self, voice_state: &VoiceState) {
+        let settings = self
+            .settings
+            .lock()
+            .expect("could not unlock admin settings")
+            .clone();
+        self.watch_party
+            .lock()
+            .expect("could not unlock watch party")
+            .update(
+                &voice_state.user_id,
+                voice_state.channel_id.as_deref(),
+                Local::now().naive_local(),
+                &settings,
+            );
+    }
 }
@@ -39,7 +39,9 @@ pub struct DiscordGatewayIdentify {
 
 impl DiscordGatewayIdentify {
     pub fn new(token: &str) -> Self {
+        // https://discord.com/developers/docs/events/gateway#list-of-intents
         let intents = (1 << 1)
+            | (1 << 7)
             | (1 << 9)
             | (1 << 10)
             | (1 << 11)
@@ -0,0 +1,155 @@
+use crate::error::Error;
+use crate::inbound_payloads::Emoji;
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::attendance_record::{rsvp_response, RSVP_NO, RSVP_YES};
+use pog_common::repos::AttendanceRepository;
+use pog_common::{discord_headers, Authorization, DISCORD_API_ROOT};
+use serde::Deserialize;
+
+// https://discord.com/developers/docs/resources/message#message-object
+#[derive(Debug, Clone, Deserialize)]
+struct CreatedMessage {
+    id: String,
+}
+
+pub async fn send_pending_posts<R: AttendanceRepository>(
+    repo: &R,
+    authorization: &Authorization,
+) -> Result<usize, Error> {
+    let mut sent = 0;
+    for post in repo.pending_rsvp_posts().await? {
+        // claimed before sending so a failure after this can't send it twice; a post lost to an
+        // error waits for an admin to ask for it again rather than the channel filling up with copies
+        if !repo.claim_rsvp_post(post.season, &post.date).await? {
+            continue;
+        }
+        let message_id = post_message(authorization, &post.channel_id, &post.content).await?;
+        repo.rsvp_posted(post.season, &post.date, &message_id)
+            .await?;
+        for emoji in [RSVP_YES, RSVP_NO] {
+            add_reaction(authorization, &post.channel_id, &message_id, emoji).await?;
+        }
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+// a reaction on an rsvp post answers whether the manager is coming, taking it away takes back
+// the answer
+pub async fn record_reaction<R: AttendanceRepository>(
+    repo: &R,
+    message_id: &str,
+    user_id: &str,
+    emoji: &Emoji,
+    added: bool,
+) -> Result<bool, Error> {
+    let attending = match emoji.name.as_deref().and_then(rsvp_response) {
+        Some(attending) => attending,
+        None => return Ok(false),
+    };
+    let owner = match DiscordId::from_raw_str(user_id) {
+        Some(owner) => owner,
+        None => return Ok(false),
+    };
+    let recorded = match added {
+        true => repo.record_rsvp(message_id, &owner, attending).await?,
+        false => repo.remove_rsvp(message_id, &owner, attending).await?,
+    };
+    Ok(recorded)
+}
+
+async fn post_message(
+    authorization: &Authorization,
+    channel_id: &str,
+    content: &str,
+) -> Result<String, Error> {
+    let created: CreatedMessage = reqwest::Client::new()
+        .post(format!(
+            "{}/channels/{}/messages",
+            DISCORD_API_ROOT, channel_id
+        ))
+        .headers(discord_headers(authorization))
+        .json(&serde_json::json!({ "content": content }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(created.id)
+}
+
+// https://discord.com/developers/docs/resources/message#create-reaction
+async fn add_reaction(
+    authorization: &Authorization,
+    channel_id: &str,
+    message_id: &str,
+    emoji: &str,
+) -> Result<(), Error> {
+    let emoji: String = emoji.bytes().map(|byte| format!("%{:02X}", byte)).collect();
+    reqwest::Client::new()
+        .put(format!(
+            "{}/channels/{}/messages/{}/reactions/{}/@me",
+            DISCORD_API_ROOT, channel_id, message_id, emoji
+        ))
+        .headers(discord_headers(authorization))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::inbound_payloads::Emoji;
+    use crate::rsvp::record_reaction;
+    use pog_common::discord_id::DiscordId;
+    use pog_common::repos::attendance_record::{RsvpPost, RsvpRecord, RSVP_NO, RSVP_YES};
+    use pog_common::repos::{AttendanceRepository, InMemoryAttendanceRepository};
+
+    #[tokio::test]
+    async fn reactions_answer_rsvp_posts() {
+        let repo = InMemoryAttendanceRepository::default();
+        let dave: DiscordId = 695398918694895710.into();
+        repo.add_team(dave.clone(), "Dave");
+        repo.add_attendance(2024, 12, "2024-11-24", dave.clone());
+        let post = RsvpPost {
+            season: 2024,
+            date: "2024-11-24".to_string(),
+            channel_id: "1165637665908080730".to_string(),
+            content: "Who's coming?".to_string(),
+            message_id: None,
+        };
+        repo.request_rsvp_post(&post).await.unwrap();
+        repo.rsvp_posted(2024, "2024-11-24", "1311111111111111111")
+            .await
+            .unwrap();
+        let emoji = |name: &str| Emoji {
+            name: Some(name.to_string()),
+            id: None,
+        };
+
+        let react = |message_id, emoji, added| {
+            let repo = repo.clone();
+            async move {
+                record_reaction(&repo, message_id, "695398918694895710", &emoji, added)
+                    .await
+                    .unwrap()
+            }
+        };
+        assert!(!react("1311111111111111111", emoji("\u{1f44d}"), true).await);
+        assert!(!react("1322222222222222222", emoji(RSVP_YES), true).await);
+        assert!(react("1311111111111111111", emoji(RSVP_NO), true).await);
+        assert!(react("1311111111111111111", emoji(RSVP_YES), true).await);
+        assert!(!react("1311111111111111111", emoji(RSVP_NO), false).await);
+        assert_eq!(
+            vec![RsvpRecord {
+                owner_id: dave,
+                coming: 1,
+                showed_up: 1
+            }],
+            repo.rsvp_reliability(2024).await.unwrap()
+        );
+        assert!(react("1311111111111111111", emoji(RSVP_YES), false).await);
+        assert!(repo.rsvp_reliability(2024).await.unwrap().is_empty());
+    }
+}
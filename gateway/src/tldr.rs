@@ -15,6 +15,7 @@ pub async fn tldr(tldr: TlDrMessage) -> Result<(), Error> {
             message_id: tldr.original_message_id,
             channel_id: tldr.channel_id,
         }),
+        components: vec![],
     };
     create_message(create_message_data).await
 }
@@ -0,0 +1,334 @@
+use crate::error::Error;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{AdminSettings, AttendanceRepository};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+// time spent in the watch channel, counted as attendance once enough of a game window is covered
+#[derive(Debug, Default)]
+pub struct WatchParty {
+    joined: HashMap<String, NaiveDateTime>,
+    watched: HashMap<(String, NaiveDate), Duration>,
+    credited: HashSet<(String, NaiveDate)>,
+}
+
+impl WatchParty {
+    pub fn update(
+        &mut self,
+        user_id: &str,
+        channel_id: Option<&str>,
+        now: NaiveDateTime,
+        settings: &AdminSettings,
+    ) {
+        let watching = !settings.watch_channel.is_empty()
+            && channel_id == Some(settings.watch_channel.as_str());
+        match (watching, self.joined.get(user_id).copied()) {
+            (true, None) => {
+                self.joined.insert(user_id.to_string(), now);
+            }
+            (false, Some(joined)) => {
+                self.joined.remove(user_id);
+                for (date, watched) in game_time(joined, now, settings) {
+                    *self
+                        .watched
+                        .entry((user_id.to_string(), date))
+                        .or_insert(Duration::zero()) += watched;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // who has watched long enough on a game day and hasn't been credited for it yet
+    pub fn attendees(
+        &mut self,
+        now: NaiveDateTime,
+        settings: &AdminSettings,
+    ) -> Vec<(String, NaiveDate)> {
+        let recent = now.date() - Duration::days(2);
+        self.watched.retain(|(_, date), _| *date >= recent);
+        self.credited.retain(|(_, date)| *date >= recent);
+
+        let mut totals = self.watched.clone();
+        for (user_id, joined) in self.joined.iter() {
+            for (date, watched) in game_time(*joined, now, settings) {
+                *totals
+                    .entry((user_id.clone(), date))
+                    .or_insert(Duration::zero()) += watched;
+            }
+        }
+        let minimum = Duration::minutes(settings.watch_minutes as i64);
+        let mut attendees: Vec<(String, NaiveDate)> = totals
+            .into_iter()
+            .filter(|(_, watched)| *watched >= minimum)
+            .map(|(attendee, _)| attendee)
+            .filter(|attendee| !self.credited.contains(attendee))
+            .collect();
+        attendees.sort();
+        attendees
+    }
+
+    pub fn credit(&mut self, user_id: &str, date: NaiveDate) {
+        self.credited.insert((user_id.to_string(), date));
+    }
+}
+
+// writes anyone who has watched long enough as attending the current week
+pub async fn record_attendance<R: AttendanceRepository>(
+    repo: &R,
+    party: &Mutex<WatchParty>,
+    settings: &AdminSettings,
+    now: NaiveDateTime,
+) -> Result<usize, Error> {
+    // without a current week there is nowhere to put the attendance, so wait for one to be set
+    if settings.ff_week == 0 {
+        return Ok(0);
+    }
+    let attendees = party
+        .lock()
+        .expect("could not unlock watch party")
+        .attendees(now, settings);
+    let mut recorded = 0;
+    for (user_id, date) in attendees {
+        if let Some(owner) = DiscordId::from_raw_str(&user_id) {
+            if repo
+                .mark_attended(
+                    settings.current_season(date),
+                    settings.ff_week,
+                    &date.to_string(),
+                    &owner,
+                )
+                .await?
+            {
+                recorded += 1;
+            }
+        }
+        // only credited once the write goes through, so a failed one is tried again next sweep
+        party
+            .lock()
+            .expect("could not unlock watch party")
+            .credit(&user_id, date);
+    }
+    Ok(recorded)
+}
+
+// the part of a stay in the channel that falls within each day's game window
+fn game_time(
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    settings: &AdminSettings,
+) -> Vec<(NaiveDate, Duration)> {
+    from.date()
+        .iter_days()
+        .take_while(|date| *date <= to.date())
+        .filter_map(|date| {
+            let (start, end) = settings.watch_window(date)?;
+            let watched = end.min(to) - start.max(from);
+            (watched > Duration::zero()).then_some((date, watched))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::voice::{record_attendance, WatchParty};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use pog_common::discord_id::DiscordId;
+    use pog_common::error::Error;
+    use pog_common::repos::attendance_record::{
+        AttendanceRecords, RsvpPost, RsvpRecord, WeeklyAttendanceRecord,
+    };
+    use pog_common::repos::{AdminSettings, AttendanceRepository, InMemoryAttendanceRepository};
+    use std::sync::Mutex;
+
+    const WATCH_CHANNEL: &str = "1045748883499765822";
+    const DAVE: &str = "695398918694895710";
+    const STEVE: &str = "431634941626023936";
+
+    fn settings() -> AdminSettings {
+        let mut settings = AdminSettings::new(String::new(), 2024, 12);
+        settings.watch_channel = WATCH_CHANNEL.to_string();
+        settings.watch_days = vec![7];
+        settings.watch_start = 13;
+        settings.watch_end = 20;
+        settings.watch_minutes = 30;
+        settings
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 11, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn only_time_in_the_game_window_counts() {
+        let settings = settings();
+        let sunday = NaiveDate::from_ymd_opt(2024, 11, 24).unwrap();
+        let mut party = WatchParty::default();
+
+        party.update(DAVE, Some(WATCH_CHANNEL), at(24, 12, 0), &settings);
+        party.update(STEVE, Some("1045748883499765823"), at(24, 13, 0), &settings);
+        assert!(party.attendees(at(24, 13, 20), &settings).is_empty());
+        party.update(DAVE, None, at(24, 13, 20), &settings);
+        party.update(DAVE, Some(WATCH_CHANNEL), at(24, 15, 0), &settings);
+        assert_eq!(
+            vec![(DAVE.to_string(), sunday)],
+            party.attendees(at(24, 15, 10), &settings)
+        );
+        assert_eq!(
+            vec![(DAVE.to_string(), sunday)],
+            party.attendees(at(24, 15, 20), &settings)
+        );
+        party.credit(DAVE, sunday);
+        assert!(party.attendees(at(24, 19, 0), &settings).is_empty());
+
+        party.update(STEVE, Some(WATCH_CHANNEL), at(24, 19, 45), &settings);
+        assert!(party.attendees(at(24, 23, 0), &settings).is_empty());
+
+        let mut saturday = WatchParty::default();
+        saturday.update(STEVE, Some(WATCH_CHANNEL), at(23, 13, 0), &settings);
+        assert!(saturday.attendees(at(23, 19, 0), &settings).is_empty());
+    }
+
+    #[tokio::test]
+    async fn watching_records_attendance_for_the_current_week() {
+        let repo = InMemoryAttendanceRepository::default();
+        let dave = DiscordId::from_raw_str(DAVE).unwrap();
+        repo.add_team(dave.clone(), "Dave");
+        let mut settings = settings();
+        let party = Mutex::new(WatchParty::default());
+        for user_id in [DAVE, STEVE] {
+            party
+                .lock()
+                .unwrap()
+                .update(user_id, Some(WATCH_CHANNEL), at(24, 13, 0), &settings);
+        }
+
+        settings.ff_week = 0;
+        assert_eq!(
+            0,
+            record_attendance(&repo, &party, &settings, at(24, 14, 0))
+                .await
+                .unwrap()
+        );
+        settings.ff_week = 12;
+        assert_eq!(
+            1,
+            record_attendance(&repo, &party, &settings, at(24, 14, 0))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            0,
+            record_attendance(&repo, &party, &settings, at(24, 15, 0))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            vec![dave],
+            repo.date_attendance(2024, "2024-11-24").await.unwrap()
+        );
+        assert_eq!(vec![12], repo.weeks(2024).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn failed_writes_are_tried_again() {
+        let repo = InMemoryAttendanceRepository::default();
+        let dave = DiscordId::from_raw_str(DAVE).unwrap();
+        repo.add_team(dave.clone(), "Dave");
+        let settings = settings();
+        let party = Mutex::new(WatchParty::default());
+        party
+            .lock()
+            .unwrap()
+            .update(DAVE, Some(WATCH_CHANNEL), at(24, 13, 0), &settings);
+
+        assert!(
+            record_attendance(&Unavailable, &party, &settings, at(24, 14, 0))
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            1,
+            record_attendance(&repo, &party, &settings, at(24, 14, 1))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            vec![dave],
+            repo.date_attendance(2024, "2024-11-24").await.unwrap()
+        );
+    }
+
+    // a database that can't be reached
+    struct Unavailable;
+
+    fn unavailable<T>() -> Result<T, Error> {
+        Err(Error::Database("connection refused".to_string()))
+    }
+
+    impl AttendanceRepository for Unavailable {
+        async fn combined_attendance(&self, _: u16) -> Result<AttendanceRecords, Error> {
+            unavailable()
+        }
+        async fn week_attendance(
+            &self,
+            _: u16,
+            _: u8,
+            _: &Option<DiscordId>,
+        ) -> Result<WeeklyAttendanceRecord, Error> {
+            unavailable()
+        }
+        async fn weeks(&self, _: u16) -> Result<Vec<u8>, Error> {
+            unavailable()
+        }
+        async fn attended_weeks(&self, _: u16) -> Result<Vec<(DiscordId, u8)>, Error> {
+            unavailable()
+        }
+        async fn date_attendance(&self, _: u16, _: &str) -> Result<Vec<DiscordId>, Error> {
+            unavailable()
+        }
+        async fn set_attendance(
+            &self,
+            _: u16,
+            _: u8,
+            _: &str,
+            _: &[DiscordId],
+        ) -> Result<Vec<DiscordId>, Error> {
+            unavailable()
+        }
+        async fn mark_attended(
+            &self,
+            _: u16,
+            _: u8,
+            _: &str,
+            _: &DiscordId,
+        ) -> Result<bool, Error> {
+            unavailable()
+        }
+        async fn request_rsvp_post(&self, _: &RsvpPost) -> Result<bool, Error> {
+            unavailable()
+        }
+        async fn pending_rsvp_posts(&self) -> Result<Vec<RsvpPost>, Error> {
+            unavailable()
+        }
+        async fn claim_rsvp_post(&self, _: u16, _: &str) -> Result<bool, Error> {
+            unavailable()
+        }
+        async fn rsvp_posted(&self, _: u16, _: &str, _: &str) -> Result<(), Error> {
+            unavailable()
+        }
+        async fn record_rsvp(&self, _: &str, _: &DiscordId, _: bool) -> Result<bool, Error> {
+            unavailable()
+        }
+        async fn remove_rsvp(&self, _: &str, _: &DiscordId, _: bool) -> Result<bool, Error> {
+            unavailable()
+        }
+        async fn rsvp_reliability(&self, _: u16) -> Result<Vec<RsvpRecord>, Error> {
+            unavailable()
+        }
+    }
+}
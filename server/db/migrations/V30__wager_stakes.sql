@@ -0,0 +1,5 @@
+ALTER TABLE wagers
+    ADD stake_amount bigint,
+    ADD stake_currency text,
+    ADD stake_item text,
+    ADD stake_quantity int;
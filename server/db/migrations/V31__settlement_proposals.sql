@@ -0,0 +1,3 @@
+ALTER TABLE wagers
+    ADD proposed_status smallint,
+    ADD proposed_by bigint;
@@ -0,0 +1,2 @@
+ALTER TABLE wagers
+    ADD paid_outcome smallint;
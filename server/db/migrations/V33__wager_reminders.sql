@@ -0,0 +1,2 @@
+ALTER TABLE wagers
+    ADD last_reminded text;
@@ -0,0 +1,18 @@
+CREATE TABLE wager_revisions
+(
+    revision_id                   int      NOT NULL,
+    wager_id                      int      NOT NULL,
+    time                          text     NOT NULL,
+    proposed_by                   bigint   NOT NULL,
+    status                        smallint NOT NULL,
+    previous_wager                text     NOT NULL,
+    previous_outcome              text     NOT NULL,
+    previous_expected_settle_date text,
+    wager                         text     NOT NULL,
+    outcome                       text     NOT NULL,
+    expected_settle_date          text,
+    PRIMARY KEY (revision_id)
+);
+CREATE SEQUENCE seq_revision_id MINVALUE 101 OWNED BY wager_revisions.revision_id;
+
+CREATE INDEX idx_wager_revisions_wager_id ON wager_revisions (wager_id);
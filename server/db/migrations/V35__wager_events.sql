@@ -0,0 +1,18 @@
+CREATE TABLE wager_events
+(
+    event_id        int      NOT NULL,
+    wager_id        int      NOT NULL,
+    time            text     NOT NULL,
+    actor           bigint,
+    action          smallint NOT NULL,
+    previous_status smallint,
+    status          smallint NOT NULL,
+    detail          text     NOT NULL,
+    PRIMARY KEY (event_id)
+);
+CREATE SEQUENCE seq_event_id MINVALUE 101 OWNED BY wager_events.event_id;
+
+CREATE INDEX idx_wager_events_wager_id ON wager_events (wager_id);
+
+CREATE RULE wager_events_no_update AS ON UPDATE TO wager_events DO INSTEAD NOTHING;
+CREATE RULE wager_events_no_delete AS ON DELETE TO wager_events DO INSTEAD NOTHING;
@@ -0,0 +1,26 @@
+CREATE TABLE pools
+(
+    pool_id        int      NOT NULL,
+    time           text     NOT NULL,
+    created_by     bigint   NOT NULL,
+    question       text     NOT NULL,
+    buy_in         text     NOT NULL,
+    options        text[]   NOT NULL,
+    status         smallint NOT NULL,
+    winning_option text,
+    PRIMARY KEY (pool_id)
+);
+CREATE SEQUENCE seq_pool_id MINVALUE 101 OWNED BY pools.pool_id;
+
+CREATE TABLE pool_entries
+(
+    pool_id int    NOT NULL,
+    user_id bigint NOT NULL,
+    time    text   NOT NULL,
+    name    text   NOT NULL,
+    pick    text   NOT NULL,
+    buy_in  text   NOT NULL,
+    PRIMARY KEY (pool_id, user_id)
+);
+
+CREATE INDEX idx_pool_entries_user_id ON pool_entries (user_id);
@@ -0,0 +1,10 @@
+ALTER TABLE wagers
+    ADD accepting_wager text,
+    ADD accepting_stake_amount bigint,
+    ADD accepting_stake_currency text,
+    ADD accepting_stake_item text,
+    ADD accepting_stake_quantity int;
+
+ALTER TABLE wager_revisions
+    ADD previous_accepting_wager text,
+    ADD accepting_wager text;
@@ -0,0 +1,20 @@
+CREATE TABLE rsvp_posts
+(
+    season     int  NOT NULL,
+    date       text NOT NULL,
+    channel_id text NOT NULL,
+    content    text NOT NULL,
+    message_id text,
+    PRIMARY KEY (season, date)
+);
+
+CREATE UNIQUE INDEX idx_rsvp_posts_message_id ON rsvp_posts (message_id);
+
+CREATE TABLE rsvps
+(
+    message_id text    NOT NULL,
+    owner      bigint  NOT NULL,
+    attending  boolean NOT NULL,
+    time       text    NOT NULL,
+    PRIMARY KEY (message_id, owner)
+);
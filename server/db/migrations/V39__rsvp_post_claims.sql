@@ -0,0 +1,2 @@
+ALTER TABLE rsvp_posts
+    ADD claimed boolean NOT NULL DEFAULT false;
@@ -0,0 +1,23 @@
+-- wagers placed before V30 only have the text they were entered with, fill in the money stakes
+-- written the ways the bot parses them ($20, 20$, 20 bucks, USD 20) so ledger totals count them
+UPDATE wagers SET stake_amount= (parsed.amount::numeric * 100)::bigint, stake_currency= parsed.currency
+FROM (
+    SELECT wager_id,
+        replace(CASE
+            WHEN stake ~ '^(\$|€|£)' THEN substring(stake FROM '^(?:\$|€|£)\s*(\S+)$')
+            WHEN stake ~ '(\$|€|£)$' THEN substring(stake FROM '^(\S+)\s*(?:\$|€|£)$')
+            WHEN stake ~* '^\S+\s+(bucks?|dollars?|euros?|pounds?|quid|usd|eur|gbp)$' THEN substring(stake FROM '^(\S+)')
+            WHEN stake ~* '^(usd|eur|gbp)\s+\S+$' THEN substring(stake FROM '(\S+)$')
+        END, ',', '') AS amount,
+        CASE
+            WHEN stake ~ '^\$|\$$' OR stake ~* '^\S+\s+(bucks?|dollars?|usd)$' OR stake ~* '^usd\s+\S+$' THEN 'USD'
+            WHEN stake ~ '^€|€$' OR stake ~* '^\S+\s+(euros?|eur)$' OR stake ~* '^eur\s+\S+$' THEN 'EUR'
+            WHEN stake ~ '^£|£$' OR stake ~* '^\S+\s+(pounds?|quid|gbp)$' OR stake ~* '^gbp\s+\S+$' THEN 'GBP'
+        END AS currency
+    FROM (SELECT wager_id, trim(wager) AS stake FROM wagers
+        WHERE stake_amount IS NULL AND stake_item IS NULL) AS legacy
+) AS parsed
+WHERE wagers.wager_id = parsed.wager_id
+    -- Postgres may evaluate these in any order, so the cast is only reached for amounts that look numeric
+    AND CASE WHEN parsed.amount ~ '^[0-9]+(\.[0-9]{1,2})?$' THEN parsed.amount::numeric END > 0
+    AND parsed.currency IS NOT NULL;
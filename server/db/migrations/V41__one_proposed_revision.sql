@@ -0,0 +1,6 @@
+-- only one change to a wager can wait on a response, so two can't both be accepted against the same terms
+UPDATE wager_revisions SET status= 2
+WHERE status= 0
+    AND revision_id NOT IN (SELECT MAX(revision_id) FROM wager_revisions WHERE status= 0 GROUP BY wager_id);
+
+CREATE UNIQUE INDEX idx_wager_revisions_proposed ON wager_revisions (wager_id) WHERE status= 0;
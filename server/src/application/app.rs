@@ -1,9 +1,12 @@
 use discord_api::interaction_request::{
-    ApplicationCommandInteractionData, InteractionData, InteractionObject,
+    ApplicationCommandInteractionData, GuildMember, InteractionData, InteractionObject,
     MessageComponentInteractionData, ModalSubmitInteractionData, User,
 };
 use discord_api::interaction_response::InteractionResponse;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
 
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
@@ -18,21 +21,24 @@ pub struct Application<
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 > {
     pub wager_repo: WR,
     pub attendance_repo: AR,
     pub admin_repo: SR,
     pub whois_repo: UR,
+    pub pool_repo: PR,
     pub client: C,
 }
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub fn new(
@@ -40,6 +46,7 @@ where
         attendance_repo: AR,
         admin_repo: SR,
         whois_repo: UR,
+        pool_repo: PR,
         client: C,
     ) -> Self {
         Self {
@@ -47,6 +54,7 @@ where
             attendance_repo,
             admin_repo,
             whois_repo,
+            pool_repo,
             client,
         }
     }
@@ -58,31 +66,35 @@ where
         match request.get_data()? {
             InteractionData::Ping => Ok(InteractionResponse::ping_response()),
             InteractionData::Command(data) => {
-                self.command_handler(data, request.expect_member()?.expect_user()?)
-                    .await
+                self.command_handler(data, request.expect_member()?).await
             }
             InteractionData::Message(data) => self.select_choice_handler(data, request).await,
-            // InteractionData::CommandAutocomplete(data) => {}
+            InteractionData::CommandAutocomplete(data) => {
+                self.autocomplete(data, request.expect_member()?).await
+            }
             InteractionData::ModalSubmit(data) => {
                 self.modal_response_handler(data, request.expect_member()?.expect_user()?)
                     .await
             }
-            _ => Err(Error::Invalid(format!(
-                "unknown response type: {}",
-                request.interaction_type
-            ))),
         }
     }
 
     pub async fn command_handler(
         &self,
         data: ApplicationCommandInteractionData,
-        user: &User,
+        member: &GuildMember,
     ) -> Result<InteractionResponse, Error> {
+        let user = member.expect_user()?;
         match data.name.as_str() {
             pog_common::ADD_BET_COMMAND => self.initiate_bet(data).await,
             pog_common::LIST_BET_COMMAND => self.list_bets(data).await,
-            pog_common::SETTLE_BET_COMMAND => self.pay_bet(data, user).await,
+            pog_common::SETTLE_BET_COMMAND => self.pay_bet(data, member).await,
+            pog_common::EDIT_BET_COMMAND => self.edit_bet(data, member).await,
+            pog_common::POOL_COMMAND => self.start_pool().await,
+            pog_common::DEBTS_COMMAND => self.debts(user).await,
+            pog_common::LEDGER_COMMAND => self.ledger(data, user).await,
+            pog_common::BET_STATS_COMMAND => self.bet_stats(data).await,
+            pog_common::BET_HISTORY_COMMAND => self.bet_history(data, member).await,
             pog_common::ATTENDANCE_BET_COMMAND => self.attendance(data, user).await,
             pog_common::HELP_COMMAND => self.help().await,
             pog_common::ADMIN_COMMAND => self.admin(data, user).await,
@@ -99,11 +111,20 @@ where
         data: MessageComponentInteractionData,
         request: InteractionObject,
     ) -> Result<InteractionResponse, Error> {
-        let ident: String = data.custom_id.chars().take(6).collect();
-        match ident.as_str() {
-            "offeri" | "accept" | "nobet_" | "cancel" => self.settle_bet(data, request).await,
-            "settle" => self.bet_selected(data, request).await,
-            &_ => Err("unknown component custom id".into()),
+        match CustomId::decode(&data.custom_id)?.route {
+            Route::Outcome => self.settle_bet(data, request).await,
+            Route::Settle => self.bet_selected(data, request).await,
+            Route::Answer => self.accept_wager(data, request).await,
+            Route::Amend => self.confirm_amendment(data, request).await,
+            Route::Offer => self.answer_offer(data, request).await,
+            Route::Pool => self.pool_action(data, request).await,
+            Route::Verify => self.confirm_settlement(data, request).await,
+            Route::Payment => self.mark_paid(data, request).await,
+            Route::SettleUp => self.confirm_settle_up(data, request).await,
+            Route::BetPage => self.turn_bets_page(data, request).await,
+            Route::Remind => self.settle_reminder(data, request).await,
+            Route::Attendance => self.record_attendance(data, request).await,
+            _ => Err("unknown component custom id".into()),
         }
     }
 
@@ -112,12 +133,12 @@ where
         data: ModalSubmitInteractionData,
         user: &User,
     ) -> Result<InteractionResponse, Error> {
-        if data.custom_id.starts_with("wager") {
-            self.add_wager(data, user).await
-        } else if data.custom_id.starts_with("setuser") {
-            self.set_user(data, user).await
-        } else {
-            Err("unknown modal response type".into())
+        match CustomId::decode(&data.custom_id)?.route {
+            Route::WagerModal | Route::OpenOfferModal => self.add_wager(data, user).await,
+            Route::EditModal => self.amend_wager(data, user).await,
+            Route::PoolModal => self.create_pool(data, user).await,
+            Route::SetUserModal => self.set_user(data, user).await,
+            _ => Err("unknown modal response type".into()),
         }
     }
 }
@@ -126,19 +147,25 @@ where
 mod test {
     use std::fs;
 
-    use chrono::{Local, NaiveDate};
+    use chrono::{Datelike, Local, NaiveDate};
 
     use discord_api::interaction_request::InteractionObject;
     use discord_api::interaction_response::InteractionResponse;
-    use pog_common::repos::attendance_record::AttendanceRecords;
+    use pog_common::discord_id::DiscordId;
+    use pog_common::repos::attendance_record::{RsvpPost, RSVP_YES};
     use pog_common::repos::{
-        AdminRepository, AdminSettings, InMemAdminRepository, InMemWagerRepository,
-        InMemWhoisRepository, InMemoryAttendanceRepository, WagerRepository, WhoisRepository,
+        AdminRepository, AdminSettings, AttendanceRepository, InMemAdminRepository,
+        InMemPoolRepository, InMemWagerRepository, InMemWhoisRepository,
+        InMemoryAttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
     };
 
     use crate::application::Application;
     use crate::discord_client::TestDiscordClient;
-    use pog_common::wager::{Wager, WagerStatus};
+    use pog_common::pool::{Pool, PoolEntry, PoolStatus};
+    use pog_common::wager::{
+        RevisionStatus, SettlementProposal, Wager, WagerAction, WagerEvent, WagerRevision,
+        WagerStatus, OPEN_OFFER_ACCEPTING,
+    };
 
     #[tokio::test]
     async fn ping_request() {
@@ -148,6 +175,7 @@ mod test {
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
         let result = app.request_handler(request).await.unwrap();
@@ -164,26 +192,29 @@ mod test {
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
         let found = serde_json::to_string(&result).unwrap();
-        let mut expected = r#"{"type":9,"data":{"custom_id":"wager|1050119194533961860|Cisco","title":"Place a bet","components":[{"type":1,"components":[{"type":4,"custom_id":"wager","label":"How much are we wagering?","placeholder":"$20","style":1,"min_length":2,"max_length":10,"required":true}]},{"type":1,"components":[{"type":4,"custom_id":"outcome","label":"What is the bet on?","placeholder":"Raiders make the playoffs","style":2,"min_length":3,"max_length":100,"required":true}]},{"type":1,"components":[{"type":4,"custom_id":"settlement","label":"When will this bet settle?","placeholder":""#.to_string();
+        let mut expected = r#"{"type":9,"data":{"custom_id":"wager:1:Cisco:1050119194533961860","title":"Place a bet","components":[{"type":1,"components":[{"type":4,"custom_id":"wager","label":"How much are we wagering?","placeholder":"$20","style":1,"min_length":2,"max_length":30,"required":true}]},{"type":1,"components":[{"type":4,"custom_id":"odds","label":"What do they put up? (empty for even odds)","placeholder":"3-1 or $10","style":1,"max_length":30,"required":false}]},{"type":1,"components":[{"type":4,"custom_id":"outcome","label":"What is the bet on?","placeholder":"Raiders make the playoffs","style":2,"min_length":3,"max_length":100,"required":true}]},{"type":1,"components":[{"type":4,"custom_id":"settlement","label":"When will this bet settle?","placeholder":""#.to_string();
         expected += Local::now().format("%m/%d").to_string().as_str();
-        expected += r#"","style":1,"min_length":3,"max_length":10,"required":false}]}]}}"#;
+        expected += r#"","style":1,"min_length":3,"max_length":30,"required":false}]}]}}"#;
         assert_eq!(found, expected);
     }
 
     #[tokio::test]
     async fn t11_bet_modal_request() {
         let request = expect_request_from("dto_payloads/T11_bet_modal_request.json");
+        let repo = InMemWagerRepository::default();
         let app = Application::new(
-            InMemWagerRepository::default(),
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
         let result = app.request_handler(request).await.unwrap();
@@ -191,421 +222,3067 @@ mod test {
         let found = serde_json::to_string(&result).unwrap();
         assert_eq!(
             &found,
-            r#"{"type":4,"data":{"content":"<@695398918694895710> vs <@695398918694895710>, wager: $20 - something something"}}"#
+            r#"{"type":4,"data":{"content":"<@695398918694895710>, do you accept this wager?\n<@695398918694895710> vs <@695398918694895710>, wager: $20 - something something","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Accept","custom_id":"answer:1:accept:1","disabled":false},{"type":2,"style":4,"label":"Decline","custom_id":"answer:1:decline:1","disabled":false}]}]}}"#
         );
+        let wager = repo.get(1).await.unwrap();
+        assert_eq!(WagerStatus::AwaitingAcceptance, wager.status);
     }
 
     #[tokio::test]
-    async fn t20_list_bets_request_no_bets() {
-        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+    async fn t11_bet_modal_request_odds() {
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_odds.json");
+        let repo = InMemWagerRepository::default();
         let app = Application::new(
-            InMemWagerRepository::default(),
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
-
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
-        assert_eq!(
-            found,
-            r#"{"type":4,"data":{"content":"Harx has no outstanding wagers","flags":64}}"#
-        );
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710>, do you accept this wager?\n<@695398918694895710> vs <@695398918694895710>, wager: $30 to $10 - something something","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Accept","custom_id":"answer:1:accept:1","disabled":false},{"type":2,"style":4,"label":"Decline","custom_id":"answer:1:decline:1","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(1).await.unwrap();
+        assert_eq!("$30", wager.wager.to_string());
+        assert_eq!(Some("$10".into()), wager.accepting_wager);
     }
 
     #[tokio::test]
-    async fn t20_list_bets_request() {
-        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+    async fn t11_bet_modal_request_uneven_odds() {
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_uneven_odds.json");
         let repo = InMemWagerRepository::default();
-        repo.insert(Wager {
-            wager_id: 109,
-            time: "".to_string(),
-            offering: "----".to_string(),
-            resolved_offering_user: Some(695398918694895710.into()),
-            accepting: "Woody".to_string(),
-            resolved_accepting_user: None,
-            wager: "$20".to_string(),
-            outcome: "Rangers repeat".to_string(),
-            status: WagerStatus::Open,
-            expected_settle_date: None,
-        })
-        .await
-        .unwrap();
         let app = Application::new(
-            repo,
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
-
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
-        assert_eq!(
-            found,
-            r#"{"type":4,"data":{"content":"Harx has 1 outstanding wagers:\n- ---- vs Woody, wager: $20 - Rangers repeat","flags":64}}"#
-        );
+        let expected = r#"{"type":4,"data":{"content":"Unable to give 3-1 odds on 1 beer, try a different amount or enter what they put up instead","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, repo.get(1).await);
     }
 
     #[tokio::test]
-    async fn t20_list_bets_request_w_no_global_user() {
-        let request =
-            expect_request_from("dto_payloads/T20_list_bets_request_w_no_global_user.json");
+    async fn t11_bet_modal_request_overflowing_odds() {
+        let contents = fs::read_to_string("dto_payloads/T11_bet_modal_request_odds.json").unwrap();
+        let request = serde_json::from_str(
+            &contents
+                .replace(r#""value": "$30""#, r#""value": "$90000000000000000""#)
+                .replace(r#""value": "3-1""#, r#""value": "1-4000000000""#),
+        )
+        .unwrap();
+        let repo = InMemWagerRepository::default();
         let app = Application::new(
-            InMemWagerRepository::default(),
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
-
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
-        assert_eq!(
-            found,
-            r#"{"type":4,"data":{"content":"johnanon has no outstanding wagers","flags":64}}"#
+        let expected = r#"{"type":4,"data":{"content":"Unable to give 1-4000000000 odds on $90000000000000000, try a different amount or enter what they put up instead","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, repo.get(1).await);
+    }
+
+    #[tokio::test]
+    async fn t11_bet_modal_request_week() {
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_week.json");
+        let repo = InMemWagerRepository::default();
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.ff_year = chrono::Utc::now().year() as u16 + 1;
+        admin_repo.update(settings.clone()).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            admin_repo,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
         );
+        app.request_handler(request).await.unwrap();
+
+        let wager = repo.get(1).await.unwrap();
+        let expected = NaiveDate::from_ymd_opt(settings.ff_year as i32, 9, 1)
+            .unwrap()
+            .iter_days()
+            .find(|date| date.weekday() == chrono::Weekday::Mon)
+            .map(|labor_day| labor_day + chrono::Duration::days(6 + 13 * 7));
+        assert_eq!(expected, wager.expected_settle_date);
+        assert_eq!(None, wager.accepting_wager);
     }
 
     #[tokio::test]
-    async fn t30_payout_request() {
-        let request = expect_request_from("dto_payloads/T30_payout_request.json");
-        let repository = InMemWagerRepository::default();
-        repository
-            .insert(Wager {
-                wager_id: 1,
-                time: "".to_string(),
-                offering: "Harx".to_string(),
-                resolved_offering_user: Some(695398918694895710.into()),
-                accepting: "Woody".to_string(),
-                resolved_accepting_user: None,
-                wager: "$20".to_string(),
-                outcome: "Raiders win out".to_string(),
-                status: WagerStatus::Open,
-                expected_settle_date: None,
-            })
-            .await
-            .unwrap();
+    async fn t11_bet_modal_request_unparsed_date() {
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_unparsed_date.json");
+        let repo = InMemWagerRepository::default();
         let app = Application::new(
-            repository,
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
-
         let result = app.request_handler(request).await.unwrap();
 
-        let expected = r#"{"type":4,"data":{"content":"Close out a bet","flags":64,"components":[{"type":1,"components":[{"type":3,"custom_id":"settle","options":[{"label":"1","value":"1","description":"Harx vs Woody, wager: $20 - Raiders win out"}],"placeholder":"Close which bet?"}]}]}}"#;
+        let expected = r#"{"type":4,"data":{"content":"Unable to understand the settle date 'whenever', try `11/24`, `Feb 9`, `next sunday`, `in 3 days`, `week 14` or `end of season`","flags":64}}"#;
         assert_response(result, expected);
+        assert_eq!(None, repo.get(1).await);
     }
 
     #[tokio::test]
-    async fn t30_payout_request_no_bet() {
-        let request = expect_request_from("dto_payloads/T30_payout_request.json");
+    async fn t11_bet_modal_request_over_limit() {
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_odds.json");
+        let repo = InMemWagerRepository::default();
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.max_stake = Some("$20".to_string());
+        admin_repo.update(settings.clone()).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
-            test_admin_repo().await,
+            admin_repo.clone(),
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
-
-        let expected = r#"{"type":4,"data":{"content":"You have no open bets","flags":64}}"#;
+        let expected = r#"{"type":4,"data":{"content":"You can't place this bet, the most anyone can put up on a bet is $20","flags":64}}"#;
         assert_response(result, expected);
+        assert_eq!(None, repo.get(1).await);
+
+        settings.limit_exempt = vec!["695398918694895710".to_string()];
+        admin_repo.update(settings).await.unwrap();
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_odds.json");
+        app.request_handler(request).await.unwrap();
+        assert!(repo.get(1).await.is_some());
     }
 
-    // TODO: fix T30 response payload
-    // An integer was encountered for 'id' within the Application Command Structure
-    // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-application-command-data-structure
-    // This field is specified as a Snowflake and should always be a String, this is likely due to
-    // a malformed response on my part.
-    // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-application-command-data-structure
     #[tokio::test]
-    async fn bug_2025_03_20() {
-        expect_request_from("dto_payloads/2025_03_20_settlement_bug.json");
+    async fn t11_bet_modal_request_unparsed_stake() {
+        let request = expect_request_from("dto_payloads/T11_bet_modal_request_unparsed_stake.json");
+        let repo = InMemWagerRepository::default();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            &found,
+            r#"{"type":4,"data":{"content":"Unable to understand the wager 'lunch', use an amount like `$20` or `20 euro`, or an item with a quantity like `a beer` or `6 beers`","flags":64}}"#
+        );
+        assert!(repo.get(1).await.is_none());
     }
 
     #[tokio::test]
-    async fn t31_selected_bet_to_close_request() {
-        let request = expect_request_from("dto_payloads/T31_selected_bet_to_close_request.json");
+    async fn t12_accept_wager() {
+        let request = expect_request_from("dto_payloads/T12_accept_wager.json");
         let repo = InMemWagerRepository::default();
+        repo.insert(pending_wager(695398918694895710))
+            .await
+            .unwrap();
         let client = TestDiscordClient::default();
         set_client_message(&client, Some("original message".to_string()));
-        repo.insert(Wager {
-            wager_id: 109,
-            time: "".to_string(),
-            offering: "----".to_string(),
-            resolved_offering_user: Some(695398918694895710.into()),
-            accepting: "Woody".to_string(),
-            resolved_accepting_user: None,
-            wager: "$20".to_string(),
-            outcome: "Rangers repeat".to_string(),
-            status: WagerStatus::Open,
-            expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
-        })
-        .await
-        .unwrap();
         let app = Application::new(
-            repo,
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             client.clone(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let expected = r#"{"type":4,"data":{"content":"Closing: ---- vs Woody, wager: $20 - Rangers repeat (settles: May  5, 2024)","flags":64,"components":[{"type":1,"components":[{"type":2,"style":1,"label":"---- won","custom_id":"offering_109","disabled":false},{"type":2,"style":1,"label":"Woody won","custom_id":"accepting_109","disabled":false},{"type":2,"style":1,"label":"No bet","custom_id":"nobet_109","disabled":false},{"type":2,"style":2,"label":"Cancel","custom_id":"cancel_109","disabled":false}]}]}}"#;
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> accepted: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
         assert_response(result, expected);
-        assert_eq!(None, get_client_message(&client))
+        assert_eq!(WagerStatus::Open, repo.get(109).await.unwrap().status);
+        assert_eq!(None, get_client_message(&client));
+        assert_eq!(
+            vec![WagerAction::Accepted],
+            recorded_actions(&repo, 109).await
+        );
     }
 
     #[tokio::test]
-    async fn t32_reason_selected_cancel() {
-        let request = expect_request_from("dto_payloads/T32a_reason_selected.json");
+    async fn t12_accept_wager_not_the_accepting_user() {
+        let request = expect_request_from("dto_payloads/T12_accept_wager.json");
         let repo = InMemWagerRepository::default();
-        repo.insert(Wager {
-            wager_id: 109,
-            time: "".to_string(),
-            offering: "----".to_string(),
-            resolved_offering_user: Some(695398918694895710.into()),
-            accepting: "Woody".to_string(),
-            resolved_accepting_user: None,
-            wager: "$20".to_string(),
-            outcome: "Rangers repeat".to_string(),
-            status: WagerStatus::Open,
-            expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
-        })
-        .await
-        .unwrap();
+        repo.insert(pending_wager(1054147659289600060))
+            .await
+            .unwrap();
         let client = TestDiscordClient::default();
         set_client_message(&client, Some("original message".to_string()));
         let app = Application::new(
-            repo,
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             client.clone(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let expected = r#"{"type":4,"data":{"content":"No bets were settled","flags":64}}"#;
+        let expected = r#"{"type":4,"data":{"content":"Only <@1054147659289600060> can accept or decline this wager","flags":64}}"#;
         assert_response(result, expected);
-        assert_eq!(None, get_client_message(&client))
+        assert_eq!(
+            WagerStatus::AwaitingAcceptance,
+            repo.get(109).await.unwrap().status
+        );
+        assert_eq!(
+            Some("original message".to_string()),
+            get_client_message(&client)
+        )
     }
 
     #[tokio::test]
-    async fn t32_reason_selected() {
-        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+    async fn t12_decline_wager() {
+        let request = expect_request_from("dto_payloads/T12_decline_wager.json");
         let repo = InMemWagerRepository::default();
-        let client = TestDiscordClient::default();
-        set_client_message(&client, Some("original message".to_string()));
-        repo.insert(Wager {
-            wager_id: 109,
-            time: "".to_string(),
-            offering: "----".to_string(),
-            resolved_offering_user: Some(695398918694895710.into()),
-            accepting: "Woody".to_string(),
-            resolved_accepting_user: None,
-            wager: "$20".to_string(),
-            outcome: "Rangers repeat".to_string(),
-            status: WagerStatus::Open,
-            expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
-        })
-        .await
-        .unwrap();
+        repo.insert(pending_wager(695398918694895710))
+            .await
+            .unwrap();
         let app = Application::new(
-            repo,
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
-            client.clone(),
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let expected = r#"{"type":4,"data":{"content":"Woody won: <@695398918694895710> vs Woody, wager: $20 - Rangers repeat"}}"#;
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> declined: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
         assert_response(result, expected);
-        assert_eq!(None, get_client_message(&client))
+        assert_eq!(WagerStatus::Declined, repo.get(109).await.unwrap().status);
     }
 
     #[tokio::test]
-    async fn t40_attendance_not_an_owner() {
-        let request = expect_request_from("dto_payloads/T40_attendance_no_options.json");
+    async fn t12_accept_wager_over_limit() {
+        let request = expect_request_from("dto_payloads/T12_accept_wager.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(pending_wager(695398918694895710))
+            .await
+            .unwrap();
+        let mut other = pending_wager(695398918694895710);
+        other.wager_id = 110;
+        other.wager = "$30".into();
+        repo.insert(other).await.unwrap();
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.max_exposure = Some("$40".to_string());
+        admin_repo.update(settings).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
+            repo.clone(),
             InMemoryAttendanceRepository::default(),
-            test_admin_repo().await,
+            admin_repo,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
+        let expected = r#"{"type":4,"data":{"content":"You can't accept this bet, it would put $50 at stake across your open bets and the limit is $40","flags":64}}"#;
+        assert_response(result, expected);
         assert_eq!(
-            found,
-            r#"{"type":4,"data":{"content":"no attendance records found, is <@695398918694895710> in the league?","flags":64}}"#
+            WagerStatus::AwaitingAcceptance,
+            repo.get(109).await.unwrap().status
         );
+        assert!(recorded_actions(&repo, 109).await.is_empty());
     }
 
     #[tokio::test]
-    async fn t40_attendance_no_options() {
-        let request = expect_request_from("dto_payloads/T40_attendance_no_options.json");
+    async fn t12_accept_wager_already_answered() {
+        let request = expect_request_from("dto_payloads/T12_accept_wager.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::Declined;
+        repo.insert(wager).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
-        assert_eq!(
-            found,
-            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"Attendance through week 18\",\"type\":\"rich\",\"description\":\"<@695398918694895710>\\nRanks in the top quarter, outstanding attendance!\\n🤩\",\"fields\":[{\"name\":\"Weekly attendance\",\"value\":\"Attended 10 of 18 weeks\",\"inline\":false},{\"name\":\"Game attendance\",\"value\":\"Attended 30 games\",\"inline\":false}]}],\"flags\":64}}"
-        );
+        let expected = r#"{"type":4,"data":{"content":"This wager is no longer waiting on a response","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::Declined, repo.get(109).await.unwrap().status);
     }
 
     #[tokio::test]
-    async fn t40_attendance_manager() {
-        let request = expect_request_from("dto_payloads/T40_attendance_manager.json");
+    async fn t13_edit_bet() {
+        let request = expect_request_from("dto_payloads/T13_edit_bet.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo,
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
         let found = serde_json::to_string(&result).unwrap();
-        assert_eq!(
-            found,
-            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"Attendance through week 18\",\"type\":\"rich\",\"description\":\"<@1050119194533961860>\\nRanks in the top quarter, outstanding attendance!\\n🤩\",\"fields\":[{\"name\":\"Weekly attendance\",\"value\":\"Attended 7 of 18 weeks\",\"inline\":false},{\"name\":\"Game attendance\",\"value\":\"Attended 14 games\",\"inline\":false}]}]}}"
-        );
+        let mut expected = r#"{"type":9,"data":{"custom_id":"edit:1:109","title":"Edit a bet","components":[{"type":1,"components":[{"type":4,"custom_id":"wager","label":"How much are we wagering?","placeholder":"$20","style":1,"min_length":2,"max_length":30,"required":true,"value":"$20"}]},{"type":1,"components":[{"type":4,"custom_id":"odds","label":"What do they put up? (empty for even odds)","placeholder":"3-1 or $10","style":1,"max_length":30,"required":false}]},{"type":1,"components":[{"type":4,"custom_id":"outcome","label":"What is the bet on?","placeholder":"Raiders make the playoffs","style":2,"min_length":3,"max_length":100,"required":true,"value":"Rangers repeat"}]},{"type":1,"components":[{"type":4,"custom_id":"settlement","label":"When will this bet settle?","placeholder":""#.to_string();
+        expected += Local::now().format("%m/%d").to_string().as_str();
+        expected += r#"","style":1,"min_length":3,"max_length":30,"required":false,"value":"09/15/2024"}]}]}}"#;
+        assert_eq!(found, expected);
     }
 
     #[tokio::test]
-    async fn t40_attendance_week() {
-        let request = expect_request_from("dto_payloads/T40_attendance_week.json");
+    async fn t13_edit_bet_not_open() {
+        let request = expect_request_from("dto_payloads/T13_edit_bet.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(pending_wager(695398918694895710))
+            .await
+            .unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo,
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
-        assert_eq!(
-            found,
-            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"Attendance for week 1\",\"type\":\"rich\",\"description\":\"\",\"fields\":[{\"name\":\"Thu, Nov 23\",\"value\":\"Dave\",\"inline\":false},{\"name\":\"Sun, Nov 26\",\"value\":\"Dave, Steve, Shawn\",\"inline\":false},{\"name\":\"Mon, Nov 27\",\"value\":\"Dave\",\"inline\":false}]}],\"flags\":64}}"
-        );
+        let expected = r#"{"type":4,"data":{"content":"Only open bets can be edited","flags":64}}"#;
+        assert_response(result, expected);
     }
 
     #[tokio::test]
-    async fn t40_attendance_week_not_yet_arrived() {
-        let request = expect_request_from("dto_payloads/T40_attendance_week_19.json");
+    async fn t13_amend_wager() {
+        let request = expect_request_from("dto_payloads/T13_edit_modal.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860>, <@695398918694895710> wants to change this wager:\n<@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat (settles: Sep 15, 2024)\nto:\n<@1050119194533961860> vs <@695398918694895710>, wager: $25 - Rangers repeat (settles: Oct  1, 2024)","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Confirm","custom_id":"amend:1:confirm:1","disabled":false},{"type":2,"style":4,"label":"Reject","custom_id":"amend:1:reject:1","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(open_wager(), repo.get(109).await.unwrap());
         assert_eq!(
-            found,
-            "{\"type\":4,\"data\":{\"content\":\"No information for week 19\",\"flags\":64}}"
+            Some(RevisionStatus::Proposed),
+            repo.get_revision(1).await.map(|revision| revision.status)
         );
     }
 
     #[tokio::test]
-    async fn t60_admin_help() {
-        let request = expect_request_from("dto_payloads/T60_admin_help.json");
+    async fn t13_amend_wager_with_change_pending() {
+        let request = expect_request_from("dto_payloads/T13_edit_modal.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        propose_amendment(&repo, 1050119194533961860).await;
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        assert!(serde_json::to_value(&result).is_ok());
-        // let found = serde_json::to_string(&result).unwrap();
-        // assert_eq!(
-        //     found,
-        //     r##"{"type":4,"data":{"embeds":[{"title":"POG Admin help","type":"rich","description":"Admin-only commands","fields":[{"name":"Show bets","value":"`/welcome_channel` sets the expected landing page for new users.\n","inline":false}]}],"flags":64}}"##
-        // );
+        let expected = r#"{"type":4,"data":{"content":"Another change to this bet is still waiting on a response","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, repo.get_revision(2).await);
     }
 
     #[tokio::test]
-    async fn t60_admin_welcome() {
-        let request = expect_request_from("dto_payloads/T60_admin_welcome.json");
+    async fn t13_amend_wager_without_counterparty() {
+        let request = expect_request_from("dto_payloads/T13_edit_modal.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = open_wager();
+        wager.resolved_offering_user = None;
+        repo.insert(wager).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> amended: Cisco vs <@695398918694895710>, wager: $25 - Rangers repeat (settles: Oct  1, 2024)"}}"#;
+        assert_response(result, expected);
+        assert_eq!("$25", repo.get(109).await.unwrap().wager.to_string());
+        assert_eq!(vec![109], repo.amended(&[109]).await.unwrap());
         assert_eq!(
-            found,
-            r##"{"type":4,"data":{"content":"welcome channel updated to: <#1165637665908080730>","flags":64}}"##
+            vec![WagerAction::Edited],
+            recorded_actions(&repo, 109).await
         );
     }
 
     #[tokio::test]
-    async fn t60_set_user() {
-        let request = expect_request_from("dto_payloads/T60_admin_set_user.json");
+    async fn t13_confirm_amendment() {
+        let request = expect_request_from("dto_payloads/T13_confirm_amendment.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        propose_amendment(&repo, 1050119194533961860).await;
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
         let app = Application::new(
-            InMemWagerRepository::default(),
-            test_attendance_repo(),
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
             test_admin_repo().await,
             test_whois_repo().await,
-            TestDiscordClient::default(),
+            InMemPoolRepository::default(),
+            client.clone(),
         );
 
         let result = app.request_handler(request).await.unwrap();
 
-        let found = serde_json::to_string(&result).unwrap();
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> confirmed the change: <@1050119194533961860> vs <@695398918694895710>, wager: $25 - Rangers repeat (settles: Sep 15, 2024)"}}"#;
+        assert_response(result, expected);
+        assert_eq!("$25", repo.get(109).await.unwrap().wager.to_string());
+        assert_eq!(None, get_client_message(&client));
         assert_eq!(
-            found,
-            r##"{"type":9,"data":{"custom_id":"setuser|1166787343743725609","title":"Add a user","components":[{"type":1,"components":[{"type":4,"custom_id":"human_name","label":"Human name?","placeholder":"Neil N. Bob","style":1,"max_length":30,"required":false}]},{"type":1,"components":[{"type":4,"custom_id":"hash_name","label":"Hash name?","placeholder":"Xena, the Warrior Princess","style":1,"max_length":30,"required":false}]}]}}"##
+            vec![WagerAction::Edited],
+            recorded_actions(&repo, 109).await
         );
     }
 
     #[tokio::test]
-    async fn t61_admin_set_user_modal() {
-        let request = expect_request_from("dto_payloads/T61_admin_set_user_modal.json");
+    async fn t13_amend_wager_over_limit() {
+        let request = expect_request_from("dto_payloads/T13_edit_modal.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.max_exposure = Some("$24".to_string());
+        admin_repo.update(settings).await.unwrap();
         let app = Application::new(
-            InMemWagerRepository::default(),
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            admin_repo,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"You can't make this change, it would put $25 at stake across your open bets and the limit is $24","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, repo.get_revision(1).await);
+    }
+
+    #[tokio::test]
+    async fn t13_confirm_amendment_over_limit() {
+        let request = expect_request_from("dto_payloads/T13_confirm_amendment.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        propose_amendment(&repo, 1050119194533961860).await;
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.max_stake = Some("$20".to_string());
+        admin_repo.update(settings).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            admin_repo,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"You can't agree to this change, the most anyone can put up on a bet is $20","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(open_wager(), repo.get(109).await.unwrap());
+        assert_eq!(
+            Some(RevisionStatus::Proposed),
+            repo.get_revision(1).await.map(|revision| revision.status)
+        );
+    }
+
+    #[tokio::test]
+    async fn t13_confirm_amendment_by_proposer() {
+        let request = expect_request_from("dto_payloads/T13_confirm_amendment.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        propose_amendment(&repo, 695398918694895710).await;
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only <@1050119194533961860> can confirm or reject this change","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(open_wager(), repo.get(109).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn t13_reject_amendment_by_proposer() {
+        let request = expect_request_from("dto_payloads/T13_reject_amendment.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        propose_amendment(&repo, 695398918694895710).await;
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> withdrew the change to: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat (settles: Sep 15, 2024)"}}"#;
+        assert_response(result, expected);
+        assert_eq!(open_wager(), repo.get(109).await.unwrap());
+        assert_eq!(
+            Some(RevisionStatus::Rejected),
+            repo.get_revision(1).await.map(|revision| revision.status)
+        );
+        assert_eq!(
+            vec![WagerAction::EditWithdrawn],
+            recorded_actions(&repo, 109).await
+        );
+    }
+
+    #[tokio::test]
+    async fn t14_open_offer_request() {
+        let request = expect_request_from("dto_payloads/T14_open_offer_request.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert!(found.starts_with(
+            r#"{"type":9,"data":{"custom_id":"open_offer:1","title":"Post an open offer","#
+        ));
+    }
+
+    #[tokio::test]
+    async fn t14_open_offer_modal() {
+        let request = expect_request_from("dto_payloads/T14_open_offer_modal.json");
+        let repo = InMemWagerRepository::default();
+        let client = TestDiscordClient::default();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected =
+            r#"{"type":4,"data":{"content":"Your offer was posted in <#555555555>","flags":64}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(1).await.unwrap();
+        assert_eq!(WagerStatus::OpenOffer, wager.status);
+        assert_eq!(OPEN_OFFER_ACCEPTING, wager.accepting);
+        assert_eq!(None, wager.resolved_accepting_user);
+        assert_eq!(vec![WagerAction::Created], recorded_actions(&repo, 1).await);
+        let messages = client.created_messages.lock().unwrap().clone();
+        assert_eq!(1, messages.len());
+        let (channel, message, components) = &messages[0];
+        assert_eq!("555555555", channel);
+        assert!(message.starts_with("Open offer, the first to take it is on: <@695398918694895710> vs anyone, wager: $20 - something something\nExpires <t:"));
+        assert_eq!(
+            r#"[{"type":1,"components":[{"type":2,"style":3,"label":"Take it","custom_id":"offer:1:take:1","disabled":false},{"type":2,"style":4,"label":"Withdraw","custom_id":"offer:1:withdraw:1","disabled":false}]}]"#,
+            serde_json::to_string(components).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn t14_take_offer() {
+        let request = expect_request_from("dto_payloads/T14_take_offer.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_offer(1050119194533961860)).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":7,"data":{"content":"<@695398918694895710> took the offer: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","components":[{"type":1,"components":[{"type":2,"style":2,"label":"Taken","custom_id":"offer:1:closed:109","disabled":true}]}]}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(109).await.unwrap();
+        assert_eq!(WagerStatus::Open, wager.status);
+        assert_eq!("Harx", wager.accepting);
+        assert_eq!(
+            Some(695398918694895710.into()),
+            wager.resolved_accepting_user
+        );
+        assert_eq!(
+            vec![WagerAction::Accepted],
+            recorded_actions(&repo, 109).await
+        );
+    }
+
+    #[tokio::test]
+    async fn t14_take_offer_over_limit() {
+        let request = expect_request_from("dto_payloads/T14_take_offer.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_offer(1050119194533961860)).await.unwrap();
+        let mut own_offer = open_offer(695398918694895710);
+        own_offer.wager_id = 110;
+        repo.insert(own_offer).await.unwrap();
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.max_open_wagers = 1;
+        admin_repo.update(settings).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            admin_repo,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"You can't take this offer, you already have 1 open bets and the limit is 1","flags":64}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(109).await.unwrap();
+        assert_eq!(WagerStatus::OpenOffer, wager.status);
+        assert_eq!(None, wager.resolved_accepting_user);
+    }
+
+    #[tokio::test]
+    async fn t14_take_offer_already_taken() {
+        let request = expect_request_from("dto_payloads/T14_take_offer.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = open_offer(1050119194533961860);
+        wager.status = WagerStatus::Open;
+        wager.accepting = "Woody".to_string();
+        wager.resolved_accepting_user = Some(1054147659289600060.into());
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@1054147659289600060> already took this offer","flags":64}}"#;
+        assert_response(result, expected);
+        assert!(recorded_actions(&repo, 109).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn t14_take_own_offer() {
+        let request = expect_request_from("dto_payloads/T14_take_offer.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_offer(695398918694895710)).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected =
+            r#"{"type":4,"data":{"content":"You can't take your own offer","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::OpenOffer, repo.get(109).await.unwrap().status);
+    }
+
+    #[tokio::test]
+    async fn t14_take_expired_offer() {
+        let request = expect_request_from("dto_payloads/T14_take_offer.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = open_offer(1050119194533961860);
+        wager.time = "2024-09-01T12:00:00+00:00".to_string();
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":7,"data":{"content":"This offer expired: <@1050119194533961860> vs anyone, wager: $20 - Rangers repeat","components":[{"type":1,"components":[{"type":2,"style":2,"label":"Expired","custom_id":"offer:1:closed:109","disabled":true}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::Declined, repo.get(109).await.unwrap().status);
+        assert_eq!(
+            vec![WagerAction::Expired],
+            recorded_actions(&repo, 109).await
+        );
+    }
+
+    #[tokio::test]
+    async fn t14_withdraw_offer() {
+        let request = expect_request_from("dto_payloads/T14_withdraw_offer.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_offer(695398918694895710)).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":7,"data":{"content":"<@695398918694895710> withdrew the offer: <@695398918694895710> vs anyone, wager: $20 - Rangers repeat","components":[{"type":1,"components":[{"type":2,"style":2,"label":"Withdrawn","custom_id":"offer:1:closed:109","disabled":true}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::Declined, repo.get(109).await.unwrap().status);
+        assert_eq!(
+            vec![WagerAction::Withdrawn],
+            recorded_actions(&repo, 109).await
+        );
+    }
+
+    #[tokio::test]
+    async fn t14_withdraw_offer_not_the_offering_user() {
+        let request = expect_request_from("dto_payloads/T14_withdraw_offer.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_offer(1050119194533961860)).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only <@1050119194533961860> can withdraw this offer","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::OpenOffer, repo.get(109).await.unwrap().status);
+    }
+
+    #[tokio::test]
+    async fn t14_expire_offers() {
+        let repo = InMemWagerRepository::default();
+        let mut stale = open_offer(1050119194533961860);
+        stale.time = "2024-09-01T12:00:00+00:00".to_string();
+        repo.insert(stale).await.unwrap();
+        let mut fresh = open_offer(1050119194533961860);
+        fresh.wager_id = 110;
+        fresh.time = "2024-09-02T06:00:00+00:00".to_string();
+        repo.insert(fresh).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+        let now = chrono::DateTime::parse_from_rfc3339("2024-09-02T13:00:00+00:00")
+            .unwrap()
+            .to_utc();
+
+        assert_eq!(1, app.expire_offers(now).await.unwrap());
+        assert_eq!(0, app.expire_offers(now).await.unwrap());
+
+        assert_eq!(WagerStatus::Declined, repo.get(109).await.unwrap().status);
+        assert_eq!(WagerStatus::OpenOffer, repo.get(110).await.unwrap().status);
+    }
+
+    async fn test_pool_repo(entries: &[(i64, &str)]) -> InMemPoolRepository {
+        let repo = InMemPoolRepository::default();
+        repo.insert(Pool {
+            pool_id: 0,
+            time: "2024-09-01T12:00:00+00:00".to_string(),
+            created_by: 1050119194533961860.into(),
+            question: "Who wins the Super Bowl?".to_string(),
+            buy_in: "$10".into(),
+            options: vec!["Chiefs".to_string(), "Eagles".to_string()],
+            status: PoolStatus::Open,
+            winning_option: None,
+            entries: vec![],
+        })
+        .await
+        .unwrap();
+        for (user_id, pick) in entries {
+            let entry = PoolEntry {
+                user_id: (*user_id).into(),
+                name: user_id.to_string(),
+                pick: pick.to_string(),
+                buy_in: "$10".into(),
+            };
+            repo.join(1, entry).await.unwrap();
+        }
+        repo
+    }
+
+    async fn pool_app(
+        pool_repo: InMemPoolRepository,
+    ) -> Application<
+        InMemWagerRepository,
+        InMemoryAttendanceRepository,
+        InMemAdminRepository,
+        InMemWhoisRepository,
+        InMemPoolRepository,
+        TestDiscordClient,
+    > {
+        Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            pool_repo,
+            TestDiscordClient::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn t15_pool_request() {
+        let request = expect_request_from("dto_payloads/T15_pool_request.json");
+        let app = pool_app(InMemPoolRepository::default()).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":9,"data":{"custom_id":"pool_modal:1","title":"Start a pool","components":[{"type":1,"components":[{"type":4,"custom_id":"question","label":"What is the pool on?","placeholder":"Who wins the Super Bowl?","style":1,"min_length":3,"max_length":100,"required":true}]},{"type":1,"components":[{"type":4,"custom_id":"buy_in","label":"What is the buy-in?","placeholder":"$10","style":1,"min_length":2,"max_length":30,"required":true}]},{"type":1,"components":[{"type":4,"custom_id":"options","label":"What can people pick? (one per line)","placeholder":"Chiefs\nEagles","style":2,"min_length":3,"max_length":1000,"required":true}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t15_pool_modal() {
+        let request = expect_request_from("dto_payloads/T15_pool_modal.json");
+        let pool_repo = InMemPoolRepository::default();
+        let app = pool_app(pool_repo.clone()).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> started a pool: Pool 1: Who wins the Super Bowl?, buy-in: $10, 0 in, pot: $0\n- Chiefs\n- Eagles","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Join","custom_id":"pool:1:join:1","disabled":false},{"type":2,"style":2,"label":"Settle","custom_id":"pool:1:settle:1","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        let pool = pool_repo.get(1).await.unwrap();
+        assert_eq!(Some(695398918694895710.into()), Some(pool.created_by));
+        assert_eq!(
+            vec!["Chiefs".to_string(), "Eagles".to_string()],
+            pool.options
+        );
+    }
+
+    #[tokio::test]
+    async fn t15_pool_modal_long_option() {
+        let contents = fs::read_to_string("dto_payloads/T15_pool_modal.json").unwrap();
+        let request = serde_json::from_str(&contents.replace(
+            r#""value": "Chiefs\nEagles\n""#,
+            &format!(r#""value": "Chiefs\n{}\n""#, "E".repeat(101)),
+        ))
+        .unwrap();
+        let pool_repo = InMemPoolRepository::default();
+        let app = pool_app(pool_repo.clone()).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Pool options can be at most 100 characters each","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, pool_repo.get(1).await);
+    }
+
+    #[tokio::test]
+    async fn t15_join_pool() {
+        let request = expect_request_from("dto_payloads/T15_join_pool.json");
+        let app = pool_app(test_pool_repo(&[(1050119194533961860, "Eagles")]).await).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Pick your option for Who wins the Super Bowl? (buy-in: $10)","flags":64,"components":[{"type":1,"components":[{"type":3,"custom_id":"pool:1:pick:1","options":[{"label":"Chiefs","value":"Chiefs","description":"0 picked"},{"label":"Eagles","value":"Eagles","description":"1 picked"}],"placeholder":"Your pick"}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t15_join_pool_already_joined() {
+        let request = expect_request_from("dto_payloads/T15_join_pool.json");
+        let app = pool_app(test_pool_repo(&[(695398918694895710, "Eagles")]).await).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected =
+            r#"{"type":4,"data":{"content":"You're already in this pool with Eagles","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t15_pick_pool() {
+        let request = expect_request_from("dto_payloads/T15_pick_pool.json");
+        let pool_repo = test_pool_repo(&[(1050119194533961860, "Eagles")]).await;
+        let app = pool_app(pool_repo.clone()).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> joined pool 1 with Chiefs, the pot is now $20"}}"#;
+        assert_response(result, expected);
+        let pool = pool_repo.get(1).await.unwrap();
+        let entry = pool.entry(&695398918694895710.into()).unwrap();
+        assert_eq!("Chiefs", entry.pick);
+        assert_eq!("Harx", entry.name);
+    }
+
+    #[tokio::test]
+    async fn t15_settle_pool() {
+        let request = expect_request_from("dto_payloads/T15_settle_pool.json");
+        let app = pool_app(test_pool_repo(&[(1050119194533961860, "Eagles")]).await).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Which option won? Who wins the Super Bowl?","flags":64,"components":[{"type":1,"components":[{"type":3,"custom_id":"pool:1:winner:1","options":[{"label":"Chiefs","value":"Chiefs","description":"0 picked"},{"label":"Eagles","value":"Eagles","description":"1 picked"}],"placeholder":"Winning option"}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t15_settle_pool_not_admin() {
+        let request = expect_request_from("dto_payloads/T15_settle_pool_not_admin.json");
+        let app = pool_app(test_pool_repo(&[(695398918694895710, "Eagles")]).await).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only <@1050119194533961860> or an admin can settle this pool","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t15_pool_winner() {
+        let request = expect_request_from("dto_payloads/T15_pool_winner.json");
+        let pool_repo = test_pool_repo(&[
+            (695398918694895710, "Chiefs"),
+            (1050119194533961860, "Eagles"),
+            (1054147659289600060, "Chiefs"),
+        ])
+        .await;
+        let app = pool_app(pool_repo.clone()).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Pool 1 settled, Chiefs won: Who wins the Super Bowl?\nPot: $30\n- <@695398918694895710> wins $15\n- <@1054147659289600060> wins $15"}}"#;
+        assert_response(result, expected);
+        let pool = pool_repo.get(1).await.unwrap();
+        assert_eq!(PoolStatus::Settled, pool.status);
+        assert_eq!(Some("Chiefs".to_string()), pool.winning_option);
+    }
+
+    #[tokio::test]
+    async fn t15_pool_winner_already_settled() {
+        let request = expect_request_from("dto_payloads/T15_pool_winner.json");
+        let pool_repo = test_pool_repo(&[(695398918694895710, "Chiefs")]).await;
+        pool_repo.settle(1, "Eagles").await.unwrap();
+        let app = pool_app(pool_repo.clone()).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"This pool was already settled, Eagles won: Pool 1: Who wins the Super Bowl?, buy-in: $10, 1 in, pot: $10","flags":64}}"#;
+        assert_response(result, expected);
+        let pool = pool_repo.get(1).await.unwrap();
+        assert_eq!(Some("Eagles".to_string()), pool.winning_option);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_no_bets() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r#"{"type":4,"data":{"content":"Harx has no outstanding wagers","flags":64}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(Wager {
+            wager_id: 109,
+            time: "".to_string(),
+            resolved_offering_user: Some(695398918694895710.into()),
+            outcome: "Rangers repeat".to_string(),
+            ..Wager::fixture("----", "Woody", WagerStatus::Open)
+        })
+        .await
+        .unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r#"{"type":4,"data":{"content":"Harx has 1 outstanding wagers:\n- ---- vs Woody, wager: $20 - Rangers repeat","flags":64}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_unsettled() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let repo = InMemWagerRepository::default();
+        for (wager_id, status) in [
+            (109, WagerStatus::Open),
+            (110, WagerStatus::AwaitingAcceptance),
+            (111, WagerStatus::Disputed),
+            (112, WagerStatus::Declined),
+        ] {
+            repo.insert(Wager {
+                wager_id,
+                time: "".to_string(),
+                resolved_offering_user: Some(695398918694895710.into()),
+                outcome: "Rangers repeat".to_string(),
+                ..Wager::fixture("----", "Woody", status)
+            })
+            .await
+            .unwrap();
+        }
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx has 3 outstanding wagers:\n- ---- vs Woody, wager: $20 - Rangers repeat (disputed)\n- ---- vs Woody, wager: $20 - Rangers repeat (awaiting acceptance)\n- ---- vs Woody, wager: $20 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_amended() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = open_wager();
+        wager.resolved_offering_user = Some(695398918694895710.into());
+        wager.expected_settle_date = None;
+        repo.insert(wager).await.unwrap();
+        let revision_id = propose_amendment(&repo, 695398918694895710).await;
+        repo.resolve_revision(revision_id as i32, RevisionStatus::Accepted, &[])
+            .await
+            .unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx has 1 outstanding wagers:\n- Cisco vs Harx, wager: $25 - Rangers repeat (amended)","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_open_offers() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_offer(695398918694895710)).await.unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx has no outstanding wagers\nOpen offers waiting to be taken:\n- Cisco vs anyone, wager: $20 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_many_offers_and_pools() {
+        let repo = InMemWagerRepository::default();
+        for _ in 0..12 {
+            let mut offer = open_offer(695398918694895710);
+            offer.outcome = "x".repeat(100);
+            repo.insert(offer).await.unwrap();
+        }
+        let pool_repo = InMemPoolRepository::default();
+        for pool_id in 1..=12 {
+            let pick = "p".repeat(600);
+            pool_repo
+                .insert(Pool {
+                    pool_id: 0,
+                    time: "2024-09-01T12:00:00+00:00".to_string(),
+                    created_by: 1050119194533961860.into(),
+                    question: "q".repeat(100),
+                    buy_in: "$10".into(),
+                    options: vec![pick.clone()],
+                    status: PoolStatus::Open,
+                    winning_option: None,
+                    entries: vec![],
+                })
+                .await
+                .unwrap();
+            let entry = PoolEntry {
+                user_id: 695398918694895710.into(),
+                name: "Harx".to_string(),
+                pick,
+                buy_in: "$10".into(),
+            };
+            pool_repo.join(pool_id, entry).await.unwrap();
+        }
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            pool_repo,
+            TestDiscordClient::default(),
+        );
+
+        let mut request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let (mut offers, mut pools, mut pages) = (0, 0, 0);
+        loop {
+            let result = app.request_handler(request).await.unwrap();
+            let response = serde_json::to_value(&result).unwrap();
+            let content = response["data"]["content"].as_str().unwrap();
+            assert!(content.chars().count() <= 2000, "{}", content);
+            offers += content.matches("Cisco vs anyone").count();
+            pools += content.matches("\n- Pool ").count();
+            pages += 1;
+            let next = response["data"]["components"][0]["components"]
+                .as_array()
+                .and_then(|buttons| buttons.iter().find(|button| button["label"] == "Next"));
+            let custom_id = match next {
+                Some(button) => button["custom_id"].as_str().unwrap().to_string(),
+                None => break,
+            };
+            let contents = fs::read_to_string("dto_payloads/T20_list_bets_next_page.json").unwrap();
+            request = serde_json::from_str(
+                &contents.replace("betpage:1:1:all:::695398918694895710:Harx", &custom_id),
+            )
+            .unwrap();
+        }
+        assert_eq!((12, 12), (offers, pools));
+        assert!(pages > 3);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_pools() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request.json");
+        let app = pool_app(test_pool_repo(&[(695398918694895710, "Chiefs")]).await).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx has no outstanding wagers\nPools:\n- Pool 1: Who wins the Super Bowl?, buy-in: $10, 1 in, pot: $10 (picked Chiefs)","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_all_pools() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request_all.json");
+        let pool_repo = test_pool_repo(&[
+            (695398918694895710, "Chiefs"),
+            (1050119194533961860, "Eagles"),
+        ])
+        .await;
+        pool_repo.settle(1, "Chiefs").await.unwrap();
+        let app = pool_app(pool_repo).await;
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx has no wagers\nPools:\n- Pool 1: Who wins the Super Bowl?, buy-in: $10, 2 in, pot: $20 (picked Chiefs, won $20)","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_refunded_pools() {
+        let pool_repo = test_pool_repo(&[
+            (695398918694895710, "Eagles"),
+            (1050119194533961860, "Eagles"),
+        ])
+        .await;
+        pool_repo.settle(1, "Chiefs").await.unwrap();
+        let app = pool_app(pool_repo).await;
+
+        let request = expect_request_from("dto_payloads/T20_list_bets_request_lost.json");
+        let result = app.request_handler(request).await.unwrap();
+        let expected = r#"{"type":4,"data":{"content":"Harx has no lost wagers","flags":64}}"#;
+        assert_response(result, expected);
+
+        let request = expect_request_from("dto_payloads/T20_list_bets_request_all.json");
+        let result = app.request_handler(request).await.unwrap();
+        let expected = r#"{"type":4,"data":{"content":"Harx has no wagers\nPools:\n- Pool 1: Who wins the Super Bowl?, buy-in: $10, 2 in, pot: $20 (picked Eagles, Chiefs won, refunded)","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_all() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request_all.json");
+        let app = Application::new(
+            test_history_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx has 10 wagers (page 1 of 2):\n- Harx vs Woody, wager: $10 - Game 10 (no bet)\n- Harx vs Woody, wager: $9 - Game 9 (Woody won)\n- Harx vs Woody, wager: $8 - Game 8 (Harx won)\n- Harx vs Woody, wager: $7 - Game 7 (no bet)\n- Harx vs Woody, wager: $6 - Game 6 (Woody won)\n- Harx vs Woody, wager: $5 - Game 5 (Harx won)\n- Harx vs Woody, wager: $4 - Game 4 (no bet)\n- Harx vs Woody, wager: $3 - Game 3 (Woody won)","flags":64,"components":[{"type":1,"components":[{"type":2,"style":2,"label":"Next","custom_id":"betpage:1:1:all:::695398918694895710:Harx","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_by_name() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_request_by_name.json");
+        let app = Application::new(
+            test_history_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"woody has 3 won wagers:\n- Harx vs Woody, wager: $9 - Game 9 (Woody won)\n- Harx vs Woody, wager: $6 - Game 6 (Woody won)\n- Harx vs Woody, wager: $3 - Game 3 (Woody won)","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_next_page_for_long_name() {
+        let name = "Woody the #1 fan: ".repeat(5).trim_end().to_string();
+        let repo = InMemWagerRepository::default();
+        for wager_id in 1..=9 {
+            let mut wager = pending_wager(695398918694895710);
+            wager.wager_id = wager_id;
+            wager.accepting = name.clone();
+            wager.resolved_accepting_user = None;
+            wager.status = WagerStatus::AcceptingWon;
+            repo.insert(wager).await.unwrap();
+        }
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+        let contents =
+            fs::read_to_string("dto_payloads/T20_list_bets_request_by_name.json").unwrap();
+        let request = serde_json::from_str(&contents.replace(
+            r#""value": "woody""#,
+            &format!(r#""value": "{}""#, name.to_lowercase()),
+        ))
+        .unwrap();
+
+        let result = app.request_handler(request).await.unwrap();
+        let response = serde_json::to_value(&result).unwrap();
+        let custom_id = response["data"]["components"][0]["components"][0]["custom_id"]
+            .as_str()
+            .unwrap();
+        assert_eq!("betpage:1:1:won:::::9:accepting", custom_id);
+
+        let contents = fs::read_to_string("dto_payloads/T20_list_bets_next_page.json").unwrap();
+        let request = serde_json::from_str(
+            &contents.replace("betpage:1:1:all:::695398918694895710:Harx", custom_id),
+        )
+        .unwrap();
+        let result = app.request_handler(request).await.unwrap();
+        let response = serde_json::to_value(&result).unwrap();
+        let content = response["data"]["content"].as_str().unwrap();
+        let expected = format!("{} has 9 won wagers (page 2 of 2):", name);
+        assert!(content.starts_with(&expected), "{}", content);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_next_page() {
+        let request = expect_request_from("dto_payloads/T20_list_bets_next_page.json");
+        let app = Application::new(
+            test_history_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":7,"data":{"content":"Harx has 10 wagers (page 2 of 2):\n- Harx vs Woody, wager: $2 - Game 2 (Harx won)\n- Harx vs Woody, wager: $1 - Game 1 (no bet)","flags":64,"components":[{"type":1,"components":[{"type":2,"style":2,"label":"Prev","custom_id":"betpage:1:0:all:::695398918694895710:Harx","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t20_list_bets_request_w_no_global_user() {
+        let request =
+            expect_request_from("dto_payloads/T20_list_bets_request_w_no_global_user.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r#"{"type":4,"data":{"content":"johnanon has no outstanding wagers","flags":64}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn t21_debts_request() {
+        let request = expect_request_from("dto_payloads/T21_debts_request.json");
+        let repo = InMemWagerRepository::default();
+        let mut lost = pending_wager(695398918694895710);
+        lost.time = "2024-01-01T00:00:00+00:00".to_string();
+        lost.status = WagerStatus::OfferingWon;
+        repo.insert(lost).await.unwrap();
+        repo.insert(Wager {
+            wager_id: 110,
+            resolved_offering_user: Some(695398918694895710.into()),
+            wager: "a beer".into(),
+            outcome: "Raiders win out".to_string(),
+            ..Wager::fixture("Harx", "Woody", WagerStatus::OfferingWon)
+        })
+        .await
+        .unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"You owe:\n- **<@1050119194533961860>: $20 - Rangers repeat (stale, from Jan  1, 2024)**\nYou are owed:\n- Woody: 1 beer - Raiders win out","flags":64,"components":[{"type":1,"components":[{"type":3,"custom_id":"payment:1","options":[{"label":"109","value":"109","description":"Cisco vs Harx, wager: $20 - Rangers repeat"},{"label":"110","value":"110","description":"Harx vs Woody, wager: 1 beer - Raiders win out"}],"placeholder":"Mark which bet paid?"}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t21_debts_request_many_debts() {
+        let request = expect_request_from("dto_payloads/T21_debts_request.json");
+        let repo = InMemWagerRepository::default();
+        for wager_id in 110..140 {
+            repo.insert(Wager {
+                wager_id,
+                resolved_offering_user: Some(695398918694895710.into()),
+                outcome: "Raiders win out".to_string(),
+                ..Wager::fixture("Harx", "Woody", WagerStatus::OfferingWon)
+            })
+            .await
+            .unwrap();
+        }
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_value(&result).unwrap();
+        let content = found["data"]["content"].as_str().unwrap();
+        assert_eq!(25, content.matches("\n- Woody").count());
+        assert!(content.ends_with("\n...and 5 more"));
+        assert_eq!(
+            25,
+            found["data"]["components"][0]["components"][0]["options"]
+                .as_array()
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[tokio::test]
+    async fn t21_debts_request_no_debts() {
+        let request = expect_request_from("dto_payloads/T21_debts_request.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"You have no unpaid bets","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t22_ledger_request() {
+        let request = expect_request_from("dto_payloads/T22_ledger_request.json");
+        let app = Application::new(
+            test_ledger_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Ledger for <@695398918694895710>:\n- <@1050119194533961860> owes <@695398918694895710> $15\n- <@695398918694895710> owes <@1054147659289600060> £2.50","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t22_ledger_request_no_bets() {
+        let request = expect_request_from("dto_payloads/T22_ledger_request.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> has no unpaid bets with other members","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t22_ledger_settle_all() {
+        let request = expect_request_from("dto_payloads/T22_ledger_settle_all.json");
+        let repo = test_ledger_repo().await;
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> settled up with <@1050119194533961860>, 2 bets marked paid: <@1050119194533961860> owes <@695398918694895710> $15"}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::Paid, repo.get(1).await.unwrap().status);
+        assert_eq!(WagerStatus::Paid, repo.get(2).await.unwrap().status);
+        assert_eq!(WagerStatus::AcceptingWon, repo.get(3).await.unwrap().status);
+        assert_eq!(vec![WagerAction::Paid], recorded_actions(&repo, 2).await);
+        assert!(recorded_actions(&repo, 3).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn t22_ledger_settle_all_by_debtor() {
+        let request = expect_request_from("dto_payloads/T22_ledger_settle_all.json");
+        let repo = test_ledger_repo().await;
+        for (wager_id, won, status) in [
+            (1, WagerStatus::OfferingWon, WagerStatus::AcceptingWon),
+            (2, WagerStatus::AcceptingWon, WagerStatus::OfferingWon),
+        ] {
+            let mut wager = repo.get(wager_id).await.unwrap();
+            wager.status = status;
+            repo.update_status(wager_id, won, &wager, &[])
+                .await
+                .unwrap();
+        }
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860>, <@695398918694895710> says they settled up with you: <@695398918694895710> owes <@1050119194533961860> $15","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Confirm paid","custom_id":"settleup:1:695398918694895710:1050119194533961860:11bf3b34","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::AcceptingWon, repo.get(1).await.unwrap().status);
+        assert_eq!(WagerStatus::OfferingWon, repo.get(2).await.unwrap().status);
+        assert!(recorded_actions(&repo, 1).await.is_empty());
+
+        let request = expect_request_from("dto_payloads/T22_confirm_settle_up_by_debtor.json");
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only <@1050119194533961860> can confirm they were paid","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::AcceptingWon, repo.get(1).await.unwrap().status);
+    }
+
+    #[tokio::test]
+    async fn t22_confirm_settle_up() {
+        let request = expect_request_from("dto_payloads/T22_confirm_settle_up.json");
+        let repo = test_ledger_repo().await;
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> confirmed they were paid by <@1050119194533961860>, 2 bets marked paid: <@1050119194533961860> owes <@695398918694895710> $15"}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::Paid, repo.get(1).await.unwrap().status);
+        assert_eq!(WagerStatus::Paid, repo.get(2).await.unwrap().status);
+        assert_eq!(None, get_client_message(&client));
+    }
+
+    #[tokio::test]
+    async fn t22_confirm_settle_up_after_new_bets() {
+        let request = expect_request_from("dto_payloads/T22_confirm_settle_up.json");
+        let repo = test_ledger_repo().await;
+        let mut wager = repo.get(1).await.unwrap();
+        wager.wager_id = 4;
+        wager.wager = "$10".into();
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860>, your bets with <@695398918694895710> changed since you settled up, settle up again to send them the new balance: <@1050119194533961860> owes <@695398918694895710> $25"}}"#;
+        assert_response(result, expected);
+        for wager_id in [1, 2, 4] {
+            assert_ne!(WagerStatus::Paid, repo.get(wager_id).await.unwrap().status);
+        }
+        assert_eq!(None, get_client_message(&client));
+    }
+
+    #[tokio::test]
+    async fn t23_bet_stats_leaderboard() {
+        let request = expect_request_from("dto_payloads/T23_bet_stats_request.json");
+        let app = Application::new(
+            test_ledger_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"embeds":[{"title":"Betting leaderboard","type":"rich","description":"1. <@1054147659289600060> 1 won, 0 lost, 0 no bet, net +£2.50\n2. <@1050119194533961860> 1 won, 1 lost, 0 no bet, net -$15\n3. <@695398918694895710> 1 won, 2 lost, 0 no bet, net +$15 -£2.50"}],"flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t23_bet_stats_personal_record() {
+        let request = expect_request_from("dto_payloads/T23_bet_stats_user_season.json");
+        let repo = test_ledger_repo().await;
+        let mut wager = pending_wager(695398918694895710);
+        wager.time = "2024-10-01T00:00:00+00:00".to_string();
+        wager.status = WagerStatus::NoBet;
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710>'s record for the 2024 season: 0 won, 0 lost, 1 no bet","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t24_bet_history() {
+        let request = expect_request_from("dto_payloads/T24_bet_history.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(open_wager()).await.unwrap();
+        for (time, actor, action, detail) in [
+            (
+                "2024-09-01T12:00:00+00:00",
+                695398918694895710,
+                WagerAction::Accepted,
+                "",
+            ),
+            (
+                "2024-09-02T12:00:00+00:00",
+                1050119194533961860,
+                WagerAction::Edited,
+                "Cisco vs Harx, wager: $25 - Rangers repeat",
+            ),
+        ] {
+            let mut event = WagerEvent::new(
+                109,
+                Some(actor.into()),
+                action,
+                Some(WagerStatus::AwaitingAcceptance),
+                WagerStatus::Open,
+            )
+            .with_detail(detail);
+            event.time = time.to_string();
+            repo.record_event(event).await.unwrap();
+        }
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"History of bet 109: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat (settles: Sep 15, 2024)\n- Cisco placed the bet\n- <t:1725192000:f> <@695398918694895710> accepted\n- <t:1725278400:f> <@1050119194533961860> edited the bet: Cisco vs Harx, wager: $25 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t30_payout_request() {
+        let request = expect_request_from("dto_payloads/T30_payout_request.json");
+        let repository = InMemWagerRepository::default();
+        repository
+            .insert(Wager {
+                wager_id: 1,
+                time: "".to_string(),
+                resolved_offering_user: Some(695398918694895710.into()),
+                outcome: "Raiders win out".to_string(),
+                ..Wager::fixture("Harx", "Woody", WagerStatus::Open)
+            })
+            .await
+            .unwrap();
+        let app = Application::new(
+            repository,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Close out a bet","flags":64,"components":[{"type":1,"components":[{"type":3,"custom_id":"settle:1","options":[{"label":"1","value":"1","description":"Harx vs Woody, wager: $20 - Raiders win out"}],"placeholder":"Close which bet?"}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t30_settle_search() {
+        let request = expect_request_from("dto_payloads/T30_settle_search.json");
+        let app = Application::new(
+            test_settle_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Closing: Harx vs Woody, wager: $20 - Rangers repeat","flags":64,"components":[{"type":1,"components":[{"type":2,"style":1,"label":"Harx won","custom_id":"outcome:1:offering:2","disabled":false},{"type":2,"style":1,"label":"Woody won","custom_id":"outcome:1:accepting:2","disabled":false},{"type":2,"style":1,"label":"No bet","custom_id":"outcome:1:nobet:2","disabled":false},{"type":2,"style":2,"label":"Cancel","custom_id":"outcome:1:cancel:2","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t30_payout_request_admin_sees_disputed() {
+        let request = expect_request_from("dto_payloads/T30_payout_request.json");
+        let repository = InMemWagerRepository::default();
+        let mut wager = pending_wager(1054147659289600060);
+        wager.status = WagerStatus::Disputed;
+        repository.insert(wager).await.unwrap();
+        let app = Application::new(
+            repository,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Close out a bet","flags":64,"components":[{"type":1,"components":[{"type":3,"custom_id":"settle:1","options":[{"label":"109","value":"109","description":"Cisco vs Harx, wager: $20 - Rangers repeat"}],"placeholder":"Close which bet?"}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t30_payout_request_admin_many_disputed() {
+        let request = expect_request_from("dto_payloads/T30_payout_request.json");
+        let repository = InMemWagerRepository::default();
+        for wager_id in 1..=27 {
+            repository
+                .insert(Wager {
+                    wager_id,
+                    time: "".to_string(),
+                    outcome: "Raiders win out".to_string(),
+                    ..Wager::fixture("Harx", "Woody", WagerStatus::Disputed)
+                })
+                .await
+                .unwrap();
+        }
+        let app = Application::new(
+            repository,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let response = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            "Close out a bet\n...and 2 more, use the `wager` option to find them",
+            response["data"]["content"]
+        );
+        let options = response["data"]["components"][0]["components"][0]["options"]
+            .as_array()
+            .unwrap();
+        assert_eq!(25, options.len());
+    }
+
+    #[tokio::test]
+    async fn t30_payout_request_no_bet() {
+        let request = expect_request_from("dto_payloads/T30_payout_request.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"You have no open bets","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    // TODO: fix T30 response payload
+    // An integer was encountered for 'id' within the Application Command Structure
+    // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-application-command-data-structure
+    // This field is specified as a Snowflake and should always be a String, this is likely due to
+    // a malformed response on my part.
+    // https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-application-command-data-structure
+    #[tokio::test]
+    async fn bug_2025_03_20() {
+        expect_request_from("dto_payloads/2025_03_20_settlement_bug.json");
+    }
+
+    #[tokio::test]
+    async fn t31_selected_bet_to_close_request() {
+        let request = expect_request_from("dto_payloads/T31_selected_bet_to_close_request.json");
+        let repo = InMemWagerRepository::default();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        repo.insert(Wager {
+            wager_id: 109,
+            time: "".to_string(),
+            resolved_offering_user: Some(695398918694895710.into()),
+            outcome: "Rangers repeat".to_string(),
+            expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
+            ..Wager::fixture("----", "Woody", WagerStatus::Open)
+        })
+        .await
+        .unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Closing: ---- vs Woody, wager: $20 - Rangers repeat (settles: May  5, 2024)","flags":64,"components":[{"type":1,"components":[{"type":2,"style":1,"label":"---- won","custom_id":"outcome:1:offering:109","disabled":false},{"type":2,"style":1,"label":"Woody won","custom_id":"outcome:1:accepting:109","disabled":false},{"type":2,"style":1,"label":"No bet","custom_id":"outcome:1:nobet:109","disabled":false},{"type":2,"style":2,"label":"Cancel","custom_id":"outcome:1:cancel:109","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, get_client_message(&client))
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_cancel() {
+        let request = expect_request_from("dto_payloads/T32a_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(Wager {
+            wager_id: 109,
+            time: "".to_string(),
+            resolved_offering_user: Some(695398918694895710.into()),
+            outcome: "Rangers repeat".to_string(),
+            expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
+            ..Wager::fixture("----", "Woody", WagerStatus::Open)
+        })
+        .await
+        .unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"No bets were settled","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, get_client_message(&client))
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        repo.insert(Wager {
+            wager_id: 109,
+            time: "".to_string(),
+            resolved_offering_user: Some(695398918694895710.into()),
+            outcome: "Rangers repeat".to_string(),
+            expected_settle_date: NaiveDate::from_ymd_opt(2024, 5, 5),
+            ..Wager::fixture("----", "Woody", WagerStatus::Open)
+        })
+        .await
+        .unwrap();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Woody won: <@695398918694895710> vs Woody, wager: $20 - Rangers repeat\n<@695398918694895710> owes Woody $20","components":[{"type":1,"components":[{"type":2,"style":1,"label":"Mark paid","custom_id":"payment:1:109","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, get_client_message(&client))
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_with_odds() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = open_wager();
+        wager.resolved_offering_user = Some(695398918694895710.into());
+        wager.accepting = "Woody".to_string();
+        wager.resolved_accepting_user = None;
+        wager.wager = "$30".into();
+        wager.accepting_wager = Some("$10".into());
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Woody won: <@695398918694895710> vs Woody, wager: $30 to $10 - Rangers repeat\n<@695398918694895710> owes Woody $30","components":[{"type":1,"components":[{"type":2,"style":1,"label":"Mark paid","custom_id":"payment:1:109","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_not_a_participant() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected_not_admin.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(1054147659289600060);
+        wager.status = WagerStatus::Open;
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only the people in this bet or an admin can settle it","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(WagerStatus::Open, repo.get(109).await.unwrap().status);
+        assert_eq!(
+            Some("original message".to_string()),
+            get_client_message(&client)
+        )
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_already_settled() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::OfferingWon;
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"This bet was already settled as Cisco won: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::OfferingWon,
+            repo.get(109).await.unwrap().status
+        );
+        assert!(repo.events(109).await.unwrap().is_empty());
+        assert_eq!(
+            Some("original message".to_string()),
+            get_client_message(&client)
+        )
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_never_accepted() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(pending_wager(695398918694895710))
+            .await
+            .unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"This bet was never accepted: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::AwaitingAcceptance,
+            repo.get(109).await.unwrap().status
+        );
+        assert!(repo.events(109).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_proposes_settlement() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::Open;
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860>, <@695398918694895710> says the result is: Harx won\n<@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Confirm","custom_id":"verify:1:confirm:109","disabled":false},{"type":2,"style":4,"label":"Dispute","custom_id":"verify:1:dispute:109","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(109).await.unwrap();
+        assert_eq!(WagerStatus::SettlementProposed, wager.status);
+        assert_eq!(
+            Some(SettlementProposal {
+                outcome: WagerStatus::AcceptingWon,
+                proposed_by: 695398918694895710.into(),
+            }),
+            wager.proposal
+        );
+    }
+
+    #[tokio::test]
+    async fn t32_reason_selected_admin_settles_dispute() {
+        let request = expect_request_from("dto_payloads/T32_reason_selected.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(1054147659289600060);
+        wager.status = WagerStatus::Disputed;
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx won: <@1050119194533961860> vs <@1054147659289600060>, wager: $20 - Rangers repeat\n<@1050119194533961860> owes <@1054147659289600060> $20","components":[{"type":1,"components":[{"type":2,"style":1,"label":"Mark paid","custom_id":"payment:1:109","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::AcceptingWon,
+            repo.get(109).await.unwrap().status
+        );
+    }
+
+    #[tokio::test]
+    async fn t33_confirm_settlement() {
+        let request = expect_request_from("dto_payloads/T33_confirm_settlement.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(proposed_wager(1050119194533961860))
+            .await
+            .unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Harx won: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat\n<@1050119194533961860> owes <@695398918694895710> $20","components":[{"type":1,"components":[{"type":2,"style":1,"label":"Mark paid","custom_id":"payment:1:109","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(109).await.unwrap();
+        assert_eq!(WagerStatus::AcceptingWon, wager.status);
+        assert_eq!(None, wager.proposal);
+        let events = repo.events(109).await.unwrap();
+        assert_eq!(1, events.len());
+        assert_eq!(WagerAction::Settled, events[0].action);
+        assert_eq!(Some(695398918694895710.into()), events[0].actor);
+        assert_eq!(
+            Some(WagerStatus::SettlementProposed),
+            events[0].previous_status
+        );
+        assert_eq!("Harx won", events[0].detail);
+    }
+
+    #[tokio::test]
+    async fn t33_confirm_settlement_twice() {
+        let repo = InMemWagerRepository::default();
+        repo.insert(proposed_wager(1050119194533961860))
+            .await
+            .unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        app.request_handler(expect_request_from(
+            "dto_payloads/T33_confirm_settlement.json",
+        ))
+        .await
+        .unwrap();
+        let result = app
+            .request_handler(expect_request_from(
+                "dto_payloads/T33_dispute_settlement.json",
+            ))
+            .await
+            .unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"This bet was already settled as Harx won: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::AcceptingWon,
+            repo.get(109).await.unwrap().status
+        );
+        assert_eq!(
+            vec![WagerAction::Settled],
+            recorded_actions(&repo, 109).await
+        );
+    }
+
+    #[tokio::test]
+    async fn t33_confirm_settlement_by_proposer() {
+        let request = expect_request_from("dto_payloads/T33_confirm_settlement.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(proposed_wager(695398918694895710))
+            .await
+            .unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only <@1050119194533961860> can confirm or dispute this result","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::SettlementProposed,
+            repo.get(109).await.unwrap().status
+        );
+    }
+
+    #[tokio::test]
+    async fn t33_dispute_settlement() {
+        let request = expect_request_from("dto_payloads/T33_dispute_settlement.json");
+        let repo = InMemWagerRepository::default();
+        repo.insert(proposed_wager(1050119194533961860))
+            .await
+            .unwrap();
+        let client = TestDiscordClient::default();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> disputed the result, an admin will settle: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
+        assert_response(result, expected);
+        let wager = repo.get(109).await.unwrap();
+        assert_eq!(WagerStatus::Disputed, wager.status);
+        assert_eq!(None, wager.proposal);
+        assert_eq!(
+            vec![(
+                "987654321".to_string(),
+                "Wager 109 is disputed, settle it with `/settle`: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat".to_string(),
+                vec![]
+            )],
+            *client.created_messages.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn t33_dispute_settlement_without_admin_channel() {
+        let repo = InMemWagerRepository::default();
+        repo.insert(proposed_wager(1050119194533961860))
+            .await
+            .unwrap();
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.admin_channel = String::new();
+        admin_repo.update(settings.clone()).await.unwrap();
+        let client = TestDiscordClient::default();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let request = expect_request_from("dto_payloads/T33_dispute_settlement.json");
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> disputed the result, an admin will settle: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            vec!["555555555".to_string()],
+            client
+                .created_messages
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(channel, _, _)| channel.clone())
+                .collect::<Vec<String>>()
+        );
+
+        let mut wager = proposed_wager(1050119194533961860);
+        wager.wager_id = 110;
+        repo.insert(wager).await.unwrap();
+        settings.betting_channel = String::new();
+        admin_repo.update(settings).await.unwrap();
+        let contents = fs::read_to_string("dto_payloads/T33_dispute_settlement.json").unwrap();
+        let request =
+            serde_json::from_str(&contents.replace("verify:1:dispute:109", "verify:1:dispute:110"))
+                .unwrap();
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> disputed the result, no admin channel is set up so ask an admin to settle it with `/settle`: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
+        assert_response(result, expected);
+        assert_eq!(1, client.created_messages.lock().unwrap().len());
+        assert_eq!(WagerStatus::Disputed, repo.get(110).await.unwrap().status);
+    }
+
+    #[tokio::test]
+    async fn t35_send_settle_reminders() {
+        let repo = test_settle_repo().await;
+        let mut wager = repo.get(2).await.unwrap();
+        wager.wager_id = 3;
+        wager.expected_settle_date = NaiveDate::from_ymd_opt(2024, 10, 1);
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        let app = Application::new(
+            repo,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+        let today = NaiveDate::from_ymd_opt(2024, 10, 2).unwrap();
+
+        assert_eq!(1, app.send_settle_reminders(today).await.unwrap());
+        assert_eq!(0, app.send_settle_reminders(today).await.unwrap());
+
+        let messages = client.created_messages.lock().unwrap().clone();
+        assert_eq!(1, messages.len());
+        let (channel, message, components) = &messages[0];
+        assert_eq!("555555555", channel);
+        assert_eq!("Reminder: this bet is past its settle date, is it time to close it out? <@695398918694895710> vs Woody, wager: $20 - Rangers repeat (settles: Oct  1, 2024)", message);
+        assert_eq!(
+            r#"[{"type":1,"components":[{"type":2,"style":1,"label":"Settle","custom_id":"remind:1:3","disabled":false}]}]"#,
+            serde_json::to_string(components).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn t35_send_settle_reminders_from_two_servers() {
+        let repo = test_settle_repo().await;
+        let mut wager = repo.get(2).await.unwrap();
+        wager.wager_id = 3;
+        wager.expected_settle_date = NaiveDate::from_ymd_opt(2024, 10, 1);
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        let server = |client: TestDiscordClient| async {
+            Application::new(
+                repo.clone(),
+                InMemoryAttendanceRepository::default(),
+                test_admin_repo().await,
+                test_whois_repo().await,
+                InMemPoolRepository::default(),
+                client,
+            )
+        };
+        let (first, second) = (server(client.clone()).await, server(client.clone()).await);
+        let today = NaiveDate::from_ymd_opt(2024, 10, 2).unwrap();
+
+        let (first, second) = tokio::join!(
+            first.send_settle_reminders(today),
+            second.send_settle_reminders(today)
+        );
+
+        assert_eq!(1, first.unwrap() + second.unwrap());
+        assert_eq!(1, client.created_messages.lock().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn t35_settle_reminder() {
+        let request = expect_request_from("dto_payloads/T35_settle_reminder.json");
+        let app = Application::new(
+            test_settle_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Closing: Harx vs Woody, wager: $20 - Rangers repeat","flags":64,"components":[{"type":1,"components":[{"type":2,"style":1,"label":"Harx won","custom_id":"outcome:1:offering:2","disabled":false},{"type":2,"style":1,"label":"Woody won","custom_id":"outcome:1:accepting:2","disabled":false},{"type":2,"style":1,"label":"No bet","custom_id":"outcome:1:nobet:2","disabled":false},{"type":2,"style":2,"label":"Cancel","custom_id":"outcome:1:cancel:2","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t34_mark_paid() {
+        let request = expect_request_from("dto_payloads/T34_mark_paid.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::AcceptingWon;
+        repo.insert(wager).await.unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> was paid: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            Some("original message".to_string()),
+            get_client_message(&client)
+        );
+        let wager = repo.get(109).await.unwrap();
+        assert_eq!(WagerStatus::Paid, wager.status);
+        assert_eq!(Some(WagerStatus::AcceptingWon), wager.paid_outcome);
+        assert_eq!(vec![WagerAction::Paid], recorded_actions(&repo, 109).await);
+    }
+
+    #[tokio::test]
+    async fn t34_confirm_paid() {
+        let request = expect_request_from("dto_payloads/T34_confirm_paid.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::AcceptingWon;
+        repo.insert(wager).await.unwrap();
+        repo.record_event(WagerEvent::new(
+            109,
+            Some(1050119194533961860.into()),
+            WagerAction::PaymentSent,
+            Some(WagerStatus::AcceptingWon),
+            WagerStatus::AcceptingWon,
+        ))
+        .await
+        .unwrap();
+        let client = TestDiscordClient::default();
+        set_client_message(&client, Some("original message".to_string()));
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            client.clone(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@695398918694895710> was paid: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat"}}"#;
+        assert_response(result, expected);
+        assert_eq!(None, get_client_message(&client));
+        assert_eq!(WagerStatus::Paid, repo.get(109).await.unwrap().status);
+        assert_eq!(
+            vec![WagerAction::PaymentSent, WagerAction::Paid],
+            recorded_actions(&repo, 109).await
+        );
+    }
+
+    #[tokio::test]
+    async fn t34_mark_paid_by_loser() {
+        let request = expect_request_from("dto_payloads/T34_mark_paid.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::OfferingWon;
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860>, <@695398918694895710> says they paid you: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","components":[{"type":1,"components":[{"type":2,"style":3,"label":"Confirm paid","custom_id":"payment:1:109:confirm","disabled":false}]}]}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::OfferingWon,
+            repo.get(109).await.unwrap().status
+        );
+        assert_eq!(
+            vec![WagerAction::PaymentSent],
+            recorded_actions(&repo, 109).await
+        );
+
+        let request = expect_request_from("dto_payloads/T34_mark_paid.json");
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Waiting on <@1050119194533961860> to confirm they were paid","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::OfferingWon,
+            repo.get(109).await.unwrap().status
+        );
+    }
+
+    #[tokio::test]
+    async fn t34_mark_paid_already_paid() {
+        let request = expect_request_from("dto_payloads/T34_mark_paid.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::Paid;
+        wager.paid_outcome = Some(WagerStatus::AcceptingWon);
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"This bet was already settled as Harx won and paid: <@1050119194533961860> vs <@695398918694895710>, wager: $20 - Rangers repeat","flags":64}}"#;
+        assert_response(result, expected);
+        assert!(recorded_actions(&repo, 109).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn t34_mark_paid_not_a_participant() {
+        let request = expect_request_from("dto_payloads/T34_mark_paid.json");
+        let repo = InMemWagerRepository::default();
+        let mut wager = pending_wager(1054147659289600060);
+        wager.status = WagerStatus::AcceptingWon;
+        repo.insert(wager).await.unwrap();
+        let app = Application::new(
+            repo.clone(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Only the people in this bet can mark it paid","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            WagerStatus::AcceptingWon,
+            repo.get(109).await.unwrap().status
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_not_an_owner() {
+        let request = expect_request_from("dto_payloads/T40_attendance_no_options.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r#"{"type":4,"data":{"content":"no attendance records found, is <@695398918694895710> in the league?","flags":64}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_no_options() {
+        let request = expect_request_from("dto_payloads/T40_attendance_no_options.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"2024 attendance through week 18\",\"type\":\"rich\",\"description\":\"<@695398918694895710>\\nRanks in the top quarter, outstanding attendance!\\n🤩\",\"fields\":[{\"name\":\"Weekly attendance\",\"value\":\"Attended 10 of 18 weeks\",\"inline\":false},{\"name\":\"Game attendance\",\"value\":\"Attended 30 games\",\"inline\":false},{\"name\":\"Attendance streak\",\"value\":\"10 weeks in a row now, longest run of 10 weeks this season\",\"inline\":false}]}],\"flags\":64}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_rsvp_reliability() {
+        let request = expect_request_from("dto_payloads/T40_attendance_no_options.json");
+        let attendance_repo = test_attendance_repo();
+        let dave: DiscordId = 695398918694895710.into();
+        attendance_repo.add_attendance(2024, 2, "2023-12-03", 431634941626023936.into());
+        for (date, message_id) in [
+            ("2023-11-26", "1311111111111111111"),
+            ("2023-12-03", "1322222222222222222"),
+            ("2023-12-10", "1333333333333333333"),
+        ] {
+            let post = RsvpPost {
+                season: 2024,
+                date: date.to_string(),
+                channel_id: "444444444".to_string(),
+                content: "Who's coming?".to_string(),
+                message_id: None,
+            };
+            attendance_repo.request_rsvp_post(&post).await.unwrap();
+            attendance_repo
+                .rsvp_posted(2024, date, message_id)
+                .await
+                .unwrap();
+            attendance_repo
+                .record_rsvp(message_id, &dave, true)
+                .await
+                .unwrap();
+        }
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            attendance_repo,
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"2024 attendance through week 18\",\"type\":\"rich\",\"description\":\"<@695398918694895710>\\nRanks in the top quarter, outstanding attendance!\\n🤩\",\"fields\":[{\"name\":\"Weekly attendance\",\"value\":\"Attended 10 of 18 weeks\",\"inline\":false},{\"name\":\"Game attendance\",\"value\":\"Attended 30 games\",\"inline\":false},{\"name\":\"Attendance streak\",\"value\":\"10 weeks in a row now, longest run of 10 weeks this season\",\"inline\":false},{\"name\":\"RSVP reliability\",\"value\":\"Showed up 1 of 2 times after saying they were coming\",\"inline\":false}]}],\"flags\":64}}";
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_manager() {
+        let request = expect_request_from("dto_payloads/T40_attendance_manager.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"2024 attendance through week 18\",\"type\":\"rich\",\"description\":\"<@1050119194533961860>\\nRanks in the top half, great record!\\n😀\",\"fields\":[{\"name\":\"Weekly attendance\",\"value\":\"Attended 7 of 18 weeks\",\"inline\":false},{\"name\":\"Game attendance\",\"value\":\"Attended 14 games\",\"inline\":false},{\"name\":\"Attendance streak\",\"value\":\"No current streak, longest run of 7 weeks this season\",\"inline\":false}]}]}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_week() {
+        let request = expect_request_from("dto_payloads/T40_attendance_week.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"2024 attendance for week 1\",\"type\":\"rich\",\"description\":\"\",\"fields\":[{\"name\":\"Thu, Nov 23\",\"value\":\"Dave\",\"inline\":false},{\"name\":\"Sun, Nov 26\",\"value\":\"Dave, Steve, Shawn\",\"inline\":false},{\"name\":\"Mon, Nov 27\",\"value\":\"Dave\",\"inline\":false}]}],\"flags\":64}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_current_week_from_settings() {
+        let request = expect_request_from("dto_payloads/T40_attendance_no_options.json");
+        let admin_repo = test_admin_repo().await;
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.ff_week = 12;
+        admin_repo.update(settings).await.unwrap();
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert!(found.contains(r#""title":"2024 attendance through week 12""#));
+        assert!(found.contains("Attended 10 of 12 weeks"));
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_past_season() {
+        let request = expect_request_from("dto_payloads/T40_attendance_season.json");
+        let attendance_repo = test_attendance_repo();
+        let dave: DiscordId = 695398918694895710.into();
+        for (week, date) in [(3, "2023-09-24"), (3, "2023-09-25"), (16, "2023-12-24")] {
+            attendance_repo.add_attendance(2023, week, date, dave.clone());
+        }
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            attendance_repo,
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            "{\"type\":4,\"data\":{\"embeds\":[{\"title\":\"2023 attendance through week 16\",\"type\":\"rich\",\"description\":\"<@695398918694895710>\\nRanks in the top quarter, outstanding attendance!\\n🤩\",\"fields\":[{\"name\":\"Weekly attendance\",\"value\":\"Attended 2 of 16 weeks\",\"inline\":false},{\"name\":\"Game attendance\",\"value\":\"Attended 3 games\",\"inline\":false},{\"name\":\"Attendance streak\",\"value\":\"2 weeks in a row now, longest run of 2 weeks this season\",\"inline\":false}]}],\"flags\":64}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_league() {
+        let request = expect_request_from("dto_payloads/T40_attendance_league.json");
+        let attendance_repo = InMemoryAttendanceRepository::default();
+        let teams: [(i64, &str, &[u8]); 5] = [
+            (695398918694895710, "Dave", &[1, 2, 3, 5, 6]),
+            (431634941626023936, "Steve", &[2, 3, 5, 6]),
+            (1048049562960539648, "Shawn", &[1, 2, 3, 6]),
+            (1050119194533961860, "Cisco", &[1]),
+            (885945439961108550, "Team 5", &[]),
+        ];
+        for (owner, name, weeks) in teams {
+            attendance_repo.add_team(owner.into(), name);
+            for week in weeks {
+                let date = NaiveDate::from_ymd_opt(2023, 9, 3).unwrap()
+                    + chrono::Days::new(7 * *week as u64);
+                attendance_repo.add_attendance(2023, *week, &date.to_string(), owner.into());
+            }
+        }
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            attendance_repo,
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"embeds":[{"title":"2023 league attendance through week 6","type":"rich","description":"1. <@695398918694895710> - 5 weeks, 5 games, streak 5 (best 5)\n2. <@431634941626023936> - 4 weeks, 4 games, streak 4 (best 4)\n2. <@1048049562960539648> - 4 weeks, 4 games, streak 1 (best 3)\n4. <@1050119194533961860> - 1 week, 1 game, streak 0 (best 1)\n5. <@885945439961108550> - 0 weeks, 0 games, streak 0 (best 0)"}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_season_not_recorded() {
+        let request = expect_request_from("dto_payloads/T40_attendance_season.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r#"{"type":4,"data":{"content":"No attendance recorded for the 2023 season","flags":64}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn t40_attendance_week_not_yet_arrived() {
+        let request = expect_request_from("dto_payloads/T40_attendance_week_19.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            "{\"type\":4,\"data\":{\"content\":\"No information for week 19\",\"flags\":64}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn t60_admin_help() {
+        let request = expect_request_from("dto_payloads/T60_admin_help.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        assert!(serde_json::to_value(&result).is_ok());
+        // let found = serde_json::to_string(&result).unwrap();
+        // assert_eq!(
+        //     found,
+        //     r##"{"type":4,"data":{"embeds":[{"title":"POG Admin help","type":"rich","description":"Admin-only commands","fields":[{"name":"Show bets","value":"`/welcome_channel` sets the expected landing page for new users.\n","inline":false}]}],"flags":64}}"##
+        // );
+    }
+
+    #[tokio::test]
+    async fn t60_admin_welcome() {
+        let request = expect_request_from("dto_payloads/T60_admin_welcome.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r##"{"type":4,"data":{"content":"welcome channel updated to: <#1165637665908080730>","flags":64}}"##
+        );
+    }
+
+    #[tokio::test]
+    async fn t60_admin_link_user() {
+        let request = expect_request_from("dto_payloads/T60_admin_link_user.json");
+        let repo = test_history_repo().await;
+        let app = Application::new(
+            repo.clone(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"linked 10 wagers placed against \"Woody\" to <@1050119194533961860>","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            Some(1050119194533961860.into()),
+            repo.get(3).await.unwrap().resolved_accepting_user
+        );
+    }
+
+    #[tokio::test]
+    async fn t60_admin_reminder_days() {
+        let request = expect_request_from("dto_payloads/T60_admin_reminder_days.json");
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"overdue bets will be reminded every 3 days","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(3, admin_repo.get().await.unwrap().reminder_days);
+    }
+
+    #[tokio::test]
+    async fn t60_admin_offer_hours() {
+        let request = expect_request_from("dto_payloads/T60_admin_offer_hours.json");
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected =
+            r#"{"type":4,"data":{"content":"open offers will expire after 48 hours","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(48, admin_repo.get().await.unwrap().offer_hours);
+    }
+
+    #[tokio::test]
+    async fn t60_admin_week() {
+        let request = expect_request_from("dto_payloads/T60_admin_week.json");
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected =
+            r#"{"type":4,"data":{"content":"the current week is now week 12","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(12, admin_repo.get().await.unwrap().ff_week);
+    }
+
+    #[tokio::test]
+    async fn t60_admin_rsvp_day() {
+        let request = expect_request_from("dto_payloads/T60_admin_rsvp_day.json");
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"every Thu the rsvp channel will be asked who is coming on Sunday","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(4, admin_repo.get().await.unwrap().rsvp_day);
+    }
+
+    #[tokio::test]
+    async fn t60_admin_watch_window() {
+        let request = expect_request_from("dto_payloads/T60_admin_watch_window.json");
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"anyone in the watch party channel on Mon, Thu, Sun from 13:00 to 24:00 will be marked as attending","flags":64}}"#;
+        assert_response(result, expected);
+        let settings = admin_repo.get().await.unwrap();
+        assert_eq!(vec![1, 4, 7], settings.watch_days);
+        assert_eq!((13, 24), (settings.watch_start, settings.watch_end));
+    }
+
+    #[tokio::test]
+    async fn t60_admin_max_stake() {
+        let request = expect_request_from("dto_payloads/T60_admin_max_stake.json");
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"the most anyone can put up on a bet is now $50","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            Some("$50".to_string()),
+            admin_repo.get().await.unwrap().max_stake
+        );
+    }
+
+    #[tokio::test]
+    async fn t60_admin_limit_exempt() {
+        let admin_repo = test_admin_repo().await;
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let request = expect_request_from("dto_payloads/T60_admin_limit_exempt.json");
+        let result = app.request_handler(request).await.unwrap();
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860> is now exempt from betting limits","flags":64}}"#;
+        assert_response(result, expected);
+        assert_eq!(
+            vec!["1050119194533961860".to_string()],
+            admin_repo.get().await.unwrap().limit_exempt
+        );
+
+        let request = expect_request_from("dto_payloads/T60_admin_limit_exempt.json");
+        let result = app.request_handler(request).await.unwrap();
+        let expected = r#"{"type":4,"data":{"content":"<@1050119194533961860> is no longer exempt from betting limits","flags":64}}"#;
+        assert_response(result, expected);
+        assert!(admin_repo.get().await.unwrap().limit_exempt.is_empty());
+    }
+
+    #[tokio::test]
+    async fn t60_set_user() {
+        let request = expect_request_from("dto_payloads/T60_admin_set_user.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let found = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            found,
+            r##"{"type":9,"data":{"custom_id":"setuser:1:1166787343743725609","title":"Add a user","components":[{"type":1,"components":[{"type":4,"custom_id":"human_name","label":"Human name?","placeholder":"Neil N. Bob","style":1,"max_length":30,"required":false}]},{"type":1,"components":[{"type":4,"custom_id":"hash_name","label":"Hash name?","placeholder":"Xena, the Warrior Princess","style":1,"max_length":30,"required":false}]}]}}"##
+        );
+    }
+
+    #[tokio::test]
+    async fn t61_admin_set_user_modal() {
+        let request = expect_request_from("dto_payloads/T61_admin_set_user_modal.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
             test_attendance_repo(),
             test_admin_repo().await,
             test_whois_repo().await,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
@@ -618,14 +3295,133 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn t62_admin_attendance() {
+        let request = expect_request_from("dto_payloads/T62_admin_attendance.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Who attended on Sun, Nov 26 (week 1)? The selection replaces what was recorded for the day, clear it to remove the day.","flags":64,"components":[{"type":1,"components":[{"type":5,"custom_id":"attendance:1:2024:1:2023-11-26","placeholder":"Who attended?","min_values":0,"max_values":25,"default_values":[{"id":"431634941626023936","type":"user"},{"id":"695398918694895710","type":"user"},{"id":"1048049562960539648","type":"user"}]}]}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t62_admin_attendance_select() {
+        let request = expect_request_from("dto_payloads/T62_admin_attendance_select.json");
+        let attendance_repo = test_attendance_repo();
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            attendance_repo.clone(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":7,"data":{"content":"Recorded week 1 attendance for Sun, Nov 26: <@695398918694895710>, <@1050119194533961860>\nNot in the league, so not recorded: <@1336020615089356801>","flags":64,"components":[{"type":1,"components":[{"type":5,"custom_id":"attendance:1:2024:1:2023-11-26","placeholder":"Who attended?","min_values":0,"max_values":25,"default_values":[{"id":"695398918694895710","type":"user"},{"id":"1050119194533961860","type":"user"}]}]}]}}"#;
+        assert_response(result, expected);
+        let attended = attendance_repo
+            .date_attendance(2024, "2023-11-26")
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![
+                DiscordId::from(695398918694895710),
+                DiscordId::from(1050119194533961860)
+            ],
+            attended
+        );
+    }
+
+    #[tokio::test]
+    async fn t63_admin_rsvp() {
+        let request = expect_request_from("dto_payloads/T63_admin_rsvp.json");
+        let attendance_repo = test_attendance_repo();
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            attendance_repo.clone(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"Asking who is coming on Sun, Nov 24 in <#444444444>","flags":64}}"#;
+        assert_response(result, expected);
+        let pending = attendance_repo.pending_rsvp_posts().await.unwrap();
+        assert_eq!(
+            vec![RsvpPost {
+                season: 2024,
+                date: "2024-11-24".to_string(),
+                channel_id: "444444444".to_string(),
+                content: format!("Who's coming to watch on Sun, Nov 24? React with {} if you'll be there or \u{274c} if you can't make it.", RSVP_YES),
+                message_id: None,
+            }],
+            pending
+        );
+
+        let request = expect_request_from("dto_payloads/T63_admin_rsvp.json");
+        let result = app.request_handler(request).await.unwrap();
+        let expected = r#"{"type":4,"data":{"content":"Sun, Nov 24 already has a post asking who is coming","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t63_send_rsvp_post() {
+        let admin_repo = test_admin_repo().await;
+        let attendance_repo = test_attendance_repo();
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            attendance_repo.clone(),
+            admin_repo.clone(),
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+        let thursday = NaiveDate::from_ymd_opt(2024, 11, 21).unwrap();
+
+        assert!(!app.send_rsvp_post(thursday).await.unwrap());
+        let mut settings = admin_repo.get().await.unwrap();
+        settings.rsvp_day = 4;
+        admin_repo.update(settings).await.unwrap();
+        assert!(!app
+            .send_rsvp_post(thursday.succ_opt().unwrap())
+            .await
+            .unwrap());
+        assert!(app.send_rsvp_post(thursday).await.unwrap());
+        assert!(!app.send_rsvp_post(thursday).await.unwrap());
+
+        let pending = attendance_repo.pending_rsvp_posts().await.unwrap();
+        assert_eq!(1, pending.len());
+        assert_eq!("2024-11-24", pending[0].date);
+    }
+
     #[tokio::test]
     async fn t70_whois() {
         let request = expect_request_from("dto_payloads/T70_whois.json");
+        let whois_repo = test_whois_repo().await;
+        whois_repo
+            .add(431634941626023936, "Steve", "SB")
+            .await
+            .unwrap();
         let app = Application::new(
             InMemWagerRepository::default(),
             test_attendance_repo(),
             test_admin_repo().await,
-            test_whois_repo().await,
+            whois_repo,
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
@@ -634,8 +3430,99 @@ mod test {
         let found = serde_json::to_string(&result).unwrap();
         assert_eq!(
             found,
-            r###"{"type":4,"data":{"content":"_User lookup_\n<@431634941626023936>\nHuman name: Dave\nHash name: FBS","flags":64}}"###
+            r###"{"type":4,"data":{"content":"_User lookup_\n<@431634941626023936>\nHuman name: Steve\nHash name: SB","flags":64}}"###
+        );
+    }
+
+    #[tokio::test]
+    async fn t70_whois_by_name() {
+        let request = expect_request_from("dto_payloads/T70_whois_by_name.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":4,"data":{"content":"_User lookup_\n<@695398918694895710>\nHuman name: Dave\nHash name: FBS","flags":64}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t80_autocomplete_settle() {
+        let request = expect_request_from("dto_payloads/T80_autocomplete_settle.json");
+        let app = Application::new(
+            test_settle_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":8,"data":{"choices":[{"name":"Harx vs Woody, wager: $20 - Rangers repeat","value":"2"}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t80_autocomplete_edit_bet() {
+        let request = expect_request_from("dto_payloads/T80_autocomplete_edit_bet.json");
+        let app = Application::new(
+            test_settle_repo().await,
+            InMemoryAttendanceRepository::default(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":8,"data":{"choices":[{"name":"Harx vs Woody, wager: $20 - Rangers repeat","value":"2"}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t80_autocomplete_week() {
+        let request = expect_request_from("dto_payloads/T80_autocomplete_week.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
+        );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected = r#"{"type":8,"data":{"choices":[{"name":"Week 1","value":"1"},{"name":"Week 10","value":"10"}]}}"#;
+        assert_response(result, expected);
+    }
+
+    #[tokio::test]
+    async fn t80_autocomplete_whois() {
+        let request = expect_request_from("dto_payloads/T80_autocomplete_whois.json");
+        let app = Application::new(
+            InMemWagerRepository::default(),
+            test_attendance_repo(),
+            test_admin_repo().await,
+            test_whois_repo().await,
+            InMemPoolRepository::default(),
+            TestDiscordClient::default(),
         );
+
+        let result = app.request_handler(request).await.unwrap();
+
+        let expected =
+            r#"{"type":8,"data":{"choices":[{"name":"Dave (FBS)","value":"695398918694895710"}]}}"#;
+        assert_response(result, expected);
     }
 
     #[tokio::test]
@@ -646,6 +3533,7 @@ mod test {
             test_attendance_repo(),
             test_admin_repo().await,
             InMemWhoisRepository::default(),
+            InMemPoolRepository::default(),
             TestDiscordClient::default(),
         );
 
@@ -666,41 +3554,205 @@ mod test {
         client.message.lock().unwrap().clone()
     }
 
+    fn pending_wager(accepting_user: i64) -> Wager {
+        Wager {
+            wager_id: 109,
+            time: "".to_string(),
+            resolved_offering_user: Some(1050119194533961860.into()),
+            resolved_accepting_user: Some(accepting_user.into()),
+            outcome: "Rangers repeat".to_string(),
+            ..Wager::fixture("Cisco", "Harx", WagerStatus::AwaitingAcceptance)
+        }
+    }
+
+    fn open_wager() -> Wager {
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::Open;
+        wager.expected_settle_date = NaiveDate::from_ymd_opt(2024, 9, 15);
+        wager
+    }
+
+    fn open_offer(offering_user: i64) -> Wager {
+        let mut wager = pending_wager(695398918694895710);
+        wager.time = chrono::Utc::now().to_rfc3339();
+        wager.resolved_offering_user = Some(offering_user.into());
+        wager.accepting = OPEN_OFFER_ACCEPTING.to_string();
+        wager.resolved_accepting_user = None;
+        wager.status = WagerStatus::OpenOffer;
+        wager
+    }
+
+    async fn recorded_actions(repo: &InMemWagerRepository, wager_id: i32) -> Vec<WagerAction> {
+        repo.events(wager_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|event| event.action)
+            .collect()
+    }
+
+    async fn propose_amendment(repo: &InMemWagerRepository, proposed_by: i64) -> u32 {
+        let wager = repo.get(109).await.unwrap();
+        let mut revised = wager.terms();
+        revised.wager = "$25".into();
+        repo.propose_revision(WagerRevision {
+            revision_id: 0,
+            wager_id: 109,
+            time: "".to_string(),
+            proposed_by: proposed_by.into(),
+            status: RevisionStatus::Proposed,
+            previous: wager.terms(),
+            revised,
+        })
+        .await
+        .unwrap()
+        .unwrap()
+    }
+
+    fn proposed_wager(proposed_by: i64) -> Wager {
+        let mut wager = pending_wager(695398918694895710);
+        wager.status = WagerStatus::SettlementProposed;
+        wager.proposal = Some(SettlementProposal {
+            outcome: WagerStatus::AcceptingWon,
+            proposed_by: proposed_by.into(),
+        });
+        wager
+    }
+
+    async fn test_history_repo() -> InMemWagerRepository {
+        let repo = InMemWagerRepository::default();
+        for wager_id in 1..=10 {
+            let status = match wager_id % 3 {
+                0 => WagerStatus::AcceptingWon,
+                1 => WagerStatus::NoBet,
+                _ => WagerStatus::OfferingWon,
+            };
+            repo.insert(Wager {
+                wager_id,
+                time: "".to_string(),
+                resolved_offering_user: Some(695398918694895710.into()),
+                wager: format!("${}", wager_id).as_str().into(),
+                outcome: format!("Game {}", wager_id),
+                ..Wager::fixture("Harx", "Woody", status)
+            })
+            .await
+            .unwrap();
+        }
+        repo
+    }
+
+    async fn test_ledger_repo() -> InMemWagerRepository {
+        let repo = InMemWagerRepository::default();
+        for (wager_id, accepting_user, stake, status) in [
+            (1, 1050119194533961860, "$20", WagerStatus::OfferingWon),
+            (2, 1050119194533961860, "$5", WagerStatus::AcceptingWon),
+            (
+                3,
+                1054147659289600060,
+                "2.50 quid",
+                WagerStatus::AcceptingWon,
+            ),
+        ] {
+            repo.insert(Wager {
+                wager_id,
+                time: "".to_string(),
+                resolved_offering_user: Some(695398918694895710.into()),
+                resolved_accepting_user: Some(accepting_user.into()),
+                wager: stake.into(),
+                outcome: "Rangers repeat".to_string(),
+                ..Wager::fixture("Harx", "Cisco", status)
+            })
+            .await
+            .unwrap();
+        }
+        repo
+    }
+
     fn test_attendance_repo() -> InMemoryAttendanceRepository {
-        let combined_attendance = AttendanceRecords(vec![
-            (695398918694895710, 10, 30).into(),
-            (431634941626023936, 10, 21).into(),
-            (1048049562960539648, 7, 15).into(),
-            (1050119194533961860, 7, 14).into(),
-            (1054147659289600060, 7, 11).into(),
-            (156425668270358529, 6, 9).into(),
-            (689977564202401792, 6, 9).into(),
-            (1045764168210448384, 5, 8).into(),
-            (1045795671489380354, 4, 8).into(),
-            (1046484657249718414, 4, 5).into(),
-            (460972684986023937, 2, 4).into(),
-            (885945439961108550, 0, 0).into(),
-        ]);
-        let mut weekly_attendance: Vec<(String, Vec<String>)> = Vec::default();
-        weekly_attendance.push(("2023-11-23".to_string(), vec!["Dave".to_string()]));
-        weekly_attendance.push((
-            "2023-11-26".to_string(),
-            vec!["Dave".to_string(), "Steve".to_string(), "Shawn".to_string()],
-        ));
-        weekly_attendance.push(("2023-11-27".to_string(), vec!["Dave".to_string()]));
-        let weekly_attendance = weekly_attendance.into();
-        InMemoryAttendanceRepository {
-            combined_attendance,
-            weekly_attendance,
+        let repo = InMemoryAttendanceRepository::default();
+        let dave: DiscordId = 695398918694895710.into();
+        let week_one = [
+            ("2023-11-23", dave.clone()),
+            ("2023-11-26", dave.clone()),
+            ("2023-11-26", 431634941626023936.into()),
+            ("2023-11-26", 1048049562960539648.into()),
+            ("2023-11-27", dave),
+        ];
+        for (date, owner) in week_one.iter() {
+            repo.add_attendance(2024, 1, date, owner.clone());
+        }
+        // owner, name, weeks and games attended, spread over the weeks after week one
+        let teams: [(i64, &str, u8, u8); 12] = [
+            (695398918694895710, "Dave", 10, 30),
+            (431634941626023936, "Steve", 10, 21),
+            (1048049562960539648, "Shawn", 7, 15),
+            (1050119194533961860, "Cisco", 7, 14),
+            (1054147659289600060, "Woody", 7, 11),
+            (156425668270358529, "Team 6", 6, 9),
+            (689977564202401792, "Team 7", 6, 9),
+            (1045764168210448384, "Team 8", 5, 8),
+            (1045795671489380354, "Team 9", 4, 8),
+            (1046484657249718414, "Team 10", 4, 5),
+            (460972684986023937, "Team 11", 2, 4),
+            (885945439961108550, "Team 12", 0, 0),
+        ];
+        for (owner, name, weeks, games) in teams {
+            let owner: DiscordId = owner.into();
+            repo.add_team(owner.clone(), name);
+            let attended = week_one.iter().filter(|(_, id)| id == &owner).count() as u8;
+            let weeks = weeks - attended.min(1);
+            let games = games - attended;
+            for i in 0..weeks {
+                let week = i + 2;
+                let kickoff = NaiveDate::from_ymd_opt(2023, 9, 7).unwrap()
+                    + chrono::Days::new(7 * (week as u64 - 1));
+                let count = games / weeks + u8::from(i < games % weeks);
+                for day in [0, 3, 4].iter().take(count as usize) {
+                    let date = kickoff + chrono::Days::new(*day);
+                    repo.add_attendance(2024, week, &date.to_string(), owner.clone());
+                }
+            }
+        }
+        repo
+    }
+
+    async fn test_settle_repo() -> InMemWagerRepository {
+        let repo = InMemWagerRepository::default();
+        for (wager_id, outcome) in [(1, "Raiders win out"), (2, "Rangers repeat")] {
+            repo.insert(Wager {
+                wager_id,
+                time: "".to_string(),
+                resolved_offering_user: Some(695398918694895710.into()),
+                outcome: outcome.to_string(),
+                ..Wager::fixture("Harx", "Woody", WagerStatus::Open)
+            })
+            .await
+            .unwrap();
         }
+        repo
     }
 
     async fn test_admin_repo() -> InMemAdminRepository {
         let repo = InMemAdminRepository::default();
         repo.update(AdminSettings {
             welcome_channel: "123456789".to_string(),
+            admin_channel: "987654321".to_string(),
+            betting_channel: "555555555".to_string(),
+            reminder_days: 3,
+            offer_hours: 24,
             ff_year: 2024,
             ff_week: 18,
+            max_stake: None,
+            max_open_wagers: 0,
+            max_exposure: None,
+            limit_exempt: vec![],
+            rsvp_channel: "444444444".to_string(),
+            rsvp_day: 0,
+            watch_channel: "333333333".to_string(),
+            watch_days: vec![7],
+            watch_start: 13,
+            watch_end: 24,
+            watch_minutes: 30,
         })
         .await
         .unwrap();
@@ -2,14 +2,28 @@ pub(crate) mod app;
 mod parse_date;
 mod t10_initiate_bet;
 mod t11_add_wager;
+mod t12_accept_wager;
+mod t13_edit_wager;
+mod t14_open_offer;
+mod t15_pool_bet;
 mod t20_list_bets;
+mod t21_debts;
+mod t22_ledger;
+mod t23_bet_stats;
+mod t24_bet_history;
 mod t30_settle_bet;
 mod t31_settle_bet;
 mod t32_settle_bet;
+mod t33_confirm_settlement;
+mod t34_mark_paid;
+mod t35_settle_reminders;
 mod t40_attendance;
 mod t50_help;
 mod t60_admin;
 mod t61_admin_set_user;
+mod t62_admin_attendance;
+mod t63_admin_rsvp;
 mod t70_whois;
+mod t80_autocomplete;
 
 pub use app::Application;
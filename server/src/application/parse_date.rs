@@ -1,66 +1,469 @@
-use chrono::{Datelike, Duration, NaiveDate, Utc};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use pog_common::repos::AdminSettings;
+use pog_common::wager::DateRange;
 
-pub fn parse_date(value: &str) -> Option<NaiveDate> {
+pub const REGULAR_SEASON_WEEKS: u32 = 18;
+const SUPER_BOWL_WEEK: u32 = 23;
+
+pub fn parse_date(
+    value: &str,
+    today: NaiveDate,
+    settings: &AdminSettings,
+) -> Result<Option<NaiveDate>, String> {
+    let original = value.trim();
+    let value = normalize(value);
     if value.is_empty() {
-        return None;
+        return Ok(None);
+    }
+    let words: Vec<&str> = value.split(' ').collect();
+    if let Some(week) = parse_nfl_week(&words, settings) {
+        return match nfl_week_sunday(settings.current_season(today) as i32, week) {
+            Some(date) if date >= today => Ok(Some(date)),
+            _ if week == SUPER_BOWL_WEEK => {
+                Err("The Super Bowl has already been played this season".to_string())
+            }
+            _ => Err(format!(
+                "Week {} has already been played this season, pick a later week",
+                week
+            )),
+        };
+    }
+    parse_numeric_date(&value, today)
+        .or_else(|| parse_relative_date(&words, today))
+        .or_else(|| parse_weekday(&words, today))
+        .or_else(|| parse_month_date(&words, today))
+        .map(Some)
+        .ok_or(format!(
+            "Unable to understand the settle date '{}', try `11/24`, `Feb 9`, `next sunday`, `in 3 days`, `week 14` or `end of season`",
+            original
+        ))
+}
+
+pub fn parse_full_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .ok()
+}
+
+pub fn parse_date_range(
+    from: Option<&String>,
+    to: Option<&String>,
+) -> Result<(DateRange, String), String> {
+    let parse = |value: &String| {
+        parse_full_date(value).ok_or(format!(
+            "Unable to understand the date '{}', use mm/dd/yyyy",
+            value
+        ))
+    };
+    let range = DateRange {
+        from: from.map(parse).transpose()?,
+        to: to.map(parse).transpose()?,
+    };
+    let label = date_range_label(&range);
+    Ok((range, label))
+}
+
+pub fn date_range_label(range: &DateRange) -> String {
+    match (range.from, range.to) {
+        (Some(from), Some(to)) => format!(" from {} to {}", from, to),
+        (Some(from), None) => format!(" since {}", from),
+        (None, Some(to)) => format!(" until {}", to),
+        (None, None) => "".to_string(),
+    }
+}
+
+// for dates already gone by, so a missing year or a weekday means the most recent one
+pub fn parse_past_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let value = normalize(value);
+    match value.as_str() {
+        "today" | "tonight" => return Some(today),
+        "yesterday" => return today.pred_opt(),
+        _ => {}
+    }
+    if let Ok(weekday) = value.parse::<Weekday>() {
+        let days_back =
+            (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        return today.checked_sub_signed(Duration::days(days_back as i64));
     }
-    if let Ok(date) = NaiveDate::parse_from_str(value, "%m/%d/%Y") {
+    if let Some(date) = parse_full_date(&value) {
         return Some(date);
     }
-    let value_with_year = format!("{}/{}", value, Utc::now().year());
-    if let Ok(date) = NaiveDate::parse_from_str(value_with_year.as_str(), "%m/%d/%Y") {
+    let date =
+        NaiveDate::parse_from_str(&format!("{}/{}", value, today.year()), "%m/%d/%Y").ok()?;
+    match date > today {
+        true => date.checked_sub_months(Months::new(12)),
+        false => Some(date),
+    }
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .replace([',', '.'], " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn parse_numeric_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+    {
         return Some(date);
     }
-    try_guessing_year(value, Utc::now().date_naive())
-}
-fn try_guessing_year(value: &str, today: NaiveDate) -> Option<NaiveDate> {
-    let value_with_year = format!("{}/{}", value, today.year());
-    match NaiveDate::parse_from_str(value_with_year.as_str(), "%m/%d/%Y") {
-        Ok(date) => {
-            let delta = date - today;
-            if delta > Duration::zero() {
-                Some(date)
-            } else {
-                Some(date + chrono::Months::new(12))
+    let (month, day) = value.split_once('/')?;
+    upcoming_date(month.parse().ok()?, day.parse().ok()?, today)
+}
+
+// a date without a year, `2/9` or `Feb 9`, is the next one on or after today
+fn upcoming_date(month: u32, day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    match NaiveDate::from_ymd_opt(today.year(), month, day) {
+        Some(date) if date >= today => Some(date),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+    }
+}
+
+fn parse_relative_date(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    match words {
+        ["today"] | ["tonight"] => Some(today),
+        ["tomorrow"] => today.succ_opt(),
+        ["end", "of", "the", period] | ["end", "of", period] => match *period {
+            "week" => Some(upcoming(Weekday::Sun, today)),
+            "month" => last_day_of_month(today.year(), today.month()),
+            "year" => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+            month => {
+                let month = parse_month(month)?;
+                last_day_of_month(today.year(), month)
+                    .filter(|end| *end >= today)
+                    .or_else(|| last_day_of_month(today.year() + 1, month))
+            }
+        },
+        ["in", count, unit] | [count, unit] => {
+            let count = parse_count(count)?;
+            match unit.trim_end_matches('s') {
+                "day" => today.checked_add_signed(Duration::days(count as i64)),
+                "week" => today.checked_add_signed(Duration::weeks(count as i64)),
+                "month" => today.checked_add_months(Months::new(count)),
+                _ => None,
             }
         }
-        Err(_) => {
-            // metric(|mut m| m.count("parse_date_failure"));
-            None
-        }
+        _ => None,
+    }
+}
+
+fn parse_count(value: &str) -> Option<u32> {
+    match value {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        "four" => Some(4),
+        "five" => Some(5),
+        "six" => Some(6),
+        "seven" => Some(7),
+        _ => value.parse().ok(),
     }
 }
 
+fn parse_weekday(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    match words {
+        [day] | ["this", day] => Some(upcoming(parse_weekday_name(day)?, today)),
+        ["next", day] => Some(upcoming(parse_weekday_name(day)?, today.succ_opt()?)),
+        _ => None,
+    }
+}
+
+pub fn parse_weekday_name(value: &str) -> Option<Weekday> {
+    match value {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// the first given weekday on or after the from date
+pub fn upcoming(weekday: Weekday, from: NaiveDate) -> NaiveDate {
+    let days = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Duration::days(days as i64)
+}
+
+fn parse_month_date(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    let (first, second, year) = match words {
+        [first, second] => (first, second, None),
+        [first, second, year] => (first, second, Some(year.parse().ok()?)),
+        _ => return None,
+    };
+    let (month, day) = match parse_month(first) {
+        Some(month) => (month, parse_day(second)?),
+        None => (parse_month(second)?, parse_day(first)?),
+    };
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => upcoming_date(month, day, today),
+    }
+}
+
+fn parse_month(value: &str) -> Option<u32> {
+    let months = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    if value.len() < 3 {
+        return None;
+    }
+    months
+        .iter()
+        .position(|month| month.starts_with(value))
+        .map(|index| index as u32 + 1)
+}
+
+fn parse_day(value: &str) -> Option<u32> {
+    value
+        .trim_end_matches("st")
+        .trim_end_matches("nd")
+        .trim_end_matches("rd")
+        .trim_end_matches("th")
+        .parse()
+        .ok()
+}
+
+fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, 1)?
+        .checked_add_months(Months::new(1))?
+        .pred_opt()
+}
+
+fn parse_nfl_week(words: &[&str], settings: &AdminSettings) -> Option<u32> {
+    let week = match words {
+        ["week", week] | ["wk", week] | ["nfl", "week", week] => week.parse().ok()?,
+        ["this", "week"] => settings.ff_week as u32,
+        ["next", "week"] => settings.ff_week as u32 + 1,
+        ["end", "of", "season"] | ["end", "of", "the", "season"] => REGULAR_SEASON_WEEKS,
+        ["super", "bowl"] => SUPER_BOWL_WEEK,
+        _ => return None,
+    };
+    match week == 0 || (week > REGULAR_SEASON_WEEKS && week != SUPER_BOWL_WEEK) {
+        true => None,
+        false => Some(week),
+    }
+}
+
+// week 1 Sunday follows Labor Day, the first Monday in September
+fn nfl_week_sunday(season: i32, week: u32) -> Option<NaiveDate> {
+    let labor_day = upcoming(Weekday::Mon, NaiveDate::from_ymd_opt(season, 9, 1)?);
+    Some(labor_day + Duration::days(6) + Duration::weeks(week as i64 - 1))
+}
+
 #[test]
 fn test_date() {
+    let today: NaiveDate = "2024-10-16".parse().unwrap();
+    let settings = AdminSettings::new(String::new(), 2024, 7);
+    assert_eq!(
+        Ok(NaiveDate::from_ymd_opt(2024, 11, 24)),
+        parse_date("11/24/2024", today, &settings)
+    );
+    assert_eq!(
+        Ok(NaiveDate::from_ymd_opt(2024, 11, 24)),
+        parse_date("11/24", today, &settings)
+    );
+    assert_eq!(
+        Ok(NaiveDate::from_ymd_opt(2025, 1, 5)),
+        parse_date("1/5", today, &settings)
+    );
+    assert_eq!(
+        Ok(NaiveDate::from_ymd_opt(2025, 3, 1)),
+        parse_date("2025-03-01", today, &settings)
+    );
+    assert_eq!(Ok(None), parse_date("  ", today, &settings));
+}
+
+#[test]
+fn test_relative_date() {
+    let today: NaiveDate = "2024-10-16".parse().unwrap();
+    let settings = AdminSettings::new(String::new(), 2024, 7);
+    let parse = |value| parse_date(value, today, &settings).unwrap().unwrap();
+    assert_eq!(today, parse("Today"));
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 17).unwrap(),
+        parse("tomorrow")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 19).unwrap(),
+        parse("in 3 days")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 30).unwrap(),
+        parse("two weeks")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 11, 16).unwrap(),
+        parse("in a month")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 31).unwrap(),
+        parse("end of month")
+    );
     assert_eq!(
-        NaiveDate::from_ymd_opt(2024, 11, 24),
-        parse_date("11/24/2024")
+        NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        parse("end of the year")
     );
     assert_eq!(
-        NaiveDate::from_ymd_opt(2024, 11, 24),
-        parse_date("11/24/2024")
+        NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+        parse("end of feb")
     );
 }
 
 #[test]
-fn test_guessing_date_wrong_year() {
-    let today: NaiveDate = "2023-11-23".parse().unwrap();
+fn test_weekday_date() {
+    // a Wednesday
+    let today: NaiveDate = "2024-10-16".parse().unwrap();
+    let settings = AdminSettings::new(String::new(), 2024, 7);
+    let parse = |value| parse_date(value, today, &settings).unwrap().unwrap();
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 20).unwrap(),
+        parse("sunday")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 20).unwrap(),
+        parse("next Sunday")
+    );
+    assert_eq!(today, parse("wed"));
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 23).unwrap(),
+        parse("next wednesday")
+    );
     assert_eq!(
-        NaiveDate::from_ymd_opt(2024, 11, 23),
-        try_guessing_year("11/23", today)
+        NaiveDate::from_ymd_opt(2024, 10, 18).unwrap(),
+        parse("this friday")
     );
 }
 
 #[test]
-fn test_guessing_date() {
-    let today: NaiveDate = "2023-11-23".parse().unwrap();
+fn test_month_date() {
+    let today: NaiveDate = "2024-10-16".parse().unwrap();
+    let settings = AdminSettings::new(String::new(), 2024, 7);
+    let parse = |value| parse_date(value, today, &settings).unwrap().unwrap();
+    assert_eq!(NaiveDate::from_ymd_opt(2025, 2, 9).unwrap(), parse("Feb 9"));
+    assert_eq!(parse("2/9"), parse("9th February"));
+    assert_eq!(parse("Super Bowl"), parse("Feb 9"));
+    assert_eq!(today, parse("Oct 16"));
     assert_eq!(
-        NaiveDate::from_ymd_opt(2023, 11, 24),
-        try_guessing_year("11/24", today)
+        NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+        parse("December 25th")
     );
     assert_eq!(
-        NaiveDate::from_ymd_opt(2023, 11, 25),
-        try_guessing_year("11/25", today)
+        NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+        parse("Sept 1, 2026")
     );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 11, 3).unwrap(),
+        parse("3 nov 2024")
+    );
+}
+
+#[test]
+fn test_nfl_week_date() {
+    let today: NaiveDate = "2024-10-16".parse().unwrap();
+    let settings = AdminSettings::new(String::new(), 2024, 7);
+    let parse = |value| parse_date(value, today, &settings).unwrap().unwrap();
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 12, 8).unwrap(),
+        parse("week 14")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 20).unwrap(),
+        parse("this week")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 10, 27).unwrap(),
+        parse("next week")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(),
+        parse("end of season")
+    );
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2025, 2, 9).unwrap(),
+        parse("Super Bowl")
+    );
+    assert_eq!(
+        Err("Week 3 has already been played this season, pick a later week".to_string()),
+        parse_date("week 3", today, &settings)
+    );
+}
+
+#[test]
+fn test_nfl_week_date_following_calendar() {
+    let settings = AdminSettings::default();
+    assert_eq!(0, settings.ff_year);
+    let parse = |value, today: &str| {
+        parse_date(value, today.parse().unwrap(), &settings)
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2024, 12, 8).unwrap(),
+        parse("week 14", "2024-10-16")
+    );
+    // January still belongs to the season that started the September before
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2025, 2, 9).unwrap(),
+        parse("super bowl", "2025-01-20")
+    );
+}
+
+#[test]
+fn test_unparsed_date() {
+    let today: NaiveDate = "2024-10-16".parse().unwrap();
+    let settings = AdminSettings::new(String::new(), 2024, 7);
+    assert_eq!(
+        Err("Unable to understand the settle date 'Whenever', try `11/24`, `Feb 9`, `next sunday`, `in 3 days`, `week 14` or `end of season`".to_string()),
+        parse_date("Whenever", today, &settings)
+    );
+    assert!(parse_date("Feb 30 2025", today, &settings).is_err());
+    assert!(parse_date("week 19", today, &settings).is_err());
+}
+
+#[test]
+fn test_parse_date_range() {
+    let from = Some("10/01/2024".to_string());
+    let to = Some("2024-12-31".to_string());
+    let (range, label) = parse_date_range(from.as_ref(), to.as_ref()).unwrap();
+    assert_eq!(NaiveDate::from_ymd_opt(2024, 10, 1), range.from);
+    assert_eq!(NaiveDate::from_ymd_opt(2024, 12, 31), range.to);
+    assert_eq!(" from 2024-10-01 to 2024-12-31", label);
+    assert_eq!(
+        Err("Unable to understand the date 'someday', use mm/dd/yyyy".to_string()),
+        parse_date_range(Some(&"someday".to_string()), None)
+    );
+}
+
+#[test]
+fn test_parse_past_date() {
+    let today = NaiveDate::from_ymd_opt(2025, 1, 8).unwrap();
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+    assert_eq!(date(2025, 1, 8), parse_past_date("Today", today));
+    assert_eq!(date(2025, 1, 7), parse_past_date("yesterday", today));
+    assert_eq!(date(2025, 1, 5), parse_past_date("sunday", today));
+    assert_eq!(date(2025, 1, 8), parse_past_date("wed", today));
+    assert_eq!(date(2024, 12, 29), parse_past_date("12/29", today));
+    assert_eq!(date(2025, 1, 5), parse_past_date("1/5", today));
+    assert_eq!(date(2024, 10, 20), parse_past_date("2024-10-20", today));
+    assert_eq!(date(2024, 10, 20), parse_past_date("10/20/2024", today));
+    assert_eq!(None, parse_past_date("next week", today));
 }
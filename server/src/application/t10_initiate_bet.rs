@@ -9,15 +9,20 @@ use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
 use crate::ADD_BET_PLACEHOLDER_TEXT;
-use pog_common::discord_id::{combine_user_payload, DiscordId};
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{Wager, WagerTerms};
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn initiate_bet(
@@ -28,11 +33,11 @@ where
 
         let option = match data.options.first() {
             Some(option) => option,
-            None => return Err("bet command sent with empty options".into()),
+            None => return open_offer_modal(),
         };
 
         let accepting = option.value.to_string();
-        let accepting_user_payload: String = match DiscordId::attempt_from_str(&accepting) {
+        let custom_id = match DiscordId::attempt_from_str(&accepting) {
             Some(id) => {
                 let resolved_data = data
                     .resolved
@@ -42,25 +47,43 @@ where
                     None => &user.username,
                     Some(global_name) => global_name,
                 };
-                format!("wager|{}", combine_user_payload(user_name, Some(id)))
+                CustomId::new(Route::WagerModal)
+                    .with(user_name)
+                    .with(id.str_value())
             }
-            None => accepting,
+            None => CustomId::new(Route::WagerModal).with(accepting),
         };
-        Ok(open_buy_modal(accepting_user_payload))
+        Ok(open_buy_modal(custom_id.encode()?))
     }
 }
 
-pub fn open_buy_modal(accepting: String) -> InteractionResponse {
-    let wager_modal = Component::text_input(
+pub fn open_buy_modal(custom_id: String) -> InteractionResponse {
+    wager_modal(custom_id, "Place a bet", None)
+}
+
+pub fn open_offer_modal() -> Result<InteractionResponse, Error> {
+    let custom_id = CustomId::new(Route::OpenOfferModal).encode()?;
+    Ok(wager_modal(custom_id, "Post an open offer", None))
+}
+
+pub fn open_edit_modal(wager: &Wager) -> Result<InteractionResponse, Error> {
+    let custom_id = CustomId::new(Route::EditModal)
+        .with(wager.wager_id)
+        .encode()?;
+    Ok(wager_modal(custom_id, "Edit a bet", Some(wager.terms())))
+}
+
+fn wager_modal(custom_id: String, title: &str, terms: Option<WagerTerms>) -> InteractionResponse {
+    let mut wager_modal = Component::text_input(
         "wager",
         "How much are we wagering?",
         "$20",
         1,
         Some(2),
-        Some(10),
+        Some(30),
         true,
     );
-    let outcome_modal = Component::text_input(
+    let mut outcome_modal = Component::text_input(
         "outcome",
         "What is the bet on?",
         ADD_BET_PLACEHOLDER_TEXT,
@@ -69,21 +92,42 @@ pub fn open_buy_modal(accepting: String) -> InteractionResponse {
         Some(100),
         true,
     );
+    let mut odds_modal = Component::text_input(
+        "odds",
+        "What do they put up? (empty for even odds)",
+        "3-1 or $10",
+        1,
+        None,
+        Some(30),
+        false,
+    );
     let today = Local::now().format("%m/%d").to_string();
-    let settlement_date_modal = Component::text_input(
+    let mut settlement_date_modal = Component::text_input(
         "settlement",
         "When will this bet settle?",
         &today,
         1,
         Some(3),
-        Some(10),
+        Some(30),
         false,
     );
+    if let Some(terms) = terms {
+        wager_modal = wager_modal.with_value(&terms.wager.to_string());
+        if let Some(accepting_wager) = terms.accepting_wager {
+            odds_modal = odds_modal.with_value(&accepting_wager.to_string());
+        }
+        outcome_modal = outcome_modal.with_value(&terms.outcome);
+        if let Some(date) = terms.expected_settle_date {
+            settlement_date_modal =
+                settlement_date_modal.with_value(&date.format("%m/%d/%Y").to_string());
+        }
+    }
     let modal_component = InteractionCallbackData::modal_callback_data(
-        accepting,
-        "Place a bet",
+        custom_id,
+        title,
         vec![
             Component::action_row(vec![wager_modal]),
+            Component::action_row(vec![odds_modal]),
             Component::action_row(vec![outcome_modal]),
             Component::action_row(vec![settlement_date_modal]),
         ],
@@ -3,18 +3,27 @@ use crate::application::parse_date::parse_date;
 use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
+use chrono::NaiveDate;
 use discord_api::interaction_request::{ModalSubmitInteractionData, User};
-use discord_api::interaction_response::InteractionResponse;
-use pog_common::discord_id::{split_combined_user_payload, DiscordId};
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
-use pog_common::wager::{Wager, WagerStatus};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AdminSettings, AttendanceRepository, PoolRepository, WagerRepository,
+    WhoisRepository,
+};
+use pog_common::wager::{
+    parse_odds, DateRange, Stake, Wager, WagerAction, WagerEvent, WagerStatus, OPEN_OFFER_ACCEPTING,
+};
+use std::collections::HashMap;
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn add_wager(
@@ -29,18 +38,47 @@ where
             Some(global_name) => global_name.to_string(),
         };
         let resolved_offering_user = DiscordId::from_raw_str(&user.id);
-        let (accepting, resolved_accepting_user) =
-            split_combined_user_payload(&data.custom_id[6..]);
+        let custom_id = CustomId::decode(&data.custom_id)?;
+        let open_offer = custom_id.route == Route::OpenOfferModal;
+        let (accepting, resolved_accepting_user) = match open_offer {
+            true => (OPEN_OFFER_ACCEPTING.to_string(), None),
+            false => (
+                custom_id.field(0)?.to_string(),
+                custom_id.field(1).ok().and_then(DiscordId::from_raw_str),
+            ),
+        };
 
         let components = data.collect_components()?;
         let (wager, outcome) = match (components.get("wager"), components.get("outcome")) {
             (Some(wager), Some(outcome)) => (wager.to_string(), outcome.to_string()),
             (_, _) => return Err("missing components needed to place wager".into()),
         };
+        let wager = match Stake::parse(&wager) {
+            Some(stake) => stake,
+            None => return Ok(unrecognized_stake(&wager)),
+        };
+        let accepting_wager = match accepting_wager(&components, &wager) {
+            Ok(accepting_wager) => accepting_wager,
+            Err(response) => return Ok(*response),
+        };
+        let settings = self.admin_repo.get().await?;
+        if let Some(user_id) = &resolved_offering_user {
+            if let Some(response) = self
+                .check_limits(&settings, user_id, &wager, "place this bet", None)
+                .await?
+            {
+                return Ok(response);
+            }
+        }
+        let expected_settle_date = match settle_date(&components, &settings) {
+            Ok(expected_settle_date) => expected_settle_date,
+            Err(response) => return Ok(*response),
+        };
         let time = chrono::Utc::now().to_rfc3339();
-        let expected_settle_date = match components.get("settlement") {
-            Some(c) => parse_date(c),
-            None => None,
+        let status = match (open_offer, &resolved_accepting_user) {
+            (true, _) => WagerStatus::OpenOffer,
+            (false, Some(_)) => WagerStatus::AwaitingAcceptance,
+            (false, None) => WagerStatus::Open,
         };
         let wager = Wager {
             wager_id: 0,
@@ -50,13 +88,267 @@ where
             accepting,
             resolved_accepting_user,
             wager,
+            accepting_wager,
             outcome,
-            status: WagerStatus::Open,
+            status,
             expected_settle_date,
+            proposal: None,
+            paid_outcome: None,
         };
 
-        let response_message = wager.to_string();
-        self.wager_repo.insert(wager).await?;
-        Ok(response_message.into())
+        let created = WagerEvent::new(
+            0,
+            wager.resolved_offering_user.clone(),
+            WagerAction::Created,
+            None,
+            wager.status,
+        );
+        let wager_id = self
+            .wager_repo
+            .insert_with_event(wager.clone(), created)
+            .await?;
+        if open_offer {
+            return self.post_open_offer(wager_id, &wager).await;
+        }
+        match &wager.resolved_accepting_user {
+            Some(accepting_id) => offer_message(accepting_id, wager_id, &wager),
+            None => Ok(wager.to_string().into()),
+        }
+    }
+
+    // replacing leaves out a wager whose stake is being replaced by this one
+    pub(crate) async fn check_limits(
+        &self,
+        settings: &AdminSettings,
+        user_id: &DiscordId,
+        stake: &Stake,
+        action: &str,
+        replacing: Option<u32>,
+    ) -> Result<Option<InteractionResponse>, Error> {
+        let limited = settings.max_stake.is_some()
+            || settings.max_open_wagers > 0
+            || settings.max_exposure.is_some();
+        if !limited || settings.is_limit_exempt(user_id) {
+            return Ok(None);
+        }
+        let mut open_wagers = self
+            .wager_repo
+            .search_history_by_user_id(user_id, &DateRange::default())
+            .await?;
+        open_wagers.retain(|wager| Some(wager.wager_id) != replacing);
+        Ok(
+            limit_exceeded(settings, user_id, stake, &open_wagers).map(|reason| {
+                let message = format!("You can't {}, {}", action, reason);
+                InteractionResponse::channel_message_with_source_ephemeral(&message, vec![], vec![])
+            }),
+        )
+    }
+}
+
+fn offer_message(
+    accepting_id: &DiscordId,
+    wager_id: u32,
+    wager: &Wager,
+) -> Result<InteractionResponse, Error> {
+    let content = format!("{}, do you accept this wager?\n{}", accepting_id, wager);
+    let answer = |answer: &str| CustomId::new(Route::Answer).with(answer).with(wager_id);
+    let components = vec![Component::action_row(vec![
+        Component::button("Accept", 3, &answer("accept").encode()?),
+        Component::button("Decline", 4, &answer("decline").encode()?),
+    ])];
+    Ok(InteractionResponse::channel_message_with_source(
+        InteractionCallbackData::message_callback(Some(content), components),
+    ))
+}
+
+// the odds field takes odds the offering side gives (3-1) or the accepting side's own stake ($10)
+pub(crate) fn accepting_wager(
+    components: &HashMap<String, String>,
+    wager: &Stake,
+) -> Result<Option<Stake>, Box<InteractionResponse>> {
+    let value = match components.get("odds").map(|odds| odds.trim()) {
+        Some(value) if !value.is_empty() => value,
+        _ => return Ok(None),
+    };
+    let accepting_wager = match parse_odds(value) {
+        Some(odds) => match wager.at_odds(odds) {
+            Some(stake) => stake,
+            None => {
+                let message = format!("Unable to give {} odds on {}, try a different amount or enter what they put up instead", value, wager);
+                return Err(Box::new(
+                    InteractionResponse::channel_message_with_source_ephemeral(
+                        &message,
+                        vec![],
+                        vec![],
+                    ),
+                ));
+            }
+        },
+        None => match Stake::parse(value) {
+            Some(stake) => stake,
+            None => return Err(Box::new(unrecognized_stake(value))),
+        },
+    };
+    match &accepting_wager == wager {
+        true => Ok(None),
+        false => Ok(Some(accepting_wager)),
+    }
+}
+
+// why the bet would break the admin's betting limits, if it would
+fn limit_exceeded(
+    settings: &AdminSettings,
+    user_id: &DiscordId,
+    stake: &Stake,
+    open_wagers: &[Wager],
+) -> Option<String> {
+    if let Some(limit) = money_limit(&settings.max_stake) {
+        if money_in(stake, &limit) > money_in(&limit, &limit) {
+            return Some(format!("the most anyone can put up on a bet is {}", limit));
+        }
+    }
+    let open_wagers: Vec<&Wager> = open_wagers
+        .iter()
+        .filter(|wager| wager.status.is_unsettled() && wager.is_participant(user_id))
+        .collect();
+    let max_open_wagers = settings.max_open_wagers as usize;
+    if max_open_wagers > 0 && open_wagers.len() >= max_open_wagers {
+        return Some(format!(
+            "you already have {} open bets and the limit is {}",
+            open_wagers.len(),
+            max_open_wagers
+        ));
+    }
+    if let Some(Stake::Money { amount, currency }) = money_limit(&settings.max_exposure) {
+        let limit = Stake::Money { amount, currency };
+        let exposure = Stake::Money {
+            amount: open_wagers
+                .iter()
+                .filter_map(|wager| wager.stake_at_risk(user_id))
+                .chain(std::iter::once(stake))
+                .map(|stake| money_in(stake, &limit))
+                .sum(),
+            currency,
+        };
+        if money_in(&exposure, &limit) > amount {
+            return Some(format!(
+                "it would put {} at stake across your open bets and the limit is {}",
+                exposure, limit
+            ));
+        }
+    }
+    None
+}
+
+fn money_limit(limit: &Option<String>) -> Option<Stake> {
+    match limit.as_deref().and_then(Stake::parse) {
+        Some(limit @ Stake::Money { .. }) => Some(limit),
+        _ => None,
+    }
+}
+
+// money limits only count bets in the limit's currency
+fn money_in(stake: &Stake, limit: &Stake) -> i64 {
+    match (stake, limit) {
+        (
+            Stake::Money { amount, currency },
+            Stake::Money {
+                currency: limit_currency,
+                ..
+            },
+        ) if currency == limit_currency => *amount,
+        _ => 0,
+    }
+}
+
+pub(crate) fn settle_date(
+    components: &HashMap<String, String>,
+    settings: &AdminSettings,
+) -> Result<Option<NaiveDate>, Box<InteractionResponse>> {
+    let value = match components.get("settlement") {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    parse_date(value, chrono::Utc::now().date_naive(), settings).map_err(|message| {
+        Box::new(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![],
+            vec![],
+        ))
+    })
+}
+
+pub(crate) fn unrecognized_stake(wager: &str) -> InteractionResponse {
+    let message = format!(
+        "Unable to understand the wager '{}', use an amount like `$20` or `20 euro`, or an item with a quantity like `a beer` or `6 beers`",
+        wager
+    );
+    InteractionResponse::channel_message_with_source_ephemeral(&message, vec![], vec![])
+}
+
+#[test]
+fn test_limit_exceeded() {
+    let harx: DiscordId = 695398918694895710.into();
+    let cisco: DiscordId = 1050119194533961860.into();
+    let open_wager = |stake: &str, accepting_wager: Option<&str>| Wager {
+        wager_id: 1,
+        time: "2024-10-01T00:00:00Z".to_string(),
+        resolved_offering_user: Some(cisco.clone()),
+        resolved_accepting_user: Some(harx.clone()),
+        wager: stake.into(),
+        accepting_wager: accepting_wager.map(Stake::from),
+        ..Wager::fixture("Cisco", "Harx", WagerStatus::Open)
+    };
+    let open_wagers = vec![open_wager("$30", Some("$10")), open_wager("2 beers", None)];
+    let mut settings = AdminSettings::default();
+    assert_eq!(
+        None,
+        limit_exceeded(&settings, &harx, &"$500".into(), &open_wagers)
+    );
+
+    settings.max_stake = Some("$50".to_string());
+    assert_eq!(
+        Some("the most anyone can put up on a bet is $50".to_string()),
+        limit_exceeded(&settings, &harx, &"$60".into(), &open_wagers)
+    );
+    assert_eq!(
+        None,
+        limit_exceeded(&settings, &harx, &"60 euro".into(), &open_wagers)
+    );
+
+    settings.max_exposure = Some("$40".to_string());
+    assert_eq!(
+        Some("it would put $45 at stake across your open bets and the limit is $40".to_string()),
+        limit_exceeded(&settings, &harx, &"$35".into(), &open_wagers)
+    );
+    assert_eq!(
+        None,
+        limit_exceeded(&settings, &harx, &"$30".into(), &open_wagers)
+    );
+
+    settings.max_open_wagers = 2;
+    assert_eq!(
+        Some("you already have 2 open bets and the limit is 2".to_string()),
+        limit_exceeded(&settings, &harx, &"a beer".into(), &open_wagers)
+    );
+
+    // bets still waiting on an answer or a settlement count, finished ones don't
+    let mut open_wagers = open_wagers;
+    open_wagers[1].status = WagerStatus::Paid;
+    assert_eq!(
+        None,
+        limit_exceeded(&settings, &harx, &"a beer".into(), &open_wagers)
+    );
+    for status in [
+        WagerStatus::AwaitingAcceptance,
+        WagerStatus::OpenOffer,
+        WagerStatus::SettlementProposed,
+        WagerStatus::Disputed,
+    ] {
+        open_wagers[1].status = status;
+        assert_eq!(
+            Some("you already have 2 open bets and the limit is 2".to_string()),
+            limit_exceeded(&settings, &harx, &"a beer".into(), &open_wagers)
+        );
     }
 }
@@ -0,0 +1,140 @@
+use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_response::InteractionResponse;
+
+use crate::application::app::counter;
+use crate::application::t32_settle_bet::close_message;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{WagerAction, WagerEvent, WagerStatus};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn accept_wager(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("accept_wager");
+
+        let (accepted, wager_id) = split_answer_custom_id(&data.custom_id)?;
+        let mut wager = match self.wager_repo.get(wager_id).await {
+            Some(wager) => wager,
+            None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
+        };
+        let user_id = DiscordId::from_raw_str(&request.expect_member()?.expect_user()?.id);
+        let is_accepting = user_id.is_some() && user_id == wager.resolved_accepting_user;
+        let is_offering = user_id.is_some() && user_id == wager.resolved_offering_user;
+        let may_answer = is_accepting || (is_offering && !accepted);
+        if !may_answer {
+            let accepting = match &wager.resolved_accepting_user {
+                Some(id) => id.to_string(),
+                None => wager.accepting.to_string(),
+            };
+            let message = format!("Only {} can accept or decline this wager", accepting);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+
+        if let (true, Some(user_id)) = (accepted && is_accepting, &user_id) {
+            let settings = self.admin_repo.get().await?;
+            let stake = wager.accepting_stake().clone();
+            if let Some(response) = self
+                .check_limits(
+                    &settings,
+                    user_id,
+                    &stake,
+                    "accept this bet",
+                    Some(wager.wager_id),
+                )
+                .await?
+            {
+                return Ok(response);
+            }
+        }
+
+        wager.status = match accepted {
+            true => WagerStatus::Open,
+            false => WagerStatus::Declined,
+        };
+        let user_id = user_id.expect("user id was checked against the wager");
+        let (action, message) = match (accepted, is_accepting) {
+            (true, _) => (
+                WagerAction::Accepted,
+                format!("{} accepted: {}", user_id, wager),
+            ),
+            (false, true) => (
+                WagerAction::Declined,
+                format!("{} declined: {}", user_id, wager),
+            ),
+            (false, false) => (
+                WagerAction::Withdrawn,
+                format!("{} withdrew the offer: {}", user_id, wager),
+            ),
+        };
+        let event = WagerEvent::new(
+            wager.wager_id,
+            Some(user_id),
+            action,
+            Some(WagerStatus::AwaitingAcceptance),
+            wager.status,
+        );
+        if !self
+            .wager_repo
+            .respond_to_offer(wager_id, wager.status, &[event])
+            .await?
+        {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "This wager is no longer waiting on a response",
+                vec![],
+                vec![],
+            ));
+        }
+        close_message(&request, &self.client).await?;
+        Ok(message.into())
+    }
+}
+
+fn split_answer_custom_id(custom_id: &str) -> Result<(bool, i32), Error> {
+    let custom_id = CustomId::decode(custom_id)?;
+    let accepted = match (custom_id.route, custom_id.field(0)?) {
+        (Route::Answer, "accept") => true,
+        (Route::Answer, "decline") => false,
+        _ => return Err("custom id was not recognized".into()),
+    };
+    match custom_id.parse_field::<i32>(1) {
+        Ok(wager_id) => Ok((accepted, wager_id)),
+        Err(_) => Err("unable to parse a wager_id from the returned value".into()),
+    }
+}
+
+#[test]
+fn test_split_answer_custom_id() {
+    assert_eq!(
+        Ok((true, 109)),
+        split_answer_custom_id("answer:1:accept:109")
+    );
+    assert_eq!(
+        Ok((false, 109)),
+        split_answer_custom_id("answer:1:decline:109")
+    );
+    assert_eq!(
+        Err("custom id was not recognized".into()),
+        split_answer_custom_id("answer:1:maybe:109")
+    );
+}
@@ -0,0 +1,393 @@
+use discord_api::interaction_request::{
+    ApplicationCommandInteractionData, GuildMember, InteractionObject,
+    MessageComponentInteractionData, ModalSubmitInteractionData, User,
+};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::t10_initiate_bet::open_edit_modal;
+use crate::application::t11_add_wager::{accepting_wager, settle_date, unrecognized_stake};
+use crate::application::t30_settle_bet::wager_matches;
+use crate::application::t32_settle_bet::{close_message, conflict_response};
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{
+    RevisionStatus, Stake, Wager, WagerAction, WagerEvent, WagerRevision, WagerStatus, WagerTerms,
+};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn edit_bet(
+        &self,
+        data: ApplicationCommandInteractionData,
+        member: &GuildMember,
+    ) -> Result<InteractionResponse, Error> {
+        counter("edit_bet");
+
+        let search = match data.option_key_values().get("wager") {
+            Some(search) => search.to_string(),
+            None => return Err("edit command sent with empty options".into()),
+        };
+        let wager = match search.parse::<i32>() {
+            Ok(wager_id) => self.wager_repo.get(wager_id).await,
+            Err(_) => {
+                let mut wagers = self.editable_wagers(member).await?;
+                wagers.retain(|wager| wager_matches(wager, &search));
+                if wagers.len() > 1 {
+                    let message = format!(
+                        "More than one of your open bets match \"{}\", pick one from the list",
+                        search
+                    );
+                    return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                        &message,
+                        vec![],
+                        vec![],
+                    ));
+                }
+                wagers.pop()
+            }
+        };
+        let wager = match wager {
+            Some(wager) => wager,
+            None => {
+                let message = format!("None of your open bets match \"{}\"", search);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let user_id = DiscordId::from_raw_str(&member.expect_user()?.id);
+        if let Some(response) = check_editable(&wager, user_id.as_ref()) {
+            return Ok(response);
+        }
+        open_edit_modal(&wager)
+    }
+
+    pub(crate) async fn editable_wagers(&self, member: &GuildMember) -> Result<Vec<Wager>, Error> {
+        let wagers = match DiscordId::from_raw_str(&member.expect_user()?.id) {
+            Some(user_id) => self.wager_repo.search_by_user_id(&user_id).await?,
+            None => vec![],
+        };
+        Ok(wagers
+            .into_iter()
+            .filter(|wager| wager.status == WagerStatus::Open)
+            .collect())
+    }
+
+    pub async fn amend_wager(
+        &self,
+        data: ModalSubmitInteractionData,
+        user: &User,
+    ) -> Result<InteractionResponse, Error> {
+        counter("amend_wager_modal_response");
+
+        let wager_id =
+            match CustomId::decode(&data.custom_id).and_then(|id| id.parse_field::<i32>(0)) {
+                Ok(wager_id) => wager_id,
+                Err(_) => return Err("unable to parse a wager_id from the returned value".into()),
+            };
+        let wager = match self.wager_repo.get(wager_id).await {
+            Some(wager) => wager,
+            None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
+        };
+        let user_id = DiscordId::from_raw_str(&user.id);
+        if let Some(response) = check_editable(&wager, user_id.as_ref()) {
+            return Ok(response);
+        }
+        let proposed_by = user_id.expect("user id was checked against the wager");
+
+        let components = data.collect_components()?;
+        let (stake, outcome) = match (components.get("wager"), components.get("outcome")) {
+            (Some(wager), Some(outcome)) => (wager.to_string(), outcome.to_string()),
+            (_, _) => return Err("missing components needed to edit wager".into()),
+        };
+        let stake = match Stake::parse(&stake) {
+            Some(stake) => stake,
+            None => return Ok(unrecognized_stake(&stake)),
+        };
+        let settings = self.admin_repo.get().await?;
+        let revised = WagerTerms {
+            accepting_wager: match accepting_wager(&components, &stake) {
+                Ok(accepting_wager) => accepting_wager,
+                Err(response) => return Ok(*response),
+            },
+            wager: stake,
+            outcome,
+            expected_settle_date: match settle_date(&components, &settings) {
+                Ok(expected_settle_date) => expected_settle_date,
+                Err(response) => return Ok(*response),
+            },
+        };
+        if revised == wager.terms() {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "Nothing was changed",
+                vec![],
+                vec![],
+            ));
+        }
+        if let Some(response) = self
+            .check_revised_limits(&wager, &revised, &proposed_by, "make this change")
+            .await?
+        {
+            return Ok(response);
+        }
+
+        let revision = WagerRevision {
+            revision_id: 0,
+            wager_id: wager.wager_id,
+            time: chrono::Utc::now().to_rfc3339(),
+            proposed_by: proposed_by.clone(),
+            status: RevisionStatus::Proposed,
+            previous: wager.terms(),
+            revised,
+        };
+        let revision_id = match self.wager_repo.propose_revision(revision.clone()).await? {
+            Some(revision_id) => revision_id,
+            None => {
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    "Another change to this bet is still waiting on a response",
+                    vec![],
+                    vec![],
+                ))
+            }
+        };
+        match wager.counterparty(&proposed_by) {
+            Some(counterparty) => amendment_message(
+                counterparty,
+                &proposed_by,
+                revision_id,
+                &wager,
+                &revision.revised,
+            ),
+            None => {
+                let edited = revision_event(&wager, &revision, &proposed_by, WagerAction::Edited);
+                match self
+                    .wager_repo
+                    .resolve_revision(revision_id as i32, RevisionStatus::Accepted, &[edited])
+                    .await
+                {
+                    Err(pog_common::error::Error::Conflict(current)) => {
+                        return Ok(conflict_response(&current))
+                    }
+                    result => result?,
+                };
+                let message = format!(
+                    "{} amended: {}",
+                    proposed_by,
+                    wager.with_terms(&revision.revised)
+                );
+                Ok(message.into())
+            }
+        }
+    }
+
+    pub async fn confirm_amendment(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("confirm_amendment");
+
+        let (confirmed, revision_id) = split_amend_custom_id(&data.custom_id)?;
+        let revision = match self.wager_repo.get_revision(revision_id).await {
+            Some(revision) => revision,
+            None => {
+                return Err(Error::Invalid(format!(
+                    "revision {} not found",
+                    revision_id
+                )))
+            }
+        };
+        let wager = match self.wager_repo.get(revision.wager_id as i32).await {
+            Some(wager) => wager,
+            None => {
+                return Err(Error::Invalid(format!(
+                    "wager {} not found",
+                    revision.wager_id
+                )))
+            }
+        };
+        let user_id = DiscordId::from_raw_str(&request.expect_member()?.expect_user()?.id);
+        let counterparty = wager.counterparty(&revision.proposed_by);
+        let is_counterparty = user_id.is_some() && user_id.as_ref() == counterparty;
+        let is_proposer = user_id.as_ref() == Some(&revision.proposed_by);
+        if !(is_counterparty || (is_proposer && !confirmed)) {
+            let counterparty = match counterparty {
+                Some(counterparty) => counterparty.to_string(),
+                None => wager.accepting.to_string(),
+            };
+            let message = format!("Only {} can confirm or reject this change", counterparty);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        if confirmed && (wager.status != WagerStatus::Open || wager.terms() != revision.previous) {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "This wager has changed since the edit was proposed",
+                vec![],
+                vec![],
+            ));
+        }
+
+        if let (true, Some(user_id)) = (confirmed, &user_id) {
+            if let Some(response) = self
+                .check_revised_limits(&wager, &revision.revised, user_id, "agree to this change")
+                .await?
+            {
+                return Ok(response);
+            }
+        }
+
+        let user_id = user_id.expect("user id was checked against the wager");
+        let (status, action) = match (confirmed, is_counterparty) {
+            (true, _) => (RevisionStatus::Accepted, WagerAction::Edited),
+            (false, true) => (RevisionStatus::Rejected, WagerAction::EditRejected),
+            (false, false) => (RevisionStatus::Rejected, WagerAction::EditWithdrawn),
+        };
+        let event = revision_event(&wager, &revision, &user_id, action);
+        let resolved = match self
+            .wager_repo
+            .resolve_revision(revision_id, status, &[event])
+            .await
+        {
+            Err(pog_common::error::Error::Conflict(current)) => {
+                return Ok(conflict_response(&current))
+            }
+            result => result?,
+        };
+        if !resolved {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "This edit is no longer waiting on a response",
+                vec![],
+                vec![],
+            ));
+        }
+
+        let message = match (confirmed, is_counterparty) {
+            (true, _) => format!(
+                "{} confirmed the change: {}",
+                user_id,
+                wager.with_terms(&revision.revised)
+            ),
+            (false, true) => format!("{} rejected the change to: {}", user_id, wager),
+            (false, false) => format!("{} withdrew the change to: {}", user_id, wager),
+        };
+        close_message(&request, &self.client).await?;
+        Ok(message.into())
+    }
+
+    // the new stake takes the place of the old one in the user's betting limits
+    async fn check_revised_limits(
+        &self,
+        wager: &Wager,
+        revised: &WagerTerms,
+        user_id: &DiscordId,
+        action: &str,
+    ) -> Result<Option<InteractionResponse>, Error> {
+        let settings = self.admin_repo.get().await?;
+        let revised = wager.with_terms(revised);
+        match revised.stake_at_risk(user_id) {
+            Some(stake) => {
+                self.check_limits(&settings, user_id, stake, action, Some(wager.wager_id))
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+// the detail is the terms the revision proposed, whether or not they were taken
+fn revision_event(
+    wager: &Wager,
+    revision: &WagerRevision,
+    actor: &DiscordId,
+    action: WagerAction,
+) -> WagerEvent {
+    WagerEvent::new(
+        wager.wager_id,
+        Some(actor.clone()),
+        action,
+        Some(wager.status),
+        wager.status,
+    )
+    .with_detail(&wager.with_terms(&revision.revised).simplified_string())
+}
+
+fn check_editable(wager: &Wager, user_id: Option<&DiscordId>) -> Option<InteractionResponse> {
+    let message = match user_id {
+        Some(user_id) if wager.is_participant(user_id) => match wager.status {
+            WagerStatus::Open => return None,
+            _ => "Only open bets can be edited",
+        },
+        _ => "Only the people in this bet can edit it",
+    };
+    Some(InteractionResponse::channel_message_with_source_ephemeral(
+        message,
+        vec![],
+        vec![],
+    ))
+}
+
+fn amendment_message(
+    counterparty: &DiscordId,
+    proposed_by: &DiscordId,
+    revision_id: u32,
+    wager: &Wager,
+    revised: &WagerTerms,
+) -> Result<InteractionResponse, Error> {
+    let content = format!(
+        "{}, {} wants to change this wager:\n{}\nto:\n{}",
+        counterparty,
+        proposed_by,
+        wager,
+        wager.with_terms(revised)
+    );
+    let amend = |answer: &str| CustomId::new(Route::Amend).with(answer).with(revision_id);
+    let components = vec![Component::action_row(vec![
+        Component::button("Confirm", 3, &amend("confirm").encode()?),
+        Component::button("Reject", 4, &amend("reject").encode()?),
+    ])];
+    Ok(InteractionResponse::channel_message_with_source(
+        InteractionCallbackData::message_callback(Some(content), components),
+    ))
+}
+
+fn split_amend_custom_id(custom_id: &str) -> Result<(bool, i32), Error> {
+    let custom_id = CustomId::decode(custom_id)?;
+    let confirmed = match (custom_id.route, custom_id.field(0)?) {
+        (Route::Amend, "confirm") => true,
+        (Route::Amend, "reject") => false,
+        _ => return Err("custom id was not recognized".into()),
+    };
+    match custom_id.parse_field::<i32>(1) {
+        Ok(revision_id) => Ok((confirmed, revision_id)),
+        Err(_) => Err("unable to parse a revision_id from the returned value".into()),
+    }
+}
+
+#[test]
+fn test_split_amend_custom_id() {
+    assert_eq!(Ok((true, 3)), split_amend_custom_id("amend:1:confirm:3"));
+    assert_eq!(Ok((false, 3)), split_amend_custom_id("amend:1:reject:3"));
+    assert_eq!(
+        Err("custom id was not recognized".into()),
+        split_amend_custom_id("amend:1:maybe:3")
+    );
+}
@@ -0,0 +1,290 @@
+use chrono::{DateTime, Utc};
+use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::t32_settle_bet::conflict_response;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{Wager, WagerAction, WagerEvent, WagerStatus};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub(crate) async fn post_open_offer(
+        &self,
+        wager_id: u32,
+        wager: &Wager,
+    ) -> Result<InteractionResponse, Error> {
+        let settings = self.admin_repo.get().await?;
+        let mut content = format!("Open offer, the first to take it is on: {}", wager);
+        if let Some(expires) = offer_expires(wager, settings.offer_hours) {
+            content.push_str(format!("\nExpires <t:{}:R>", expires.timestamp()).as_str());
+        }
+        let offer = |action: &str| CustomId::new(Route::Offer).with(action).with(wager_id);
+        let components = vec![Component::action_row(vec![
+            Component::button("Take it", 3, &offer("take").encode()?),
+            Component::button("Withdraw", 4, &offer("withdraw").encode()?),
+        ])];
+        if settings.betting_channel.is_empty() {
+            return Ok(InteractionResponse::channel_message_with_source(
+                InteractionCallbackData::message_callback(Some(content), components),
+            ));
+        }
+        self.client
+            .create_message(&settings.betting_channel, &content, components)
+            .await?;
+        let message = format!("Your offer was posted in <#{}>", settings.betting_channel);
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![],
+            vec![],
+        ))
+    }
+
+    pub async fn answer_offer(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("answer_offer");
+
+        let (take, wager_id) = split_offer_custom_id(&data.custom_id)?;
+        let mut wager = match self.wager_repo.get(wager_id).await {
+            Some(wager) => wager,
+            None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
+        };
+        if wager.status != WagerStatus::OpenOffer {
+            return Ok(offer_closed(&wager));
+        }
+        let user = request.expect_member()?.expect_user()?;
+        let user_id = match DiscordId::from_raw_str(&user.id) {
+            Some(user_id) => user_id,
+            None => return Err(Error::UnresolvedDiscordUser),
+        };
+        let is_offering = wager.resolved_offering_user.as_ref() == Some(&user_id);
+
+        if !take {
+            if !is_offering {
+                let offering = match &wager.resolved_offering_user {
+                    Some(id) => id.to_string(),
+                    None => wager.offering.to_string(),
+                };
+                let message = format!("Only {} can withdraw this offer", offering);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ));
+            }
+            wager.status = WagerStatus::Declined;
+            let withdrawn = WagerEvent::new(
+                wager.wager_id,
+                Some(user_id.clone()),
+                WagerAction::Withdrawn,
+                Some(WagerStatus::OpenOffer),
+                wager.status,
+            );
+            match self
+                .wager_repo
+                .update_status(wager_id, WagerStatus::OpenOffer, &wager, &[withdrawn])
+                .await
+            {
+                Err(pog_common::error::Error::Conflict(current)) => {
+                    return Ok(offer_closed(&current))
+                }
+                result => result?,
+            }
+            let message = format!("{} withdrew the offer: {}", user_id, wager);
+            return closed_offer_message(message, "Withdrawn", wager_id);
+        }
+
+        if is_offering {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "You can't take your own offer",
+                vec![],
+                vec![],
+            ));
+        }
+        let settings = self.admin_repo.get().await?;
+        if offer_expires(&wager, settings.offer_hours).is_some_and(|expires| expires <= Utc::now())
+        {
+            if !self.expire_offer(&wager).await? {
+                return Ok(self.offer_closed_since(wager_id).await);
+            }
+            let message = format!("This offer expired: {}", wager);
+            return closed_offer_message(message, "Expired", wager_id);
+        }
+        if let Some(response) = self
+            .check_limits(
+                &settings,
+                &user_id,
+                wager.accepting_stake(),
+                "take this offer",
+                None,
+            )
+            .await?
+        {
+            return Ok(response);
+        }
+        let accepting = match &user.global_name {
+            None => user.username.to_string(),
+            Some(global_name) => global_name.to_string(),
+        };
+        let accepted = WagerEvent::new(
+            wager.wager_id,
+            Some(user_id.clone()),
+            WagerAction::Accepted,
+            Some(WagerStatus::OpenOffer),
+            WagerStatus::Open,
+        );
+        if !self
+            .wager_repo
+            .take_offer(wager_id, &accepting, &user_id, &[accepted])
+            .await?
+        {
+            return Ok(self.offer_closed_since(wager_id).await);
+        }
+        wager.status = WagerStatus::Open;
+        wager.accepting = accepting;
+        wager.resolved_accepting_user = Some(user_id.clone());
+        let message = format!("{} took the offer: {}", user_id, wager);
+        closed_offer_message(message, "Taken", wager_id)
+    }
+
+    pub async fn expire_offers(&self, now: DateTime<Utc>) -> Result<usize, Error> {
+        counter("expire_offers");
+
+        let settings = self.admin_repo.get().await?;
+        let mut expired = 0;
+        for wager in self
+            .wager_repo
+            .search_by_status(WagerStatus::OpenOffer)
+            .await?
+        {
+            let due = offer_expires(&wager, settings.offer_hours).is_some_and(|at| at <= now);
+            if due && self.expire_offer(&wager).await? {
+                expired += 1;
+            }
+        }
+        Ok(expired)
+    }
+
+    async fn expire_offer(&self, wager: &Wager) -> Result<bool, Error> {
+        let mut expired = wager.clone();
+        expired.status = WagerStatus::Declined;
+        let event = WagerEvent::new(
+            wager.wager_id,
+            None,
+            WagerAction::Expired,
+            Some(WagerStatus::OpenOffer),
+            expired.status,
+        );
+        match self
+            .wager_repo
+            .update_status(
+                wager.wager_id as i32,
+                WagerStatus::OpenOffer,
+                &expired,
+                &[event],
+            )
+            .await
+        {
+            Err(pog_common::error::Error::Conflict(_)) => return Ok(false),
+            result => result?,
+        }
+        Ok(true)
+    }
+
+    async fn offer_closed_since(&self, wager_id: i32) -> InteractionResponse {
+        match self.wager_repo.get(wager_id).await {
+            Some(wager) => offer_closed(&wager),
+            None => InteractionResponse::channel_message_with_source_ephemeral(
+                "This offer is no longer available",
+                vec![],
+                vec![],
+            ),
+        }
+    }
+}
+
+// offers never expire when offer_hours is 0
+fn offer_expires(wager: &Wager, offer_hours: u16) -> Option<DateTime<Utc>> {
+    if offer_hours == 0 {
+        return None;
+    }
+    let posted = DateTime::parse_from_rfc3339(&wager.time).ok()?;
+    Some(posted.with_timezone(&Utc) + chrono::Duration::hours(offer_hours as i64))
+}
+
+fn offer_closed(wager: &Wager) -> InteractionResponse {
+    let message = match wager.status {
+        WagerStatus::Open => match &wager.resolved_accepting_user {
+            Some(accepting) => format!("{} already took this offer", accepting),
+            None => format!("{} already took this offer", wager.accepting),
+        },
+        WagerStatus::Declined => "This offer is no longer available".to_string(),
+        _ => return conflict_response(wager),
+    };
+    InteractionResponse::channel_message_with_source_ephemeral(&message, vec![], vec![])
+}
+
+fn closed_offer_message(
+    content: String,
+    label: &str,
+    wager_id: i32,
+) -> Result<InteractionResponse, Error> {
+    let custom_id = CustomId::new(Route::Offer)
+        .with("closed")
+        .with(wager_id)
+        .encode()?;
+    let components = vec![Component::action_row(vec![Component::button(
+        label, 2, &custom_id,
+    )
+    .disabled()])];
+    Ok(InteractionResponse::update_message(
+        InteractionCallbackData::message_callback(Some(content), components),
+    ))
+}
+
+fn split_offer_custom_id(custom_id: &str) -> Result<(bool, i32), Error> {
+    let custom_id = CustomId::decode(custom_id)?;
+    let take = match (custom_id.route, custom_id.field(0)?) {
+        (Route::Offer, "take") => true,
+        (Route::Offer, "withdraw") => false,
+        _ => return Err("custom id was not recognized".into()),
+    };
+    match custom_id.parse_field::<i32>(1) {
+        Ok(wager_id) => Ok((take, wager_id)),
+        Err(_) => Err("unable to parse a wager_id from the returned value".into()),
+    }
+}
+
+#[test]
+fn test_split_offer_custom_id() {
+    assert_eq!(Ok((true, 109)), split_offer_custom_id("offer:1:take:109"));
+    assert_eq!(
+        Ok((false, 109)),
+        split_offer_custom_id("offer:1:withdraw:109")
+    );
+    assert_eq!(
+        Err("custom id was not recognized".into()),
+        split_offer_custom_id("offer:1:closed:109")
+    );
+    assert_eq!(
+        Err("custom id was not recognized".into()),
+        split_offer_custom_id("answer:1:take:109")
+    );
+}
@@ -0,0 +1,403 @@
+use discord_api::interaction_request::{
+    InteractionObject, MessageComponentInteractionData, ModalSubmitInteractionData, User,
+};
+use discord_api::interaction_response::{
+    Component, InteractionCallbackData, InteractionResponse, SelectMenuOption,
+};
+
+use crate::application::app::counter;
+use crate::application::t11_add_wager::unrecognized_stake;
+use crate::application::t32_settle_bet::close_message;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::pool::{Pool, PoolEntry, PoolStatus};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::Stake;
+
+const MAX_POOL_OPTIONS: usize = 25;
+// each option is the label and value of a select menu option
+const MAX_OPTION_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+enum PoolAction {
+    Join,
+    Pick,
+    Settle,
+    Winner,
+}
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn start_pool(&self) -> Result<InteractionResponse, Error> {
+        counter("start_pool");
+
+        pool_modal()
+    }
+
+    pub async fn create_pool(
+        &self,
+        data: ModalSubmitInteractionData,
+        user: &User,
+    ) -> Result<InteractionResponse, Error> {
+        counter("create_pool_modal_response");
+
+        let components = data.collect_components()?;
+        let (question, buy_in, options) = match (
+            components.get("question"),
+            components.get("buy_in"),
+            components.get("options"),
+        ) {
+            (Some(question), Some(buy_in), Some(options)) => (question, buy_in, options),
+            _ => return Err("missing components needed to create a pool".into()),
+        };
+        let buy_in = match Stake::parse(buy_in) {
+            Some(stake) => stake,
+            None => return Ok(unrecognized_stake(buy_in)),
+        };
+        let options = parse_options(options);
+        if options.len() < 2 || options.len() > MAX_POOL_OPTIONS {
+            let message = format!(
+                "A pool needs between 2 and {} options, one per line",
+                MAX_POOL_OPTIONS
+            );
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        if options
+            .iter()
+            .any(|option| option.chars().count() > MAX_OPTION_LENGTH)
+        {
+            let message = format!(
+                "Pool options can be at most {} characters each",
+                MAX_OPTION_LENGTH
+            );
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        let created_by = match DiscordId::from_raw_str(&user.id) {
+            Some(user_id) => user_id,
+            None => return Err(Error::UnresolvedDiscordUser),
+        };
+        let mut pool = Pool {
+            pool_id: 0,
+            time: chrono::Utc::now().to_rfc3339(),
+            created_by,
+            question: question.to_string(),
+            buy_in,
+            options,
+            status: PoolStatus::Open,
+            winning_option: None,
+            entries: vec![],
+        };
+        pool.pool_id = self.pool_repo.insert(pool.clone()).await?;
+
+        let content = format!("{} started a pool: {}", pool.created_by, pool);
+        let components = vec![Component::action_row(vec![
+            Component::button("Join", 3, &pool_custom_id(&pool, "join")?),
+            Component::button("Settle", 2, &pool_custom_id(&pool, "settle")?),
+        ])];
+        Ok(InteractionResponse::channel_message_with_source(
+            InteractionCallbackData::message_callback(Some(content), components),
+        ))
+    }
+
+    pub async fn pool_action(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("pool_action");
+
+        let (action, pool_id) = split_pool_custom_id(&data.custom_id)?;
+        let pool = match self.pool_repo.get(pool_id).await {
+            Some(pool) => pool,
+            None => return Err(Error::Invalid(format!("pool {} not found", pool_id))),
+        };
+        if pool.status != PoolStatus::Open {
+            return Ok(pool_closed(&pool));
+        }
+        let member = request.expect_member()?;
+        let user = member.expect_user()?;
+        let user_id = match DiscordId::from_raw_str(&user.id) {
+            Some(user_id) => user_id,
+            None => return Err(Error::UnresolvedDiscordUser),
+        };
+        if matches!(action, PoolAction::Settle | PoolAction::Winner)
+            && pool.created_by != user_id
+            && !member.is_administrator()
+        {
+            let message = format!("Only {} or an admin can settle this pool", pool.created_by);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+
+        match action {
+            PoolAction::Join => {
+                if let Some(entry) = pool.entry(&user_id) {
+                    let message = format!("You're already in this pool with {}", entry.pick);
+                    return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                        &message,
+                        vec![],
+                        vec![],
+                    ));
+                }
+                let content = format!(
+                    "Pick your option for {} (buy-in: {})",
+                    pool.question, pool.buy_in
+                );
+                Ok(InteractionResponse::select_response(
+                    content,
+                    vec![option_select(&pool, "pick", "Your pick")?],
+                ))
+            }
+            PoolAction::Pick => {
+                let pick = picked_option(&pool, &data)?;
+                let entry = PoolEntry {
+                    user_id: user_id.clone(),
+                    name: match &user.global_name {
+                        None => user.username.to_string(),
+                        Some(global_name) => global_name.to_string(),
+                    },
+                    pick: pick.to_string(),
+                    buy_in: pool.buy_in.clone(),
+                };
+                if !self.pool_repo.join(pool_id, entry).await? {
+                    return Ok(self.pool_closed_since(pool_id).await);
+                }
+                let pool = self.pool_repo.get(pool_id).await.unwrap_or(pool);
+                close_message(&request, &self.client).await?;
+                let message = format!(
+                    "{} joined pool {} with {}, the pot is now {}",
+                    user_id,
+                    pool.pool_id,
+                    pick,
+                    pool.pot()
+                );
+                Ok(message.into())
+            }
+            PoolAction::Settle => {
+                let content = format!("Which option won? {}", pool.question);
+                Ok(InteractionResponse::select_response(
+                    content,
+                    vec![option_select(&pool, "winner", "Winning option")?],
+                ))
+            }
+            PoolAction::Winner => {
+                let winning_option = picked_option(&pool, &data)?;
+                if !self.pool_repo.settle(pool_id, &winning_option).await? {
+                    return Ok(self.pool_closed_since(pool_id).await);
+                }
+                let pool = match self.pool_repo.get(pool_id).await {
+                    Some(pool) => pool,
+                    None => return Err(Error::Invalid(format!("pool {} not found", pool_id))),
+                };
+                close_message(&request, &self.client).await?;
+                Ok(settled_message(&pool).into())
+            }
+        }
+    }
+
+    async fn pool_closed_since(&self, pool_id: i32) -> InteractionResponse {
+        match self.pool_repo.get(pool_id).await {
+            Some(pool) if pool.status != PoolStatus::Open => pool_closed(&pool),
+            _ => InteractionResponse::channel_message_with_source_ephemeral(
+                "You're already in this pool",
+                vec![],
+                vec![],
+            ),
+        }
+    }
+}
+
+fn pool_modal() -> Result<InteractionResponse, Error> {
+    let question = Component::text_input(
+        "question",
+        "What is the pool on?",
+        "Who wins the Super Bowl?",
+        1,
+        Some(3),
+        Some(100),
+        true,
+    );
+    let buy_in = Component::text_input(
+        "buy_in",
+        "What is the buy-in?",
+        "$10",
+        1,
+        Some(2),
+        Some(30),
+        true,
+    );
+    let options = Component::text_input(
+        "options",
+        "What can people pick? (one per line)",
+        "Chiefs\nEagles",
+        2,
+        Some(3),
+        Some(1000),
+        true,
+    );
+    Ok(InteractionResponse::modal(
+        InteractionCallbackData::modal_callback_data(
+            CustomId::new(Route::PoolModal).encode()?,
+            "Start a pool",
+            vec![
+                Component::action_row(vec![question]),
+                Component::action_row(vec![buy_in]),
+                Component::action_row(vec![options]),
+            ],
+        ),
+    ))
+}
+
+fn parse_options(options: &str) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+    for option in options.lines().map(str::trim) {
+        if !option.is_empty() && !result.iter().any(|o| o.eq_ignore_ascii_case(option)) {
+            result.push(option.to_string());
+        }
+    }
+    result
+}
+
+fn pool_custom_id(pool: &Pool, action: &str) -> Result<String, Error> {
+    Ok(CustomId::new(Route::Pool)
+        .with(action)
+        .with(pool.pool_id)
+        .encode()?)
+}
+
+fn option_select(pool: &Pool, action: &str, placeholder: &str) -> Result<Component, Error> {
+    let options = pool
+        .options
+        .iter()
+        .map(|option| {
+            let picked = pool
+                .entries
+                .iter()
+                .filter(|entry| entry.pick.eq_ignore_ascii_case(option))
+                .count();
+            SelectMenuOption::new(
+                option.to_string(),
+                option.to_string(),
+                format!("{} picked", picked),
+            )
+        })
+        .collect();
+    Ok(Component::action_row(vec![Component::select_choice(
+        &pool_custom_id(pool, action)?,
+        placeholder,
+        options,
+    )]))
+}
+
+fn picked_option(pool: &Pool, data: &MessageComponentInteractionData) -> Result<String, Error> {
+    match data.values.first() {
+        Some(value) => match pool.options.iter().find(|o| o.eq_ignore_ascii_case(value)) {
+            Some(option) => Ok(option.to_string()),
+            None => Err(Error::Invalid(format!(
+                "{} is not an option in pool {}",
+                value, pool.pool_id
+            ))),
+        },
+        None => Err("missing response to pool option selection".into()),
+    }
+}
+
+fn settled_message(pool: &Pool) -> String {
+    let winning_option = pool.winning_option.clone().unwrap_or_default();
+    let mut message = format!(
+        "Pool {} settled, {} won: {}\nPot: {}",
+        pool.pool_id,
+        winning_option,
+        pool.question,
+        pool.pot()
+    );
+    let payouts = pool.payouts();
+    if payouts.is_empty() {
+        message.push_str(
+            format!(
+                "\nNobody picked {}, everyone gets their buy-in back",
+                winning_option
+            )
+            .as_str(),
+        );
+    }
+    for (user_id, share) in payouts {
+        message.push_str(format!("\n- {} wins {}", user_id, share).as_str());
+    }
+    message
+}
+
+fn pool_closed(pool: &Pool) -> InteractionResponse {
+    let message = format!(
+        "This pool was already settled, {} won: {}",
+        pool.winning_option.clone().unwrap_or_default(),
+        pool.simplified_string()
+    );
+    InteractionResponse::channel_message_with_source_ephemeral(&message, vec![], vec![])
+}
+
+fn split_pool_custom_id(custom_id: &str) -> Result<(PoolAction, i32), Error> {
+    let custom_id = CustomId::decode(custom_id)?;
+    let action = match (custom_id.route, custom_id.field(0)?) {
+        (Route::Pool, "join") => PoolAction::Join,
+        (Route::Pool, "pick") => PoolAction::Pick,
+        (Route::Pool, "settle") => PoolAction::Settle,
+        (Route::Pool, "winner") => PoolAction::Winner,
+        _ => return Err("custom id was not recognized".into()),
+    };
+    match custom_id.parse_field::<i32>(1) {
+        Ok(pool_id) => Ok((action, pool_id)),
+        Err(_) => Err("unable to parse a pool_id from the returned value".into()),
+    }
+}
+
+#[test]
+fn test_split_pool_custom_id() {
+    assert_eq!(
+        Ok((PoolAction::Join, 3)),
+        split_pool_custom_id("pool:1:join:3")
+    );
+    assert_eq!(
+        Ok((PoolAction::Winner, 3)),
+        split_pool_custom_id("pool:1:winner:3")
+    );
+    assert_eq!(
+        Ok((PoolAction::Pick, 3)),
+        split_pool_custom_id("pool:1:pick:3")
+    );
+    assert_eq!(
+        Err("custom id was not recognized".into()),
+        split_pool_custom_id("pool:1:leave:3")
+    );
+}
+
+#[test]
+fn test_parse_options() {
+    assert_eq!(
+        vec!["Chiefs".to_string(), "Eagles".to_string()],
+        parse_options("Chiefs\n\n  Eagles \nchiefs")
+    );
+}
@@ -1,19 +1,33 @@
 use crate::application::app::counter;
+use crate::application::parse_date::{date_range_label, parse_date_range, parse_full_date};
 use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
-use discord_api::interaction_request::ApplicationCommandInteractionData;
-use discord_api::interaction_response::InteractionResponse;
+use discord_api::interaction_request::{
+    ApplicationCommandInteractionData, InteractionObject, MessageComponentInteractionData,
+};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
 use discord_api::InteractionError;
+use pog_common::custom_id::{CustomId, Route};
 use pog_common::discord_id::DiscordId;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::pool::{Pool, PoolStatus};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{DateRange, Wager, WagerStatus};
+use std::ops::Range;
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+const PAGE_SIZE: usize = 8;
+// discord caps a message at 2000 characters, this leaves room for the heading
+pub(crate) const PAGE_LIMIT: usize = 1800;
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn list_bets(
@@ -22,41 +36,609 @@ where
     ) -> Result<InteractionResponse, Error> {
         counter("list_bets");
 
-        let option = match data.options.first() {
-            Some(option) => option,
+        let options = data.option_key_values();
+        let bettor = match options.get("bettor") {
+            Some(bettor) => bettor,
             None => return Err("bet command sent with empty options".into()),
         };
 
-        let user_id = match DiscordId::attempt_from_str(&option.value) {
-            Some(id) => id,
-            None => return Err(Error::UnresolvedDiscordUser),
+        let (user_id, username) = match DiscordId::attempt_from_str(bettor) {
+            Some(user_id) => {
+                let resolved_data = data
+                    .resolved
+                    .as_ref()
+                    .ok_or::<InteractionError>("missing resolved data".into())?;
+                let username = resolved_data
+                    .expect_user(&user_id.str_value())?
+                    .username
+                    .to_string();
+                (Some(user_id), username)
+            }
+            None => (None, bettor.trim().to_string()),
+        };
+        let filter = match options.get("status") {
+            Some(status) => BetFilter::parse(status)?,
+            None => BetFilter::Open,
+        };
+        let range = match parse_date_range(options.get("from"), options.get("to")) {
+            Ok((range, _)) => range,
+            Err(message) => {
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ))
+            }
+        };
+        let page = BetPage {
+            page: 0,
+            filter,
+            range,
+            user_id,
+            username,
+        };
+        let (content, components) = self.render_bets_page(&page).await?;
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &content,
+            components,
+            vec![],
+        ))
+    }
+
+    pub async fn turn_bets_page(
+        &self,
+        data: MessageComponentInteractionData,
+        _request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("turn_bets_page");
+
+        let (mut page, named_in) = BetPage::from_custom_id(&data.custom_id)?;
+        if let Some((wager_id, side)) = named_in {
+            let wager = match self.wager_repo.get(wager_id).await {
+                Some(wager) => wager,
+                None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
+            };
+            page.username = match side {
+                WagerSide::Offering => wager.offering,
+                WagerSide::Accepting => wager.accepting,
+            };
+        }
+        let (content, components) = self.render_bets_page(&page).await?;
+        Ok(InteractionResponse::update_message(
+            InteractionCallbackData::ephemeral_message_callback(Some(content), components, vec![]),
+        ))
+    }
+
+    async fn render_bets_page(&self, page: &BetPage) -> Result<(String, Vec<Component>), Error> {
+        let wagers = match &page.user_id {
+            Some(user_id) => {
+                self.wager_repo
+                    .search_history_by_user_id(user_id, &page.range)
+                    .await?
+            }
+            None => {
+                self.wager_repo
+                    .search_history_by_user(&page.username, &page.range)
+                    .await?
+            }
         };
-        let resolved_data = data
-            .resolved
-            .ok_or::<InteractionError>("missing resolved data".into())?;
-        let username = resolved_data.expect_user(&user_id.str_value())?;
-        let wagers = match DiscordId::attempt_from_str(&option.value) {
-            Some(user_id) => self.wager_repo.search_by_user_id(&user_id).await?,
+        let offers: Vec<Wager> = match page.filter {
+            BetFilter::Open => wagers
+                .iter()
+                .filter(|wager| wager.status == WagerStatus::OpenOffer)
+                .cloned()
+                .collect(),
+            _ => vec![],
+        };
+        let pools: Vec<Pool> = match &page.user_id {
+            Some(user_id) => self
+                .pool_repo
+                .search_by_user_id(user_id)
+                .await?
+                .into_iter()
+                .filter(|pool| {
+                    page.range.contains(&pool.time)
+                        && page.filter.matches_pool(pool, user_id)
+                        && pool.entry(user_id).is_some()
+                })
+                .collect(),
             None => vec![],
         };
-        if wagers.is_empty() {
-            let message = format!("{} has no outstanding wagers", username.username);
-            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
-                &message,
-                vec![],
-                vec![],
-            ));
-        }
-        let mut message = format!(
-            "{} has {} outstanding wagers:",
-            username.username,
-            wagers.len()
+        // the wager a name search can be picked up from on the next page
+        let named_in = wagers.first().cloned();
+        let wagers: Vec<Wager> = wagers
+            .into_iter()
+            .filter(|wager| page.filter.matches(wager, page))
+            .collect();
+        let label = date_range_label(&page.range);
+        let mut message = match wagers.is_empty() {
+            true => format!(
+                "{} has no {}wagers{}",
+                page.username,
+                page.filter.adjective(),
+                label
+            ),
+            false => format!(
+                "{} has {} {}wagers{}",
+                page.username,
+                wagers.len(),
+                page.filter.adjective(),
+                label
+            ),
+        };
+
+        // offers and pools follow the wagers on the same pages, and a page ends early rather than
+        // go past discord's message limit
+        let wager_ids: Vec<u32> = wagers.iter().map(|wager| wager.wager_id).collect();
+        let amended = self.wager_repo.amended(&wager_ids).await?;
+        let lines: Vec<BetLine> = wagers
+            .iter()
+            .map(|wager| {
+                let mut notes = vec![];
+                if page.filter != BetFilter::Open || wager.status != WagerStatus::Open {
+                    notes.push(describe_status(wager));
+                }
+                if amended.contains(&wager.wager_id) {
+                    notes.push("amended".to_string());
+                }
+                BetLine::new(BetSection::Wagers, wager.simplified_string(), &notes)
+            })
+            .chain(
+                offers
+                    .iter()
+                    .map(|offer| BetLine::new(BetSection::Offers, offer.simplified_string(), &[])),
+            )
+            .chain(pools.iter().map(|pool| {
+                let outcome = pool_outcome(pool, page);
+                BetLine::new(BetSection::Pools, pool.simplified_string(), &[outcome])
+            }))
+            .collect();
+        if lines.is_empty() {
+            return Ok((message, vec![]));
+        }
+        let pages = paginate(&lines);
+        let current = page.page.min(pages.len() - 1);
+        if pages.len() > 1 {
+            message.push_str(format!(" (page {} of {})", current + 1, pages.len()).as_str());
+        }
+        if !wagers.is_empty() {
+            message.push(':');
+        }
+        let shown = &lines[pages[current].clone()];
+        for (index, entry) in shown.iter().enumerate() {
+            if index == 0 || shown[index - 1].section != entry.section {
+                message.push_str(entry.section.heading());
+            }
+            message.push_str(&entry.text);
+        }
+
+        let mut buttons = vec![];
+        if current > 0 {
+            let custom_id = page.custom_id(current - 1, named_in.as_ref())?;
+            buttons.push(Component::button("Prev", 2, &custom_id));
+        }
+        if current + 1 < pages.len() {
+            let custom_id = page.custom_id(current + 1, named_in.as_ref())?;
+            buttons.push(Component::button("Next", 2, &custom_id));
+        }
+        let components = match buttons.is_empty() {
+            true => vec![],
+            false => vec![Component::action_row(buttons)],
+        };
+        Ok((message, components))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BetSection {
+    Wagers,
+    Offers,
+    Pools,
+}
+
+impl BetSection {
+    fn heading(&self) -> &'static str {
+        match self {
+            BetSection::Wagers => "",
+            BetSection::Offers => "\nOpen offers waiting to be taken:",
+            BetSection::Pools => "\nPools:",
+        }
+    }
+}
+
+struct BetLine {
+    section: BetSection,
+    text: String,
+}
+
+impl BetLine {
+    fn new(section: BetSection, line: String, notes: &[String]) -> Self {
+        let text = match notes.is_empty() {
+            true => format!("\n- {}", line),
+            false => format!("\n- {} ({})", line, notes.join(", ")),
+        };
+        Self { section, text }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BetFilter {
+    Open,
+    Won,
+    Lost,
+    All,
+}
+
+impl BetFilter {
+    fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "open" => Ok(BetFilter::Open),
+            "won" => Ok(BetFilter::Won),
+            "lost" => Ok(BetFilter::Lost),
+            "all" => Ok(BetFilter::All),
+            other => Err(Error::Invalid(format!("unknown bet status: {}", other))),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            BetFilter::Open => "open",
+            BetFilter::Won => "won",
+            BetFilter::Lost => "lost",
+            BetFilter::All => "all",
+        }
+    }
+
+    fn adjective(&self) -> &'static str {
+        match self {
+            BetFilter::Open => "outstanding ",
+            BetFilter::Won => "won ",
+            BetFilter::Lost => "lost ",
+            BetFilter::All => "",
+        }
+    }
+
+    fn matches(&self, wager: &Wager, page: &BetPage) -> bool {
+        match self {
+            // open offers are listed on their own, after the wagers
+            BetFilter::Open => {
+                wager.status.is_unsettled() && wager.status != WagerStatus::OpenOffer
+            }
+            BetFilter::Won => match (page.winning_status(wager), wager.won_status()) {
+                (Some(side), Some(outcome)) => side == outcome,
+                _ => false,
+            },
+            BetFilter::Lost => match (page.winning_status(wager), wager.won_status()) {
+                (Some(side), Some(outcome)) => side != outcome,
+                _ => false,
+            },
+            BetFilter::All => true,
+        }
+    }
+
+    fn matches_pool(&self, pool: &Pool, user_id: &DiscordId) -> bool {
+        let won = pool.winners().iter().any(|entry| &entry.user_id == user_id);
+        // a refunded pool was neither won nor lost, like a wager that ended in no bet
+        let decided = pool.status == PoolStatus::Settled && !pool.is_refunded();
+        match self {
+            BetFilter::Open => pool.status == PoolStatus::Open,
+            BetFilter::Won => decided && won,
+            BetFilter::Lost => decided && !won,
+            BetFilter::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BetPage {
+    page: usize,
+    filter: BetFilter,
+    range: DateRange,
+    user_id: Option<DiscordId>,
+    username: String,
+}
+
+impl BetPage {
+    fn winning_status(&self, wager: &Wager) -> Option<WagerStatus> {
+        let (offering, accepting) = match &self.user_id {
+            Some(user_id) => (
+                wager.resolved_offering_user.as_ref() == Some(user_id),
+                wager.resolved_accepting_user.as_ref() == Some(user_id),
+            ),
+            None => (
+                wager.offering.eq_ignore_ascii_case(&self.username),
+                wager.accepting_named(&self.username),
+            ),
+        };
+        match (offering, accepting) {
+            (true, _) => Some(WagerStatus::OfferingWon),
+            (_, true) => Some(WagerStatus::AcceptingWon),
+            _ => None,
+        }
+    }
+
+    // the name is free text, so it's only carried while it fits in the custom id
+    fn custom_id(&self, page: usize, named_in: Option<&Wager>) -> Result<String, Error> {
+        let date =
+            |date: Option<chrono::NaiveDate>| date.map(|d| d.to_string()).unwrap_or_default();
+        let encode = |name: &str, named_in: Option<(u32, WagerSide)>| {
+            let mut custom_id = CustomId::new(Route::BetPage)
+                .with(page)
+                .with(self.filter.as_str())
+                .with(date(self.range.from))
+                .with(date(self.range.to))
+                .with(
+                    self.user_id
+                        .as_ref()
+                        .map(|user_id| user_id.str_value())
+                        .unwrap_or_default(),
+                )
+                .with(name);
+            if let Some((wager_id, side)) = named_in {
+                custom_id = custom_id.with(wager_id).with(side.as_str());
+            }
+            custom_id.encode()
+        };
+        let error = match encode(&self.username, None) {
+            Ok(custom_id) => return Ok(custom_id),
+            Err(error) => error,
+        };
+        match (&self.user_id, named_in) {
+            // the discord id finds the wagers, the name is only shown and can be cut short
+            (Some(_), _) => {
+                let mut name: Vec<char> = self.username.chars().collect();
+                while name.pop().is_some() {
+                    if let Ok(custom_id) = encode(&name.iter().collect::<String>(), None) {
+                        return Ok(custom_id);
+                    }
+                }
+                Err(error.into())
+            }
+            // a name search picks the name up again from one of the wagers it matched
+            (None, Some(wager)) => {
+                let side = match wager.offering.eq_ignore_ascii_case(&self.username) {
+                    true => WagerSide::Offering,
+                    false => WagerSide::Accepting,
+                };
+                Ok(encode("", Some((wager.wager_id, side)))?)
+            }
+            (None, None) => Err(error.into()),
+        }
+    }
+
+    fn from_custom_id(custom_id: &str) -> Result<(Self, Option<(i32, WagerSide)>), Error> {
+        let custom_id = CustomId::decode(custom_id)?;
+        if custom_id.route != Route::BetPage {
+            return Err("custom id was not recognized".into());
+        }
+        let (page, filter, from, to, user_id, username) = (
+            custom_id.field(0)?,
+            custom_id.field(1)?,
+            custom_id.field(2)?,
+            custom_id.field(3)?,
+            custom_id.field(4)?,
+            custom_id.field(5)?,
         );
-        for wager in wagers {
-            message.push_str(format!("\n- {}", wager.simplified_string()).as_str());
+        let page = match page.parse::<usize>() {
+            Ok(page) => page,
+            Err(_) => return Err("unable to parse a page from the returned value".into()),
+        };
+        let user_id = match user_id.is_empty() {
+            true => None,
+            false => match DiscordId::from_raw_str(user_id) {
+                Some(user_id) => Some(user_id),
+                None => return Err(Error::UnresolvedDiscordUser),
+            },
+        };
+        let named_in = match custom_id.field(6) {
+            Ok(_) => Some((
+                custom_id.parse_field::<i32>(6)?,
+                WagerSide::parse(custom_id.field(7)?)?,
+            )),
+            Err(_) => None,
+        };
+        let page = BetPage {
+            page,
+            filter: BetFilter::parse(filter)?,
+            range: DateRange {
+                from: parse_full_date(from),
+                to: parse_full_date(to),
+            },
+            user_id,
+            username: username.to_string(),
+        };
+        Ok((page, named_in))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WagerSide {
+    Offering,
+    Accepting,
+}
+
+impl WagerSide {
+    fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "offering" => Ok(WagerSide::Offering),
+            "accepting" => Ok(WagerSide::Accepting),
+            other => Err(Error::Invalid(format!("unknown wager side: {}", other))),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            WagerSide::Offering => "offering",
+            WagerSide::Accepting => "accepting",
         }
-        let response =
-            InteractionResponse::channel_message_with_source_ephemeral(&message, vec![], vec![]);
-        Ok(response)
     }
 }
+
+// up to PAGE_SIZE lines a page, fewer when they're long enough to go past PAGE_LIMIT
+fn paginate(lines: &[BetLine]) -> Vec<Range<usize>> {
+    let mut pages = vec![];
+    let (mut start, mut length) = (0, 0);
+    for (index, line) in lines.iter().enumerate() {
+        let size = line.section.heading().len() + line.text.len();
+        if index > start && (index - start == PAGE_SIZE || length + size > PAGE_LIMIT) {
+            pages.push(start..index);
+            (start, length) = (index, 0);
+        }
+        length += size;
+    }
+    pages.push(start..lines.len());
+    pages
+}
+
+fn pool_outcome(pool: &Pool, page: &BetPage) -> String {
+    let pick = page
+        .user_id
+        .as_ref()
+        .and_then(|user_id| pool.entry(user_id))
+        .map(|entry| entry.pick.to_string())
+        .unwrap_or_default();
+    match &pool.winning_option {
+        None => format!("picked {}", pick),
+        Some(winning_option) => {
+            let payout = pool
+                .payouts()
+                .into_iter()
+                .find(|(user_id, _)| page.user_id.as_ref() == Some(user_id));
+            match payout {
+                Some((_, share)) => format!("picked {}, won {}", pick, share),
+                None if pool.is_refunded() => {
+                    format!("picked {}, {} won, refunded", pick, winning_option)
+                }
+                None => format!("picked {}, {} won", pick, winning_option),
+            }
+        }
+    }
+}
+
+fn describe_status(wager: &Wager) -> String {
+    match wager.status {
+        WagerStatus::Open => "open".to_string(),
+        WagerStatus::Paid => match wager.paid_outcome {
+            Some(WagerStatus::OfferingWon) => format!("{} won, paid", wager.offering),
+            Some(WagerStatus::AcceptingWon) => format!("{} won, paid", wager.accepting),
+            _ => "paid".to_string(),
+        },
+        WagerStatus::OfferingWon => format!("{} won", wager.offering),
+        WagerStatus::AcceptingWon => format!("{} won", wager.accepting),
+        WagerStatus::NoBet => "no bet".to_string(),
+        WagerStatus::AwaitingAcceptance => "awaiting acceptance".to_string(),
+        WagerStatus::Declined => "declined".to_string(),
+        WagerStatus::SettlementProposed => "settlement proposed".to_string(),
+        WagerStatus::Disputed => "disputed".to_string(),
+        WagerStatus::OpenOffer => "open offer".to_string(),
+    }
+}
+
+#[test]
+fn test_bet_page_custom_id() {
+    let page = BetPage {
+        page: 2,
+        filter: BetFilter::Won,
+        range: DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2024, 9, 1),
+            to: None,
+        },
+        user_id: Some(695398918694895710.into()),
+        username: "harx".to_string(),
+    };
+    let custom_id = page.custom_id(3, None).unwrap();
+    assert_eq!(
+        "betpage:1:3:won:2024-09-01::695398918694895710:harx",
+        custom_id
+    );
+    assert_eq!(
+        Ok((BetPage { page: 3, ..page }, None)),
+        BetPage::from_custom_id(&custom_id)
+    );
+}
+
+#[test]
+fn test_bet_page_custom_id_for_name() {
+    let page = BetPage {
+        page: 0,
+        filter: BetFilter::All,
+        range: DateRange::default(),
+        user_id: None,
+        username: "Woody".to_string(),
+    };
+    let custom_id = page.custom_id(1, None).unwrap();
+    assert_eq!("betpage:1:1:all::::Woody", custom_id);
+    assert_eq!(
+        Ok((BetPage { page: 1, ..page }, None)),
+        BetPage::from_custom_id(&custom_id)
+    );
+}
+
+#[test]
+fn test_bet_page_custom_id_with_separators() {
+    let page = BetPage {
+        page: 0,
+        filter: BetFilter::All,
+        range: DateRange::default(),
+        user_id: None,
+        username: "Woody: the | one#2".to_string(),
+    };
+    let custom_id = page.custom_id(1, None).unwrap();
+    assert_eq!(
+        Ok((BetPage { page: 1, ..page }, None)),
+        BetPage::from_custom_id(&custom_id)
+    );
+}
+
+#[test]
+fn test_bet_page_custom_id_with_long_username() {
+    let page = BetPage {
+        page: 0,
+        filter: BetFilter::Lost,
+        range: DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2024, 9, 1),
+            to: chrono::NaiveDate::from_ymd_opt(2024, 9, 30),
+        },
+        user_id: Some(695398918694895710.into()),
+        username: "Harx the #1 fan: ".repeat(5),
+    };
+    let custom_id = page.custom_id(12, None).unwrap();
+    assert!(custom_id.chars().count() <= 100);
+    let (turned, named_in) = BetPage::from_custom_id(&custom_id).unwrap();
+    assert_eq!(None, named_in);
+    assert_eq!(page.user_id, turned.user_id);
+    assert_eq!((12, page.range), (turned.page, turned.range));
+    assert!(page.username.starts_with(&turned.username));
+}
+
+#[test]
+fn test_bet_page_custom_id_with_long_name() {
+    let name = "Woody the #1 fan: ".repeat(5);
+    let page = BetPage {
+        page: 0,
+        filter: BetFilter::All,
+        range: DateRange::default(),
+        user_id: None,
+        username: name.to_lowercase(),
+    };
+    let wager = Wager {
+        wager_id: 109,
+        time: "2024-09-01T12:00:00+00:00".to_string(),
+        outcome: "Rangers repeat".to_string(),
+        ..Wager::fixture("Harx", &name, WagerStatus::Open)
+    };
+    assert!(page.custom_id(1, None).is_err());
+    let custom_id = page.custom_id(1, Some(&wager)).unwrap();
+    assert_eq!("betpage:1:1:all:::::109:accepting", custom_id);
+    assert_eq!(
+        Ok((
+            BetPage {
+                page: 1,
+                username: "".to_string(),
+                ..page
+            },
+            Some((109, WagerSide::Accepting))
+        )),
+        BetPage::from_custom_id(&custom_id)
+    );
+}
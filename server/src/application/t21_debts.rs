@@ -0,0 +1,112 @@
+use chrono::{DateTime, Duration, Utc};
+use discord_api::interaction_request::User;
+use discord_api::interaction_response::{Component, InteractionResponse, SelectMenuOption};
+
+use crate::application::app::counter;
+use crate::application::t20_list_bets::PAGE_LIMIT;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::Wager;
+
+const STALE_DEBT_DAYS: i64 = 30;
+// a select menu holds at most 25 options
+const MAX_DEBTS: usize = 25;
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn debts(&self, user: &User) -> Result<InteractionResponse, Error> {
+        counter("debts");
+
+        let user_id = match DiscordId::from_raw_str(&user.id) {
+            Some(user_id) => user_id,
+            None => return Err(Error::UnresolvedDiscordUser),
+        };
+        let wagers = self.wager_repo.search_unpaid_by_user_id(&user_id).await?;
+        if wagers.is_empty() {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "You have no unpaid bets",
+                vec![],
+                vec![],
+            ));
+        }
+
+        // owes first, and no more than fit in the message and the select menu
+        let now = Utc::now();
+        let (owes, owed): (Vec<&Wager>, Vec<&Wager>) = wagers
+            .iter()
+            .partition(|wager| wager.winner() != Some(&user_id));
+        let mut message = String::new();
+        let mut shown: Vec<&Wager> = vec![];
+        'sections: for (heading, section) in [("You owe:", owes), ("You are owed:", owed)] {
+            for (index, wager) in section.into_iter().enumerate() {
+                let mut text = String::new();
+                if index == 0 {
+                    if !message.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(heading);
+                }
+                text.push_str(format!("\n- {}", debt_line(wager, &user_id, now)).as_str());
+                if shown.len() == MAX_DEBTS || message.len() + text.len() > PAGE_LIMIT {
+                    break 'sections;
+                }
+                message.push_str(&text);
+                shown.push(wager);
+            }
+        }
+        if shown.len() < wagers.len() {
+            message.push_str(format!("\n...and {} more", wagers.len() - shown.len()).as_str());
+        }
+
+        let mut options: Vec<SelectMenuOption> = Default::default();
+        for wager in shown {
+            let value = format!("{}", wager.wager_id);
+            options.push(SelectMenuOption::new(
+                value.clone(),
+                value,
+                wager.simplified_string(),
+            ));
+        }
+        let mark_paid = Component::select_choice(
+            &CustomId::new(Route::Payment).encode()?,
+            "Mark which bet paid?",
+            options,
+        );
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![Component::action_row(vec![mark_paid])],
+            vec![],
+        ))
+    }
+}
+
+fn debt_line(wager: &Wager, user_id: &DiscordId, now: DateTime<Utc>) -> String {
+    let counterparty = match wager.counterparty(user_id) {
+        Some(counterparty) => counterparty.to_string(),
+        None if wager.resolved_offering_user.as_ref() == Some(user_id) => {
+            wager.accepting.to_string()
+        }
+        None => wager.offering.to_string(),
+    };
+    let stake = wager.stake_owed(wager.won_status()).unwrap_or(&wager.wager);
+    let line = format!("{}: {} - {}", counterparty, stake, wager.outcome);
+    match DateTime::parse_from_rfc3339(&wager.time) {
+        Ok(time) if now.signed_duration_since(time) > Duration::days(STALE_DEBT_DAYS) => {
+            format!("**{} (stale, from {})**", line, time.format("%b %e, %Y"))
+        }
+        _ => line,
+    }
+}
@@ -0,0 +1,226 @@
+use discord_api::interaction_request::{
+    ApplicationCommandInteractionData, InteractionObject, MessageComponentInteractionData, User,
+};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::t32_settle_bet::close_message;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{LedgerBalance, Stake};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn ledger(
+        &self,
+        data: ApplicationCommandInteractionData,
+        user: &User,
+    ) -> Result<InteractionResponse, Error> {
+        counter("ledger");
+
+        let command_user = match DiscordId::from_raw_str(&user.id) {
+            Some(user) => user,
+            None => return Err(Error::UnresolvedDiscordUser),
+        };
+        let options = data.option_key_values();
+        if let Some(counterparty) = options.get("settle_all") {
+            let counterparty = match DiscordId::from_raw_str(counterparty) {
+                Some(counterparty) => counterparty,
+                None => return Err(Error::UnresolvedDiscordUser),
+            };
+            return self.settle_ledger(command_user, counterparty).await;
+        }
+
+        let ledger_user = match options.get("user") {
+            Some(ledger_user) => match DiscordId::from_raw_str(ledger_user) {
+                Some(ledger_user) => ledger_user,
+                None => return Err(Error::UnresolvedDiscordUser),
+            },
+            None => command_user,
+        };
+        let balances = self.wager_repo.ledger(&ledger_user).await?;
+        if balances.is_empty() {
+            let message = format!("{} has no unpaid bets with other members", ledger_user);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        let mut message = format!("Ledger for {}:", ledger_user);
+        for balance in &balances {
+            message.push_str(format!("\n- {}", describe_balance(&ledger_user, balance)).as_str());
+        }
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![],
+            vec![],
+        ))
+    }
+
+    async fn settle_ledger(
+        &self,
+        user_id: DiscordId,
+        counterparty: DiscordId,
+    ) -> Result<InteractionResponse, Error> {
+        counter("ledger-settle_all");
+
+        let balances = self.balances_with(&user_id, &counterparty).await?;
+        // whoever is owed can call it settled, anyone who owes has to have them confirm it
+        if balances.iter().any(|balance| balance.amount < 0) {
+            return settle_up_request(&user_id, &counterparty, &balances);
+        }
+        self.settle_up(user_id, counterparty, &balances, "settled up with")
+            .await
+    }
+
+    pub async fn confirm_settle_up(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("ledger-confirm_settle_up");
+
+        let custom_id = CustomId::decode(&data.custom_id)?;
+        let (debtor, creditor) = match (
+            DiscordId::from_raw_str(custom_id.field(0)?),
+            DiscordId::from_raw_str(custom_id.field(1)?),
+        ) {
+            (Some(debtor), Some(creditor)) => (debtor, creditor),
+            _ => return Err("unable to parse the members settling up".into()),
+        };
+        let user_id = DiscordId::from_raw_str(&request.expect_member()?.expect_user()?.id);
+        if user_id.as_ref() != Some(&creditor) {
+            let message = format!("Only {} can confirm they were paid", creditor);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        // only what the creditor was shown gets settled, anything since needs a new request
+        let requested = self.balances_with(&debtor, &creditor).await?;
+        if LedgerBalance::digest(&requested) != custom_id.field(2)? {
+            close_message(&request, &self.client).await?;
+            let message = match requested.is_empty() {
+                true => format!("{} has no unpaid bets with {} left to settle", debtor, creditor),
+                false => format!(
+                    "{}, your bets with {} changed since you settled up, settle up again to send them the new balance: {}",
+                    debtor,
+                    creditor,
+                    describe_balances(&debtor, &requested)
+                ),
+            };
+            return Ok(message.into());
+        }
+        let balances = self.balances_with(&creditor, &debtor).await?;
+        close_message(&request, &self.client).await?;
+        self.settle_up(creditor, debtor, &balances, "confirmed they were paid by")
+            .await
+    }
+
+    async fn balances_with(
+        &self,
+        user_id: &DiscordId,
+        counterparty: &DiscordId,
+    ) -> Result<Vec<LedgerBalance>, Error> {
+        Ok(self
+            .wager_repo
+            .ledger(user_id)
+            .await?
+            .into_iter()
+            .filter(|balance| &balance.counterparty == counterparty)
+            .collect())
+    }
+
+    async fn settle_up(
+        &self,
+        user_id: DiscordId,
+        counterparty: DiscordId,
+        balances: &[LedgerBalance],
+        settled_with: &str,
+    ) -> Result<InteractionResponse, Error> {
+        let balances = describe_balances(&user_id, balances);
+        let settled = self.wager_repo.settle_all(&user_id, &counterparty).await?;
+        if settled.is_empty() {
+            let message = format!("You have no unpaid bets with {}", counterparty);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        let message = format!(
+            "{} {} {}, {} bets marked paid: {}",
+            user_id,
+            settled_with,
+            counterparty,
+            settled.len(),
+            balances
+        );
+        Ok(message.into())
+    }
+}
+
+fn settle_up_request(
+    debtor: &DiscordId,
+    creditor: &DiscordId,
+    balances: &[LedgerBalance],
+) -> Result<InteractionResponse, Error> {
+    let content = format!(
+        "{}, {} says they settled up with you: {}",
+        creditor,
+        debtor,
+        describe_balances(debtor, balances)
+    );
+    let confirm = CustomId::new(Route::SettleUp)
+        .with(debtor.value())
+        .with(creditor.value())
+        .with(LedgerBalance::digest(balances));
+    let components = vec![Component::action_row(vec![Component::button(
+        "Confirm paid",
+        3,
+        &confirm.encode()?,
+    )])];
+    Ok(InteractionResponse::channel_message_with_source(
+        InteractionCallbackData::message_callback(Some(content), components),
+    ))
+}
+
+fn describe_balances(user_id: &DiscordId, balances: &[LedgerBalance]) -> String {
+    balances
+        .iter()
+        .map(|balance| describe_balance(user_id, balance))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn describe_balance(user_id: &DiscordId, balance: &LedgerBalance) -> String {
+    let amount = Stake::Money {
+        amount: balance.amount.abs(),
+        currency: balance.currency,
+    };
+    match balance.amount {
+        0 => format!(
+            "{} and {} are even in {}",
+            user_id,
+            balance.counterparty,
+            balance.currency.code()
+        ),
+        a if a > 0 => format!("{} owes {} {}", balance.counterparty, user_id, amount),
+        _ => format!("{} owes {} {}", user_id, balance.counterparty, amount),
+    }
+}
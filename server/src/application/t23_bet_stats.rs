@@ -0,0 +1,199 @@
+use chrono::NaiveDate;
+use discord_api::interaction_request::ApplicationCommandInteractionData;
+use discord_api::interaction_response::{
+    Embed, InteractionCallbackData, InteractionResponse, MessageCallbackData,
+};
+
+use crate::application::app::counter;
+use crate::application::parse_date::parse_date_range;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{BettorRecord, DateRange, Stake};
+
+const LEADERBOARD_SIZE: usize = 25;
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn bet_stats(
+        &self,
+        data: ApplicationCommandInteractionData,
+    ) -> Result<InteractionResponse, Error> {
+        counter("bet_stats");
+
+        let options = data.option_key_values();
+        let (range, label) = match stats_range(
+            options.get("season"),
+            options.get("from"),
+            options.get("to"),
+        ) {
+            Ok(range) => range,
+            Err(message) => {
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ))
+            }
+        };
+        match options.get("user") {
+            Some(user_id) => match DiscordId::from_raw_str(user_id) {
+                Some(user_id) => self.personal_record(user_id, &range, &label).await,
+                None => Err(Error::UnresolvedDiscordUser),
+            },
+            None => self.leaderboard(&range, &label).await,
+        }
+    }
+
+    async fn personal_record(
+        &self,
+        user_id: DiscordId,
+        range: &DateRange,
+        label: &str,
+    ) -> Result<InteractionResponse, Error> {
+        let wagers = self
+            .wager_repo
+            .search_settled_by_user_id(&user_id, range)
+            .await?;
+        let message = match BettorRecord::tally(&wagers)
+            .into_iter()
+            .find(|record| record.user == user_id)
+        {
+            Some(record) => format!(
+                "{}'s record{}: {}",
+                user_id,
+                label,
+                describe_record(&record)
+            ),
+            None => format!("{} has no settled bets{}", user_id, label),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![],
+            vec![],
+        ))
+    }
+
+    async fn leaderboard(
+        &self,
+        range: &DateRange,
+        label: &str,
+    ) -> Result<InteractionResponse, Error> {
+        let wagers = self.wager_repo.search_settled(range).await?;
+        let records = BettorRecord::tally(&wagers);
+        if records.is_empty() {
+            let message = format!("No settled bets{}", label);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+
+        let lines: Vec<String> = records
+            .iter()
+            .take(LEADERBOARD_SIZE)
+            .enumerate()
+            .map(|(rank, record)| {
+                format!("{}. {} {}", rank + 1, record.user, describe_record(record))
+            })
+            .collect();
+        let mut embed = Embed::rich();
+        embed.title = Some(format!("Betting leaderboard{}", label));
+        embed.description = Some(lines.join("\n"));
+        let flags: Option<u32> = Some(discord_api::interaction_response::message_flags::EPHEMERAL);
+        let data = MessageCallbackData {
+            tts: false,
+            content: None,
+            embeds: vec![embed],
+            components: vec![],
+            flags,
+            allowed_mentions: vec![],
+            attachments: vec![],
+        };
+        Ok(InteractionResponse::channel_message_with_source(
+            InteractionCallbackData::Message(data),
+        ))
+    }
+}
+
+fn describe_record(record: &BettorRecord) -> String {
+    let mut description = format!(
+        "{} won, {} lost, {} no bet",
+        record.won, record.lost, record.no_bet
+    );
+    if !record.winnings.is_empty() {
+        let winnings: Vec<String> = record
+            .winnings
+            .iter()
+            .map(|(currency, amount)| {
+                let stake = Stake::Money {
+                    amount: amount.abs(),
+                    currency: *currency,
+                };
+                match amount {
+                    a if *a < 0 => format!("-{}", stake),
+                    _ => format!("+{}", stake),
+                }
+            })
+            .collect();
+        description.push_str(format!(", net {}", winnings.join(" ")).as_str());
+    }
+    description
+}
+
+fn stats_range(
+    season: Option<&String>,
+    from: Option<&String>,
+    to: Option<&String>,
+) -> Result<(DateRange, String), String> {
+    if let Some(season) = season {
+        if from.is_some() || to.is_some() {
+            return Err("Use either a season or a date range, not both".to_string());
+        }
+        let year = match season.trim().parse::<i32>() {
+            Ok(year) => year,
+            Err(_) => {
+                return Err(format!(
+                    "'{}' is not a season, use a year like 2024",
+                    season
+                ))
+            }
+        };
+        // a season starts with the football season in September and runs for a year
+        let range = DateRange {
+            from: NaiveDate::from_ymd_opt(year, 9, 1),
+            to: NaiveDate::from_ymd_opt(year + 1, 8, 31),
+        };
+        return Ok((range, format!(" for the {} season", year)));
+    }
+    parse_date_range(from, to)
+}
+
+#[test]
+fn test_stats_range() {
+    let season = Some("2024".to_string());
+    let from = Some("10/01/2024".to_string());
+    let (range, label) = stats_range(season.as_ref(), None, None).unwrap();
+    assert_eq!(NaiveDate::from_ymd_opt(2024, 9, 1), range.from);
+    assert_eq!(NaiveDate::from_ymd_opt(2025, 8, 31), range.to);
+    assert_eq!(" for the 2024 season", label);
+    let (range, label) = stats_range(None, from.as_ref(), None).unwrap();
+    assert_eq!(NaiveDate::from_ymd_opt(2024, 10, 1), range.from);
+    assert_eq!(None, range.to);
+    assert_eq!(" since 2024-10-01", label);
+    assert!(stats_range(season.as_ref(), from.as_ref(), None).is_err());
+    assert!(stats_range(None, Some(&"someday".to_string()), None).is_err());
+    assert!(stats_range(Some(&"next year".to_string()), None, None).is_err());
+}
@@ -0,0 +1,176 @@
+use discord_api::interaction_request::{ApplicationCommandInteractionData, GuildMember};
+use discord_api::interaction_response::InteractionResponse;
+
+use crate::application::app::counter;
+use crate::application::t20_list_bets::PAGE_LIMIT;
+use crate::application::t30_settle_bet::wager_matches;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{DateRange, Wager, WagerAction, WagerEvent};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn bet_history(
+        &self,
+        data: ApplicationCommandInteractionData,
+        member: &GuildMember,
+    ) -> Result<InteractionResponse, Error> {
+        counter("bet_history");
+
+        let search = match data.option_key_values().get("wager") {
+            Some(search) => search.to_string(),
+            None => return Err("bet history command sent with empty options".into()),
+        };
+        let wager = match search.parse::<i32>() {
+            Ok(wager_id) => self.wager_repo.get(wager_id).await,
+            Err(_) => self
+                .wager_history(member)
+                .await?
+                .into_iter()
+                .find(|wager| wager_matches(wager, &search)),
+        };
+        let wager = match wager {
+            Some(wager) => wager,
+            None => {
+                let message = format!("No bet matches \"{}\"", search);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+
+        let events = self.wager_repo.events(wager.wager_id as i32).await?;
+        let message = render_timeline(&wager, &events);
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![],
+            vec![],
+        ))
+    }
+
+    pub(crate) async fn wager_history(&self, member: &GuildMember) -> Result<Vec<Wager>, Error> {
+        match DiscordId::from_raw_str(&member.expect_user()?.id) {
+            Some(user_id) => Ok(self
+                .wager_repo
+                .search_history_by_user_id(&user_id, &DateRange::default())
+                .await?),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+// the most recent events are kept when the whole history would go past discord's message limit
+fn render_timeline(wager: &Wager, events: &[WagerEvent]) -> String {
+    let mut lines = vec![];
+    if !events
+        .iter()
+        .any(|event| event.action == WagerAction::Created)
+    {
+        lines.push(format!(
+            "\n- {}{} placed the bet",
+            timestamp(&wager.time),
+            wager.offering
+        ));
+    }
+    for event in events {
+        let actor = match (&event.actor, event.action) {
+            (Some(actor), _) => actor.to_string(),
+            (None, WagerAction::Expired) => "the offer".to_string(),
+            (None, _) => "someone".to_string(),
+        };
+        let mut line = format!(
+            "\n- {}{} {}",
+            timestamp(&event.time),
+            actor,
+            describe_action(event.action)
+        );
+        if !event.detail.is_empty() {
+            line.push_str(format!(": {}", event.detail).as_str());
+        }
+        lines.push(line);
+    }
+
+    let mut message = format!("History of bet {}: {}", wager.wager_id, wager);
+    let mut length = message.len();
+    let shown = lines
+        .iter()
+        .rev()
+        .take_while(|line| {
+            length += line.len();
+            length <= PAGE_LIMIT
+        })
+        .count();
+    let hidden = lines.len() - shown;
+    if hidden > 0 {
+        message.push_str(format!("\n- {} earlier events not shown", hidden).as_str());
+    }
+    for line in &lines[hidden..] {
+        message.push_str(line);
+    }
+    message
+}
+
+fn timestamp(time: &str) -> String {
+    match chrono::DateTime::parse_from_rfc3339(time) {
+        Ok(time) => format!("<t:{}:f> ", time.timestamp()),
+        Err(_) => "".to_string(),
+    }
+}
+
+fn describe_action(action: WagerAction) -> &'static str {
+    match action {
+        WagerAction::Created => "placed the bet",
+        WagerAction::Accepted => "accepted",
+        WagerAction::Declined => "declined",
+        WagerAction::Withdrawn => "withdrew the offer",
+        WagerAction::SettlementProposed => "proposed the result",
+        WagerAction::Settled => "settled the bet",
+        WagerAction::Disputed => "disputed the result",
+        WagerAction::Paid => "marked it paid",
+        WagerAction::Voided => "voided the bet",
+        WagerAction::Edited => "edited the bet",
+        WagerAction::Expired => "expired",
+        WagerAction::PaymentSent => "said they paid",
+        WagerAction::EditRejected => "rejected an edit",
+        WagerAction::EditWithdrawn => "withdrew an edit",
+    }
+}
+
+#[test]
+fn test_render_long_timeline() {
+    use pog_common::wager::WagerStatus;
+    let wager = Wager {
+        wager_id: 109,
+        ..Wager::fixture("Harx", "Woody", WagerStatus::Open)
+    };
+    let edit = |n: usize| {
+        WagerEvent::new(
+            109,
+            Some(695398918694895710.into()),
+            WagerAction::Edited,
+            Some(WagerStatus::Open),
+            WagerStatus::Open,
+        )
+        .with_detail(&format!("edit {} {}", n, "x".repeat(200)))
+    };
+    let events: Vec<WagerEvent> = (0..12).map(edit).collect();
+    let message = render_timeline(&wager, &events);
+    assert!(message.len() <= 2000);
+    assert!(message.contains("earlier events not shown"));
+    assert!(message.contains("edit 11 "));
+    assert!(!message.contains("edit 0 "));
+}
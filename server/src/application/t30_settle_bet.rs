@@ -1,33 +1,55 @@
-use discord_api::interaction_request::{ApplicationCommandInteractionData, User};
+use discord_api::interaction_request::{ApplicationCommandInteractionData, GuildMember};
 use discord_api::interaction_response::{Component, InteractionResponse, SelectMenuOption};
 
 use crate::application::app::counter;
 use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
 use pog_common::discord_id::DiscordId;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
-use pog_common::wager::Wager;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{Wager, WagerStatus};
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+// discord rejects select menus with more than 25 options
+const MAX_SETTLE_OPTIONS: usize = 25;
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn pay_bet(
         &self,
-        _data: ApplicationCommandInteractionData,
-        user: &User,
+        data: ApplicationCommandInteractionData,
+        member: &GuildMember,
     ) -> Result<InteractionResponse, Error> {
         counter("pay_bet");
 
-        let wagers = match DiscordId::from_raw_str(&user.id) {
-            Some(user_id) => self.wager_repo.search_by_user_id(&user_id).await?,
-            None => vec![],
-        };
+        let mut wagers = self.settleable_wagers(member).await?;
+        if let Some(search) = data.option_key_values().get("wager") {
+            if let Ok(wager_id) = search.parse::<i32>() {
+                return self.settle_choices(wager_id, member).await;
+            }
+            wagers.retain(|wager| wager_matches(wager, search));
+            match wagers.as_slice() {
+                [] => {
+                    let message = format!("None of your open bets match \"{}\"", search);
+                    return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                        &message,
+                        vec![],
+                        vec![],
+                    ));
+                }
+                [wager] => return self.settle_choices(wager.wager_id as i32, member).await,
+                _ => {}
+            }
+        }
         if wagers.is_empty() {
             Ok(InteractionResponse::channel_message_with_source_ephemeral(
                 "You have no open bets",
@@ -35,21 +57,60 @@ where
                 vec![],
             ))
         } else {
-            Ok(open_select_wager_for_close_choices(wagers))
+            open_select_wager_for_close_choices(wagers)
+        }
+    }
+
+    pub(crate) async fn settleable_wagers(
+        &self,
+        member: &GuildMember,
+    ) -> Result<Vec<Wager>, Error> {
+        let mut wagers = match DiscordId::from_raw_str(&member.expect_user()?.id) {
+            Some(user_id) => self.wager_repo.search_by_user_id(&user_id).await?,
+            None => vec![],
+        };
+        if member.is_administrator() {
+            let disputed = self
+                .wager_repo
+                .search_by_status(WagerStatus::Disputed)
+                .await?;
+            wagers.extend(disputed);
         }
+        Ok(wagers)
     }
 }
 
-pub fn open_select_wager_for_close_choices(wagers: Vec<Wager>) -> InteractionResponse {
+pub(crate) fn wager_matches(wager: &Wager, search: &str) -> bool {
+    wager
+        .simplified_string()
+        .to_lowercase()
+        .contains(search.trim().to_lowercase().as_str())
+}
+
+pub fn open_select_wager_for_close_choices(
+    wagers: Vec<Wager>,
+) -> Result<InteractionResponse, Error> {
+    let message = if wagers.len() > MAX_SETTLE_OPTIONS {
+        format!(
+            "Close out a bet\n...and {} more, use the `wager` option to find them",
+            wagers.len() - MAX_SETTLE_OPTIONS
+        )
+    } else {
+        "Close out a bet".to_string()
+    };
     let mut options: Vec<SelectMenuOption> = Default::default();
-    for wager in wagers {
+    for wager in wagers.into_iter().take(MAX_SETTLE_OPTIONS) {
         let value = format!("{}", wager.wager_id);
         let description = wager.simplified_string();
         options.push(SelectMenuOption::new(value.clone(), value, description));
     }
-    let close_bet = Component::select_choice("settle", "Close which bet?", options);
-    InteractionResponse::select_response(
-        "Close out a bet".to_string(),
+    let close_bet = Component::select_choice(
+        &CustomId::new(Route::Settle).encode()?,
+        "Close which bet?",
+        options,
+    );
+    Ok(InteractionResponse::select_response(
+        message,
         vec![Component::action_row(vec![close_bet])],
-    )
+    ))
 }
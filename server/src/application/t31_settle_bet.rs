@@ -1,19 +1,26 @@
 use crate::application::Application;
-use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_request::{
+    GuildMember, InteractionObject, MessageComponentInteractionData,
+};
 use discord_api::interaction_response::{Component, InteractionResponse};
 
-use crate::application::t32_settle_bet::close_message;
+use crate::application::t32_settle_bet::{close_message, conflict_response, not_a_participant};
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
 use pog_common::wager::WagerStatus;
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn bet_selected(
@@ -34,33 +41,52 @@ where
                 return Err("unable to parse a wager_id from the returned value".into());
             }
         };
+        let response = self
+            .settle_choices(wager_id, request.expect_member()?)
+            .await?;
+
+        close_message(&request, &self.client).await?;
+        Ok(response)
+    }
+
+    pub(crate) async fn settle_choices(
+        &self,
+        wager_id: i32,
+        member: &GuildMember,
+    ) -> Result<InteractionResponse, Error> {
         let wager = match self.wager_repo.get(wager_id).await {
             Some(wager) => wager,
             None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
         };
-        if wager.status != WagerStatus::Open {
-            return Err(Error::Invalid(format!("wager {} is not open", wager_id)));
+        let user_id = DiscordId::from_raw_str(&member.expect_user()?.id);
+        let is_participant = user_id.as_ref().is_some_and(|id| wager.is_participant(id));
+        if !is_participant && !member.is_administrator() {
+            return Ok(not_a_participant());
+        }
+        match wager.status {
+            WagerStatus::Open => {}
+            WagerStatus::Disputed if member.is_administrator() => {}
+            _ => return Ok(conflict_response(&wager)),
         }
 
         let offering_won = format!("{} won", wager.offering);
         let accepting_won = format!("{} won", wager.accepting);
         let content = format!("Closing: {}", wager.simplified_string());
-        let response = InteractionResponse::channel_message_with_source_ephemeral(
+        let outcome = |designation: &str| {
+            CustomId::new(Route::Outcome)
+                .with(designation)
+                .with(wager_id)
+                .encode()
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
             &content,
             vec![Component::action_row(vec![
-                Component::button(&offering_won, 1, format!("offering_{}", wager_id).as_str()),
-                Component::button(
-                    &accepting_won,
-                    1,
-                    format!("accepting_{}", wager_id).as_str(),
-                ),
-                Component::button("No bet", 1, format!("nobet_{}", wager_id).as_str()),
-                Component::button("Cancel", 2, format!("cancel_{}", wager_id).as_str()),
+                Component::button(&offering_won, 1, &outcome("offering")?),
+                Component::button(&accepting_won, 1, &outcome("accepting")?),
+                Component::button("No bet", 1, &outcome("nobet")?),
+                Component::button("Cancel", 2, &outcome("cancel")?),
             ])],
             vec![],
-        );
-
-        close_message(&request, &self.client).await?;
-        Ok(response)
+        ))
     }
 }
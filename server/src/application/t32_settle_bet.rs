@@ -2,17 +2,22 @@ use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
 use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
-use discord_api::interaction_response::InteractionResponse;
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
 use discord_api::InteractionError;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
-use pog_common::wager::WagerStatus;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{SettlementProposal, Wager, WagerAction, WagerEvent, WagerStatus};
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn settle_bet(
@@ -36,7 +41,7 @@ where
             None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
         };
 
-        wager.status = match designator {
+        let outcome = match designator {
             Designation::Offering => WagerStatus::OfferingWon,
             Designation::Accepting => WagerStatus::AcceptingWon,
             Designation::NoBet => WagerStatus::NoBet,
@@ -50,26 +55,189 @@ where
             }
         };
 
-        let message = match wager.status {
-            WagerStatus::OfferingWon => {
-                format!("{} won: {}", wager.offering, wager)
-            }
-            WagerStatus::AcceptingWon => {
-                format!("{} won: {}", wager.accepting, wager)
+        let member = request.expect_member()?;
+        let is_admin = member.is_administrator();
+        let user_id = DiscordId::from_raw_str(&member.expect_user()?.id);
+        let is_participant = user_id.as_ref().is_some_and(|id| wager.is_participant(id));
+        if !is_participant && !is_admin {
+            return Ok(not_a_participant());
+        }
+        match wager.status {
+            WagerStatus::Open => {}
+            WagerStatus::Disputed if is_admin => {}
+            _ => return Ok(conflict_response(&wager)),
+        }
+
+        let previous_status = wager.status;
+        let counterparty = user_id
+            .as_ref()
+            .and_then(|id| wager.counterparty(id).cloned());
+        let (action, response) = match (wager.status, &user_id, counterparty) {
+            (WagerStatus::Open, Some(proposed_by), Some(counterparty)) => {
+                wager.status = WagerStatus::SettlementProposed;
+                wager.proposal = Some(SettlementProposal {
+                    outcome,
+                    proposed_by: proposed_by.clone(),
+                });
+                (
+                    WagerAction::SettlementProposed,
+                    proposal_message(&counterparty, proposed_by, &wager, outcome)?,
+                )
             }
-            WagerStatus::NoBet => format!("No bet: {}", wager),
-            WagerStatus::Paid => format!("No bet: {}", wager),
-            WagerStatus::Open => {
-                return Err(Error::Invalid(format!("wager {} is still open", wager_id)))
+            _ => {
+                wager.status = outcome;
+                wager.proposal = None;
+                (settled_action(outcome), settled_response(&wager, outcome)?)
             }
         };
 
+        let event = WagerEvent::new(
+            wager.wager_id,
+            user_id,
+            action,
+            Some(previous_status),
+            wager.status,
+        )
+        .with_detail(&outcome_label(&wager, outcome));
+        match self
+            .wager_repo
+            .update_status(wager_id, previous_status, &wager, &[event])
+            .await
+        {
+            Err(pog_common::error::Error::Conflict(current)) => {
+                return Ok(conflict_response(&current))
+            }
+            result => result?,
+        }
         close_message(&request, &self.client).await?;
-        self.wager_repo.update_status(wager_id, &wager).await?;
 
-        Ok(message.into())
+        Ok(response)
+    }
+}
+
+pub(crate) fn outcome_label(wager: &Wager, outcome: WagerStatus) -> String {
+    match outcome {
+        WagerStatus::OfferingWon => format!("{} won", wager.offering),
+        WagerStatus::AcceptingWon => format!("{} won", wager.accepting),
+        _ => "No bet".to_string(),
     }
 }
+
+pub(crate) fn settled_action(outcome: WagerStatus) -> WagerAction {
+    match outcome {
+        WagerStatus::NoBet => WagerAction::Voided,
+        _ => WagerAction::Settled,
+    }
+}
+
+fn settled_message(wager: &Wager, outcome: WagerStatus) -> Result<String, Error> {
+    let message = match outcome {
+        WagerStatus::OfferingWon => format!("{} won: {}", wager.offering, wager),
+        WagerStatus::AcceptingWon => format!("{} won: {}", wager.accepting, wager),
+        WagerStatus::NoBet => return Ok(format!("No bet: {}", wager)),
+        other => {
+            return Err(Error::Invalid(format!(
+                "wager {} cannot be settled as {:?}",
+                wager.wager_id, other
+            )))
+        }
+    };
+    match wager.debt_string(outcome) {
+        Some(debt) => Ok(format!("{}\n{}", message, debt)),
+        None => Ok(message),
+    }
+}
+
+pub(crate) fn settled_response(
+    wager: &Wager,
+    outcome: WagerStatus,
+) -> Result<InteractionResponse, Error> {
+    let content = settled_message(wager, outcome)?;
+    if outcome == WagerStatus::NoBet {
+        return Ok(content.into());
+    }
+    let components = vec![Component::action_row(vec![Component::button(
+        "Mark paid",
+        1,
+        &CustomId::new(Route::Payment)
+            .with(wager.wager_id)
+            .encode()?,
+    )])];
+    Ok(InteractionResponse::channel_message_with_source(
+        InteractionCallbackData::message_callback(Some(content), components),
+    ))
+}
+
+pub(crate) fn conflict_response(wager: &Wager) -> InteractionResponse {
+    let message = match (wager.status, &wager.proposal) {
+        (WagerStatus::OfferingWon | WagerStatus::AcceptingWon | WagerStatus::NoBet, _) => format!(
+            "This bet was already settled as {}: {}",
+            outcome_label(wager, wager.status),
+            wager
+        ),
+        (WagerStatus::Paid, _) => match wager.paid_outcome {
+            Some(outcome) => format!(
+                "This bet was already settled as {} and paid: {}",
+                outcome_label(wager, outcome),
+                wager
+            ),
+            None => format!("This bet was already paid: {}", wager),
+        },
+        (WagerStatus::SettlementProposed, Some(proposal)) => format!(
+            "{} already proposed the result {}: {}",
+            proposal.proposed_by,
+            outcome_label(wager, proposal.outcome),
+            wager
+        ),
+        (WagerStatus::Disputed, _) => {
+            format!(
+                "The result of this bet is disputed, an admin will settle it: {}",
+                wager
+            )
+        }
+        (WagerStatus::AwaitingAcceptance | WagerStatus::Declined, _) => {
+            format!("This bet was never accepted: {}", wager)
+        }
+        _ => format!("This bet was changed by someone else, try again: {}", wager),
+    };
+    InteractionResponse::channel_message_with_source_ephemeral(&message, vec![], vec![])
+}
+
+pub(crate) fn not_a_participant() -> InteractionResponse {
+    InteractionResponse::channel_message_with_source_ephemeral(
+        "Only the people in this bet or an admin can settle it",
+        vec![],
+        vec![],
+    )
+}
+
+fn proposal_message(
+    counterparty: &DiscordId,
+    proposed_by: &DiscordId,
+    wager: &Wager,
+    outcome: WagerStatus,
+) -> Result<InteractionResponse, Error> {
+    let content = format!(
+        "{}, {} says the result is: {}\n{}",
+        counterparty,
+        proposed_by,
+        outcome_label(wager, outcome),
+        wager
+    );
+    let verify = |answer: &str| {
+        CustomId::new(Route::Verify)
+            .with(answer)
+            .with(wager.wager_id)
+    };
+    let components = vec![Component::action_row(vec![
+        Component::button("Confirm", 3, &verify("confirm").encode()?),
+        Component::button("Dispute", 4, &verify("dispute").encode()?),
+    ])];
+    Ok(InteractionResponse::channel_message_with_source(
+        InteractionCallbackData::message_callback(Some(content), components),
+    ))
+}
+
 pub(crate) async fn close_message<C: DiscordClient>(
     request: &InteractionObject,
     client: &C,
@@ -93,26 +261,15 @@ enum Designation {
 }
 
 fn split_custom_id(custom_id: &str) -> Result<(Designation, String), Error> {
-    let custom_id = custom_id.to_string();
-    match custom_id.find('_') {
-        Some(pos) => {
-            let designator: String = custom_id.chars().take(pos).collect();
-            let designator = match designator.as_str() {
-                "offering" => Designation::Offering,
-                "accepting" => Designation::Accepting,
-                "nobet" => Designation::NoBet,
-                "cancel" => Designation::Cancel,
-                &_ => return Err("custom id was not recognized".into()),
-            };
-            let id: String = custom_id
-                .chars()
-                .skip(pos + 1)
-                .take_while(|_| true)
-                .collect();
-            Ok((designator, id))
-        }
-        None => Err("custom id was not recognized".into()),
-    }
+    let custom_id = CustomId::decode(custom_id)?;
+    let designator = match (custom_id.route, custom_id.field(0)?) {
+        (Route::Outcome, "offering") => Designation::Offering,
+        (Route::Outcome, "accepting") => Designation::Accepting,
+        (Route::Outcome, "nobet") => Designation::NoBet,
+        (Route::Outcome, "cancel") => Designation::Cancel,
+        _ => return Err("custom id was not recognized".into()),
+    };
+    Ok((designator, custom_id.field(1)?.to_string()))
 }
 
 #[test]
@@ -121,8 +278,18 @@ fn test_split_custom_id() {
         Ok((Designation::Cancel, "127".to_string())),
         split_custom_id("cancel_127")
     );
+    assert_eq!(
+        Ok((Designation::Offering, "127".to_string())),
+        split_custom_id("outcome:1:offering:127")
+    );
     assert_eq!(
         Err("custom id was not recognized".into()),
+        split_custom_id("answer:1:offering:127")
+    );
+    assert_eq!(
+        Err(Error::Invalid(
+            "unrecognized custom id: not right".to_string()
+        )),
         split_custom_id("not right")
     );
 }
@@ -0,0 +1,164 @@
+use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_response::InteractionResponse;
+
+use crate::application::app::counter;
+use crate::application::t32_settle_bet::{
+    close_message, conflict_response, outcome_label, settled_action, settled_response,
+};
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{Wager, WagerAction, WagerEvent, WagerStatus};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn confirm_settlement(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("confirm_settlement");
+
+        let (confirmed, wager_id) = split_verify_custom_id(&data.custom_id)?;
+        let mut wager = match self.wager_repo.get(wager_id).await {
+            Some(wager) => wager,
+            None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
+        };
+        let proposal = match (wager.status, wager.proposal.take()) {
+            (WagerStatus::SettlementProposed, Some(proposal)) => proposal,
+            _ => return Ok(conflict_response(&wager)),
+        };
+
+        let user_id = DiscordId::from_raw_str(&request.expect_member()?.expect_user()?.id);
+        let counterparty = match wager.counterparty(&proposal.proposed_by) {
+            Some(counterparty) if user_id.as_ref() == Some(counterparty) => counterparty.clone(),
+            Some(counterparty) => {
+                let message = format!("Only {} can confirm or dispute this result", counterparty);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ));
+            }
+            None => {
+                return Err(Error::Invalid(format!(
+                    "wager {} has no counterparty",
+                    wager_id
+                )))
+            }
+        };
+
+        let (action, mut response) = match confirmed {
+            true => {
+                wager.status = proposal.outcome;
+                (
+                    settled_action(proposal.outcome),
+                    settled_response(&wager, proposal.outcome)?,
+                )
+            }
+            false => {
+                wager.status = WagerStatus::Disputed;
+                let message = format!(
+                    "{} disputed the result, an admin will settle: {}",
+                    counterparty, wager
+                );
+                (WagerAction::Disputed, message.into())
+            }
+        };
+
+        let event = WagerEvent::new(
+            wager.wager_id,
+            Some(counterparty.clone()),
+            action,
+            Some(WagerStatus::SettlementProposed),
+            wager.status,
+        )
+        .with_detail(&outcome_label(&wager, proposal.outcome));
+        match self
+            .wager_repo
+            .update_status(wager_id, WagerStatus::SettlementProposed, &wager, &[event])
+            .await
+        {
+            Err(pog_common::error::Error::Conflict(current)) => {
+                return Ok(conflict_response(&current))
+            }
+            result => result?,
+        }
+        if wager.status == WagerStatus::Disputed && !self.notify_admins(&wager).await? {
+            let message = format!(
+                "{} disputed the result, no admin channel is set up so ask an admin to settle it with `/{}`: {}",
+                counterparty,
+                pog_common::SETTLE_BET_COMMAND,
+                wager
+            );
+            response = message.into();
+        }
+        close_message(&request, &self.client).await?;
+
+        Ok(response)
+    }
+
+    // falls back to the betting channel, false when neither is set up
+    async fn notify_admins(&self, wager: &Wager) -> Result<bool, Error> {
+        let settings = self.admin_repo.get().await?;
+        let channel = match (
+            settings.admin_channel.as_str(),
+            settings.betting_channel.as_str(),
+        ) {
+            ("", "") => return Ok(false),
+            ("", betting_channel) => betting_channel,
+            (admin_channel, _) => admin_channel,
+        };
+        let message = format!(
+            "Wager {} is disputed, settle it with `/{}`: {}",
+            wager.wager_id,
+            pog_common::SETTLE_BET_COMMAND,
+            wager
+        );
+        self.client
+            .create_message(channel, &message, vec![])
+            .await?;
+        Ok(true)
+    }
+}
+
+fn split_verify_custom_id(custom_id: &str) -> Result<(bool, i32), Error> {
+    let custom_id = CustomId::decode(custom_id)?;
+    let confirmed = match (custom_id.route, custom_id.field(0)?) {
+        (Route::Verify, "confirm") => true,
+        (Route::Verify, "dispute") => false,
+        _ => return Err("custom id was not recognized".into()),
+    };
+    match custom_id.parse_field::<i32>(1) {
+        Ok(wager_id) => Ok((confirmed, wager_id)),
+        Err(_) => Err("unable to parse a wager_id from the returned value".into()),
+    }
+}
+
+#[test]
+fn test_split_verify_custom_id() {
+    assert_eq!(
+        Ok((true, 109)),
+        split_verify_custom_id("verify:1:confirm:109")
+    );
+    assert_eq!(
+        Ok((false, 109)),
+        split_verify_custom_id("verify:1:dispute:109")
+    );
+    assert_eq!(
+        Err("custom id was not recognized".into()),
+        split_verify_custom_id("verify:1:maybe:109")
+    );
+}
@@ -0,0 +1,156 @@
+use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::t32_settle_bet::{close_message, conflict_response};
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::{Wager, WagerAction, WagerEvent, WagerStatus};
+
+// marks the button on a payment sent message, which goes away once the winner confirms
+const CONFIRM_PAID: &str = "confirm";
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn mark_paid(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("mark_paid");
+
+        let custom_id = CustomId::decode(&data.custom_id)?;
+        let confirming = custom_id.field(1) == Ok(CONFIRM_PAID);
+        let wager_id = match data.values.first() {
+            Some(wager_id) => wager_id.to_string(),
+            None => custom_id.field(0)?.to_string(),
+        };
+        let wager_id = match wager_id.parse::<i32>() {
+            Ok(wager_id) => wager_id,
+            Err(_) => {
+                return Err("unable to parse a wager_id from the returned value".into());
+            }
+        };
+        let mut wager = match self.wager_repo.get(wager_id).await {
+            Some(wager) => wager,
+            None => return Err(Error::Invalid(format!("wager {} not found", wager_id))),
+        };
+        let won_status = match wager.status {
+            WagerStatus::OfferingWon | WagerStatus::AcceptingWon => wager.status,
+            WagerStatus::Paid => return Ok(conflict_response(&wager)),
+            _ => {
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    "This wager is not waiting on payment",
+                    vec![],
+                    vec![],
+                ))
+            }
+        };
+
+        let user_id = DiscordId::from_raw_str(&request.expect_member()?.expect_user()?.id);
+        let user_id = match user_id {
+            Some(user_id) if wager.is_participant(&user_id) => user_id,
+            _ => {
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    "Only the people in this bet can mark it paid",
+                    vec![],
+                    vec![],
+                ))
+            }
+        };
+
+        // the loser only says they sent it, the bet is paid once the winner confirms; without a
+        // winner on discord there is nobody to confirm, so the loser's word has to do
+        let message = match wager.winner() {
+            Some(winner) if winner == &user_id => format!("{} was paid: {}", user_id, wager),
+            Some(winner) => {
+                let winner = winner.clone();
+                return self
+                    .payment_sent(&wager, user_id, &winner, won_status)
+                    .await;
+            }
+            None => format!("{} paid up: {}", user_id, wager),
+        };
+        wager.status = WagerStatus::Paid;
+        wager.paid_outcome = Some(won_status);
+        let paid = WagerEvent::new(
+            wager.wager_id,
+            Some(user_id),
+            WagerAction::Paid,
+            Some(won_status),
+            wager.status,
+        );
+        match self
+            .wager_repo
+            .update_status(wager_id, won_status, &wager, &[paid])
+            .await
+        {
+            Err(pog_common::error::Error::Conflict(current)) => {
+                return Ok(conflict_response(&current))
+            }
+            result => result?,
+        }
+
+        if confirming {
+            close_message(&request, &self.client).await?;
+        }
+        Ok(message.into())
+    }
+
+    async fn payment_sent(
+        &self,
+        wager: &Wager,
+        user_id: DiscordId,
+        winner: &DiscordId,
+        won_status: WagerStatus,
+    ) -> Result<InteractionResponse, Error> {
+        let already_sent = self
+            .wager_repo
+            .events(wager.wager_id as i32)
+            .await?
+            .last()
+            .is_some_and(|event| event.action == WagerAction::PaymentSent);
+        if already_sent {
+            let message = format!("Waiting on {} to confirm they were paid", winner);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        self.wager_repo
+            .record_event(WagerEvent::new(
+                wager.wager_id,
+                Some(user_id.clone()),
+                WagerAction::PaymentSent,
+                Some(won_status),
+                won_status,
+            ))
+            .await?;
+        let content = format!("{}, {} says they paid you: {}", winner, user_id, wager);
+        let confirm = CustomId::new(Route::Payment)
+            .with(wager.wager_id)
+            .with(CONFIRM_PAID);
+        let components = vec![Component::action_row(vec![Component::button(
+            "Confirm paid",
+            3,
+            &confirm.encode()?,
+        )])];
+        Ok(InteractionResponse::channel_message_with_source(
+            InteractionCallbackData::message_callback(Some(content), components),
+        ))
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::NaiveDate;
+use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_response::{Component, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn send_settle_reminders(&self, today: NaiveDate) -> Result<usize, Error> {
+        counter("send_settle_reminders");
+
+        let settings = self.admin_repo.get().await?;
+        if settings.betting_channel.is_empty() {
+            return Ok(0);
+        }
+        let wagers = self
+            .wager_repo
+            .search_overdue(today, settings.reminder_days)
+            .await?;
+        let mut sent = 0;
+        for wager in wagers.iter() {
+            // another server may be sending the same reminders, whoever claims one sends it
+            if !self
+                .wager_repo
+                .claim_reminder(wager.wager_id as i32, today)
+                .await?
+            {
+                continue;
+            }
+            let message = format!(
+                "Reminder: this bet is past its settle date, is it time to close it out? {}",
+                wager
+            );
+            let settle = Component::button(
+                "Settle",
+                1,
+                &CustomId::new(Route::Remind).with(wager.wager_id).encode()?,
+            );
+            self.client
+                .create_message(
+                    &settings.betting_channel,
+                    &message,
+                    vec![Component::action_row(vec![settle])],
+                )
+                .await?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    pub async fn settle_reminder(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("settle_reminder");
+
+        let wager_id =
+            match CustomId::decode(&data.custom_id).and_then(|id| id.parse_field::<i32>(0)) {
+                Ok(wager_id) => wager_id,
+                Err(_) => return Err("unable to parse a wager_id from the returned value".into()),
+            };
+        self.settle_choices(wager_id, request.expect_member()?)
+            .await
+    }
+}
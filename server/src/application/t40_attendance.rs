@@ -1,4 +1,5 @@
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
+use std::collections::HashMap;
 
 use discord_api::interaction_request::{ApplicationCommandInteractionData, User};
 use discord_api::interaction_response::{
@@ -9,16 +10,19 @@ use crate::application::app::counter;
 use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
-use crate::CURRENT_FF_WEEK;
 use pog_common::discord_id::DiscordId;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::repos::attendance_record::{AttendanceRecords, AttendanceStreak};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn attendance(
@@ -34,11 +38,53 @@ where
         };
 
         let options = data.option_key_values();
+        let settings = self.admin_repo.get().await?;
+        let current_season = settings.current_season(Local::now().date_naive());
+        let season = match options.get("season") {
+            Some(season) => match season.trim().parse::<u16>() {
+                Ok(season) => season,
+                Err(_) => {
+                    let message = format!("'{}' is not a season, use a year like 2024", season);
+                    return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                        &message,
+                        vec![],
+                        vec![],
+                    ));
+                }
+            },
+            None => current_season,
+        };
+        // past seasons, or a current one without a week set, run through the last week recorded
+        let through_week = match settings.ff_week {
+            week if season == current_season && week > 0 => week,
+            _ => self
+                .attendance_repo
+                .weeks(season)
+                .await?
+                .last()
+                .copied()
+                .unwrap_or_default(),
+        };
+        if through_week == 0 {
+            let message = format!("No attendance recorded for the {} season", season);
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+
+        if options.get("view").map(String::as_str) == Some("league") {
+            return self.league_attendance(season, through_week).await;
+        }
         let manager_option = options.get("manager");
         match options.get("week") {
-            Some(week) => self.weekly_attendance(week, manager_option).await,
+            Some(week) => {
+                self.weekly_attendance(season, through_week, week, manager_option)
+                    .await
+            }
             None => {
-                self.individual_attendance(command_user, manager_option)
+                self.individual_attendance(season, through_week, command_user, manager_option)
                     .await
             }
         }
@@ -46,6 +92,8 @@ where
 
     async fn weekly_attendance(
         &self,
+        season: u16,
+        through_week: u8,
         week: &str,
         manager_option: Option<&String>,
     ) -> Result<InteractionResponse, Error> {
@@ -53,7 +101,7 @@ where
             Ok(val) => val,
             Err(_) => return Err("unable to parse week".into()),
         };
-        if !(1..=CURRENT_FF_WEEK).contains(&week) {
+        if !(1..=through_week).contains(&week) {
             let message = format!("No information for week {}", week);
             return Ok(InteractionResponse::channel_message_with_source_ephemeral(
                 &message,
@@ -67,11 +115,11 @@ where
         };
         let weekly_results = self
             .attendance_repo
-            .week_attendance(week, &manager_id)
+            .week_attendance(season, week, &manager_id)
             .await?;
 
         let mut embed = Embed::rich();
-        let title = format!("Attendance for week {}", week);
+        let title = format!("{} attendance for week {}", season, week);
         embed.title = Some(title);
         // TODO: refactor the repo call, shouldn't need a nested match here
         let description = match manager_id {
@@ -101,8 +149,11 @@ where
         );
         Ok(response)
     }
+
     async fn individual_attendance(
         &self,
+        season: u16,
+        through_week: u8,
         command_user: DiscordId,
         manager_option: Option<&String>,
     ) -> Result<InteractionResponse, Error> {
@@ -113,13 +164,11 @@ where
             }
             None => (true, command_user),
         };
-        let (overall_message, attendance) = match self
-            .attendance_repo
-            .combined_attendance()
-            .await?
-            .position_and_values(&manager_id)
-        {
-            Some((position, attendance)) => (build_response_messages(position), attendance),
+        let combined = self.attendance_repo.combined_attendance(season).await?;
+        let (overall_message, attendance) = match combined.rank_and_values(&manager_id) {
+            Some((rank, attendance)) => {
+                (build_response_messages(rank, combined.0.len()), attendance)
+            }
             None => {
                 let content = format!(
                     "no attendance records found, is {} in the league?",
@@ -133,14 +182,26 @@ where
             }
         };
 
+        let rsvp = self
+            .attendance_repo
+            .rsvp_reliability(season)
+            .await?
+            .into_iter()
+            .find(|record| record.owner_id == manager_id);
+        let streak = self
+            .season_streaks(season, through_week)
+            .await?
+            .remove(&manager_id)
+            .unwrap_or_default();
+
         let mut embed = Embed::rich();
-        let title = format!("Attendance through week {}", CURRENT_FF_WEEK);
+        let title = format!("{} attendance through week {}", season, through_week);
         embed.title = Some(title);
         embed.description = Some(format!("{}\n{}", manager_id, overall_message));
         embed.fields = vec![
             EmbedField {
                 name: "Weekly attendance".to_string(),
-                value: format!("Attended {} of {} weeks", attendance.weeks, CURRENT_FF_WEEK),
+                value: format!("Attended {} of {} weeks", attendance.weeks, through_week),
                 inline: false,
             },
             EmbedField {
@@ -148,27 +209,128 @@ where
                 value: format!("Attended {} games", attendance.games),
                 inline: false,
             },
+            EmbedField {
+                name: "Attendance streak".to_string(),
+                value: match streak.current {
+                    0 => format!(
+                        "No current streak, longest run of {} this season",
+                        count(streak.longest, "week")
+                    ),
+                    current => format!(
+                        "{} in a row now, longest run of {} this season",
+                        count(current, "week"),
+                        count(streak.longest, "week")
+                    ),
+                },
+                inline: false,
+            },
         ];
+        if let Some(rsvp) = rsvp {
+            embed.fields.push(EmbedField {
+                name: "RSVP reliability".to_string(),
+                value: format!(
+                    "Showed up {} of {} times after saying they were coming",
+                    rsvp.showed_up, rsvp.coming
+                ),
+                inline: false,
+            });
+        }
         let data = callback_data(embed, ephemeral);
         let response = InteractionResponse::channel_message_with_source(
             InteractionCallbackData::Message(data),
         );
         Ok(response)
     }
+
+    async fn league_attendance(
+        &self,
+        season: u16,
+        through_week: u8,
+    ) -> Result<InteractionResponse, Error> {
+        let combined = self.attendance_repo.combined_attendance(season).await?;
+        let streaks = self.season_streaks(season, through_week).await?;
+
+        let mut embed = Embed::rich();
+        embed.title = Some(format!(
+            "{} league attendance through week {}",
+            season, through_week
+        ));
+        embed.description = Some(league_table(&combined, &streaks));
+        let data = callback_data(embed, false);
+        let response = InteractionResponse::channel_message_with_source(
+            InteractionCallbackData::Message(data),
+        );
+        Ok(response)
+    }
+
+    // streaks run over the weeks attendance was taken, up to the week being reported on
+    async fn season_streaks(
+        &self,
+        season: u16,
+        through_week: u8,
+    ) -> Result<HashMap<DiscordId, AttendanceStreak>, Error> {
+        let taken: Vec<u8> = self
+            .attendance_repo
+            .weeks(season)
+            .await?
+            .into_iter()
+            .filter(|week| *week <= through_week)
+            .collect();
+        let mut attended: HashMap<DiscordId, Vec<u8>> = HashMap::new();
+        for (owner, week) in self.attendance_repo.attended_weeks(season).await? {
+            attended.entry(owner).or_default().push(week);
+        }
+        Ok(attended
+            .into_iter()
+            .map(|(owner, weeks)| (owner, AttendanceStreak::from_weeks(&taken, &weeks)))
+            .collect())
+    }
 }
 
-fn build_response_messages(position: u8) -> String {
-    if position < 4 {
+// tiers by the share of the league ranked ahead, so they hold for any number of teams
+fn build_response_messages(rank: usize, teams: usize) -> String {
+    let ahead = rank * 4;
+    if ahead < teams {
         "Ranks in the top quarter, outstanding attendance!\n\u{1f929}".to_string()
-    } else if position < 6 {
+    } else if ahead < teams * 2 {
         "Ranks in the top half, great record!\n\u{1f600}".to_string()
-    } else if position < 9 {
+    } else if ahead < teams * 3 {
         "Ranks in the bottom half, needs to step it up!\n\u{1f610}".to_string()
     } else {
         "Attendance has been less than satisfactory\n\u{1f641}".to_string()
     }
 }
 
+fn league_table(
+    combined: &AttendanceRecords,
+    streaks: &HashMap<DiscordId, AttendanceStreak>,
+) -> String {
+    combined
+        .0
+        .iter()
+        .map(|record| {
+            let streak = streaks.get(&record.owner_id).copied().unwrap_or_default();
+            format!(
+                "{}. {} - {}, {}, streak {} (best {})",
+                combined.rank(record) + 1,
+                record.owner_id,
+                count(record.weeks, "week"),
+                count(record.games, "game"),
+                streak.current,
+                streak.longest
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn count(count: u8, unit: &str) -> String {
+    match count {
+        1 => format!("1 {}", unit),
+        count => format!("{} {}s", count, unit),
+    }
+}
+
 fn callback_data(embed: Embed, ephemeral: bool) -> MessageCallbackData {
     let flags: Option<u32> = match ephemeral {
         true => Some(discord_api::interaction_response::message_flags::EPHEMERAL),
@@ -185,7 +347,7 @@ fn callback_data(embed: Embed, ephemeral: bool) -> MessageCallbackData {
     }
 }
 
-fn format_date(date: &str) -> String {
+pub(crate) fn format_date(date: &str) -> String {
     let date = match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
         Ok(date) => date,
         Err(err) => {
@@ -196,6 +358,29 @@ fn format_date(date: &str) -> String {
     date.format("%a, %b %e").to_string()
 }
 
+#[test]
+fn test_build_response_messages() {
+    let tier = |rank, teams| {
+        build_response_messages(rank, teams)
+            .split(',')
+            .next()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!("Ranks in the top quarter", tier(2, 12));
+    assert_eq!("Ranks in the top half", tier(3, 12));
+    assert_eq!("Ranks in the top half", tier(5, 12));
+    assert_eq!("Ranks in the bottom half", tier(6, 12));
+    assert_eq!("Ranks in the top quarter", tier(1, 8));
+    assert_eq!("Ranks in the top half", tier(2, 8));
+    assert_eq!("Ranks in the top half", tier(9, 20));
+    assert_eq!("Ranks in the bottom half", tier(10, 20));
+    assert_eq!(
+        "Attendance has been less than satisfactory\n\u{1f641}",
+        tier(15, 20)
+    );
+}
+
 #[test]
 fn test_format_date() {
     assert_eq!("Mon, Nov 27", format_date("2023-11-27"));
@@ -6,10 +6,13 @@ use crate::application::app::counter;
 use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
 
 const WHOIS_DESCRIPTION: &str = r###"`/whois` shows the human and/or hash name of some member on this server.
 After the command prompt add the user (do not use an '@' before the name here).
+Or start typing their human or hash name in `name` and pick them from the list.
 
 Don't see your name listed? Let an admin know and they can fix that for you.
 "###;
@@ -17,6 +20,10 @@ Don't see your name listed? Let an admin know and they can fix that for you.
 const ATTENDANCE_DESCRIPTION: &str = r###"`/attendance` provides attendance data for others in the league.
 - Specify a `manager` to see the attendance record for a manager
 - Specify a `week` to see the attendance on any specific week
+- Specify a `season` to look back at a past season
+- Pick the `league` view to see every team ranked, with their attendance streaks
+- Managers who answer the weekly RSVP post also see how often they showed up after saying they were coming
+- Staying in the watch party voice channel during a game counts as attending
 - Don't add anyting else to see your attendance (only you will see this)
 
 This feature only works in the `the-league` channel (you must be in the Bleachers FF league to see the channel).
@@ -24,31 +31,76 @@ This feature only works in the `the-league` channel (you must be in the Bleacher
 
 const PLACE_BET_DESCRIPTION: &str = r###"`/bet` allows you to record a bet against anyone.
 After the command prompt, add the user that you are wagering against.
-- If they are in our Discord server, use their handle starting with the @
+- If they are in our Discord server, use their handle starting with the @, they will need to accept the bet before it is recorded
 - If not on our server, just use a simple name
+- Leave it empty to post an open offer with a `Take it` button, the first member to take it is your counterparty. Offers expire if nobody takes them, and you can withdraw yours until then
 Once you've submitted the request, a modal box will appear to fill out details including the amount, a description of the bet, and your best guess as to when it will be settled.
+The amount can be money (`$20`, `20 euro`, `5 quid`) or an item with a quantity (`a beer`, `6 donuts`).
+For uneven bets, add odds (`3-1`) or what they put up, leave it empty for even odds.
+Settle dates can be `11/24`, `Feb 9`, `next sunday`, `in 3 days`, `week 14` or `end of season`.
+
+This feature only works in the `degenerate-gambling` channel.
+"###;
+
+const POOL_DESCRIPTION: &str = r###"`/pool` starts a bet anyone can buy into, like a Super Bowl pool.
+A modal will pop-up to fill out the question, the buy-in, and the options people can pick (one per line).
+Members use the `Join` button to pick an option, and the pool shows up in their `/bets`.
+The member who started the pool (or an admin) uses `Settle` to pick the winning option, and the pot is split between everyone who picked it.
 
 This feature only works in the `degenerate-gambling` channel.
 "###;
 
 const SHOW_BETS_DESCRIPTION: &str = r###"`/bets` provides a list of the current bets for a member of our Discord server.
 You must also specify the bettor, this should be their Discord handle starting with an @
+(or the name used in the bets, for someone who isn't on the server)
+- Specify a `status` to see their `won`, `lost` or `all` bets instead of the open ones
+- Specify a `from` and/or `to` date (mm/dd/yyyy) to limit the list to bets placed in that range
 
 This feature only works in the `degenerate-gambling` channel.
 "###;
 
+const EDIT_BET_DESCRIPTION: &str = r###"`/edit_bet` fixes a typo in the amount, description or settle date of an open bet.
+Start typing in `wager` to search your open bets, the bet's details will open for you to change.
+Only the two bettors can edit a wager, and the other bettor must confirm the change.
+Edited bets are marked as `amended` in `/bets`.
+"###;
+
 const SETTLE_BET_DESCRIPTION: &str = r###"`/settle` allows you to settle a previously entered wager.
 A modal will pop-up after the command is sent, select the wager that you wish to settle and an outcome.
+Or start typing in `wager` to search your open bets and skip the list.
+Only the two bettors (or an admin) can settle a wager, and the other bettor must confirm the outcome.
+If they dispute it, the wager is sent to the admins to settle.
+Once a wager is won, either bettor can use the `Mark paid` button when the money (or beer) changes hands.
+When the loser marks it paid, the winner is asked to confirm they got it.
 
 This feature only works in the `degenerate-gambling` channel.
 "###;
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+const DEBTS_DESCRIPTION: &str = r###"`/debts` lists the bets you have won or lost that haven't been paid yet.
+Debts older than a month are highlighted, pick a bet from the list to mark it paid.
+"###;
+
+const LEDGER_DESCRIPTION: &str = r###"`/ledger` shows the net amount owed between you and each member across all won bets that haven't been paid.
+- Specify a `user` to see someone else's ledger
+- Specify `settle_all` with a member to mark every unpaid money bet between the two of you as paid, if you owe them they confirm it first
+"###;
+
+const BET_STATS_DESCRIPTION: &str = r###"`/betstats` ranks the server's bettors by their won/lost record, along with their net winnings.
+- Specify a `user` to see just their record
+- Specify a `season` (like 2024) or a `from` and/or `to` date (mm/dd/yyyy) to limit which bets count
+"###;
+
+const BET_HISTORY_DESCRIPTION: &str = r###"`/bet_history` shows the timeline of a bet: who placed, accepted, edited, settled and paid it, and when.
+Start typing in `wager` to search your bets, or enter a bet's number.
+"###;
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn help(&self) -> Result<InteractionResponse, Error> {
@@ -69,16 +121,46 @@ where
                 value: PLACE_BET_DESCRIPTION.to_string(),
                 inline: false,
             },
+            EmbedField {
+                name: "Start a pool".to_string(),
+                value: POOL_DESCRIPTION.to_string(),
+                inline: false,
+            },
             EmbedField {
                 name: "Show bets".to_string(),
                 value: SHOW_BETS_DESCRIPTION.to_string(),
                 inline: false,
             },
+            EmbedField {
+                name: "Edit a bet".to_string(),
+                value: EDIT_BET_DESCRIPTION.to_string(),
+                inline: false,
+            },
             EmbedField {
                 name: "Settle a bet".to_string(),
                 value: SETTLE_BET_DESCRIPTION.to_string(),
                 inline: false,
             },
+            EmbedField {
+                name: "Unpaid bets".to_string(),
+                value: DEBTS_DESCRIPTION.to_string(),
+                inline: false,
+            },
+            EmbedField {
+                name: "Who owes who".to_string(),
+                value: LEDGER_DESCRIPTION.to_string(),
+                inline: false,
+            },
+            EmbedField {
+                name: "Leaderboard".to_string(),
+                value: BET_STATS_DESCRIPTION.to_string(),
+                inline: false,
+            },
+            EmbedField {
+                name: "Bet history".to_string(),
+                value: BET_HISTORY_DESCRIPTION.to_string(),
+                inline: false,
+            },
             EmbedField {
                 name: "Check league attendance".to_string(),
                 value: ATTENDANCE_DESCRIPTION.to_string(),
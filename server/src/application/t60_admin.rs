@@ -1,20 +1,28 @@
+use chrono::Weekday;
 use discord_api::interaction_request::{ApplicationCommandInteractionData, User};
 use discord_api::interaction_response::{
     Component, Embed, EmbedField, InteractionCallbackData, InteractionResponse, MessageCallbackData,
 };
 
 use crate::application::app::counter;
+use crate::application::parse_date::{parse_weekday_name, REGULAR_SEASON_WEEKS};
 use crate::application::Application;
 use crate::discord_client::DiscordClient;
 use crate::error::Error;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+use pog_common::wager::Stake;
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn admin(
@@ -22,6 +30,17 @@ where
         data: ApplicationCommandInteractionData,
         _user: &User,
     ) -> Result<InteractionResponse, Error> {
+        let options = data.option_key_values();
+        if options.contains_key("link_name") || options.contains_key("link_user") {
+            return self
+                .link_user(options.get("link_name"), options.get("link_user"))
+                .await;
+        }
+        if let Some(date) = options.get("attendance") {
+            return self
+                .attendance_initiate(date, options.get("attendance_week"))
+                .await;
+        }
         let option = match data.options.first() {
             None => return admin_help(),
             Some(option) => option,
@@ -29,6 +48,22 @@ where
         match option.name.as_ref() {
             "set_user" => self.set_user_initiate(&option.value).await,
             "welcome_channel" => self.welcome_channel(&option.value).await,
+            "admin_channel" => self.admin_channel(&option.value).await,
+            "betting_channel" => self.betting_channel(&option.value).await,
+            "reminder_days" => self.reminder_days(&option.value).await,
+            "offer_hours" => self.offer_hours(&option.value).await,
+            "season" => self.ff_season(&option.value).await,
+            "week" => self.ff_week(&option.value).await,
+            "max_stake" => self.max_stake(&option.value).await,
+            "max_open_bets" => self.max_open_bets(&option.value).await,
+            "max_exposure" => self.max_exposure(&option.value).await,
+            "limit_exempt" => self.limit_exempt(&option.value).await,
+            "rsvp" => self.rsvp_initiate(&option.value).await,
+            "rsvp_channel" => self.rsvp_channel(&option.value).await,
+            "rsvp_day" => self.rsvp_day(&option.value).await,
+            "watch_channel" => self.watch_channel(&option.value).await,
+            "watch_window" => self.watch_window(&option.value).await,
+            "watch_minutes" => self.watch_minutes(&option.value).await,
             other => Err(Error::Unexpected(format!(
                 "WARNING: Unrecognised option: {}",
                 other
@@ -39,7 +74,7 @@ where
     async fn set_user_initiate(&self, user_id: &str) -> Result<InteractionResponse, Error> {
         counter("admin-set_user_initiate");
 
-        Ok(open_set_user_modal(user_id))
+        open_set_user_modal(user_id)
     }
     async fn welcome_channel(&self, channel: &str) -> Result<InteractionResponse, Error> {
         counter("admin-welcome_channel");
@@ -54,6 +89,399 @@ where
             vec![],
         ))
     }
+    async fn admin_channel(&self, channel: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-admin_channel");
+
+        let mut settings = self.admin_repo.get().await?;
+        settings.admin_channel = channel.to_string();
+        self.admin_repo.update(settings).await?;
+        let message = format!("admin channel updated to: <#{}>", channel);
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn betting_channel(&self, channel: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-betting_channel");
+
+        let mut settings = self.admin_repo.get().await?;
+        settings.betting_channel = channel.to_string();
+        self.admin_repo.update(settings).await?;
+        let message = format!("betting channel updated to: <#{}>", channel);
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn rsvp_channel(&self, channel: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-rsvp_channel");
+
+        let mut settings = self.admin_repo.get().await?;
+        settings.rsvp_channel = channel.to_string();
+        self.admin_repo.update(settings).await?;
+        let message = format!("rsvp channel updated to: <#{}>", channel);
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn rsvp_day(&self, day: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-rsvp_day");
+
+        let value = day.trim().to_lowercase();
+        let weekday = match value.as_str() {
+            "none" | "0" => None,
+            value => match parse_weekday_name(value) {
+                Some(weekday) => Some(weekday),
+                None => {
+                    let message = format!(
+                        "\"{}\" is not a day of the week, use a day like `tuesday` or `none`",
+                        day
+                    );
+                    return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                        message.as_str(),
+                        vec![],
+                        vec![],
+                    ));
+                }
+            },
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.rsvp_day = weekday.map_or(0, |weekday| weekday.number_from_monday() as u8);
+        self.admin_repo.update(settings).await?;
+        let message = match weekday {
+            None => "no longer posting a weekly rsvp".to_string(),
+            Some(weekday) => format!(
+                "every {} the rsvp channel will be asked who is coming on Sunday",
+                weekday
+            ),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn watch_channel(&self, channel: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-watch_channel");
+
+        let mut settings = self.admin_repo.get().await?;
+        settings.watch_channel = channel.to_string();
+        self.admin_repo.update(settings).await?;
+        let message = format!("watch party channel updated to: <#{}>", channel);
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn watch_window(&self, window: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-watch_window");
+
+        let (days, hours) = match parse_watch_window(window) {
+            Some(window) => window,
+            None => {
+                let message = format!(
+                    "\"{}\" is not a game window, use days and hours like `sunday 13-24` or `none`",
+                    window
+                );
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.watch_days = days
+            .iter()
+            .map(|day| day.number_from_monday() as u8)
+            .collect();
+        (settings.watch_start, settings.watch_end) = hours;
+        self.admin_repo.update(settings).await?;
+        let message = match days.is_empty() {
+            true => "no longer taking attendance from the watch party channel".to_string(),
+            false => format!(
+                "anyone in the watch party channel on {} from {}:00 to {}:00 will be marked as attending",
+                days.iter()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                hours.0,
+                hours.1
+            ),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn watch_minutes(&self, minutes: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-watch_minutes");
+
+        let minutes = match minutes.trim().parse::<u16>() {
+            Ok(minutes) => minutes,
+            Err(_) => {
+                let message = format!("\"{}\" is not a number of minutes", minutes);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.watch_minutes = minutes;
+        self.admin_repo.update(settings).await?;
+        let message = format!(
+            "watch party attendance now needs {} minutes in the channel during a game window",
+            minutes
+        );
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn reminder_days(&self, days: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-reminder_days");
+
+        let days = match days.trim().parse::<u16>() {
+            Ok(days) => days,
+            Err(_) => {
+                let message = format!("\"{}\" is not a number of days", days);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.reminder_days = days;
+        self.admin_repo.update(settings).await?;
+        let message = match days {
+            0 => "overdue bets will only be reminded once".to_string(),
+            days => format!("overdue bets will be reminded every {} days", days),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn offer_hours(&self, hours: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-offer_hours");
+
+        let hours = match hours.trim().parse::<u16>() {
+            Ok(hours) => hours,
+            Err(_) => {
+                let message = format!("\"{}\" is not a number of hours", hours);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.offer_hours = hours;
+        self.admin_repo.update(settings).await?;
+        let message = match hours {
+            0 => "open offers will stay up until they are taken or withdrawn".to_string(),
+            hours => format!("open offers will expire after {} hours", hours),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn ff_season(&self, season: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-ff_season");
+
+        let season = match season.trim().parse::<u16>() {
+            Ok(season) if season == 0 || (2000..3000).contains(&season) => season,
+            _ => {
+                let message = format!("\"{}\" is not a season, use a year like 2024", season);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.ff_year = season;
+        self.admin_repo.update(settings).await?;
+        let message = match season {
+            0 => "the season will follow the calendar, starting each September".to_string(),
+            season => format!("the current season is now {}", season),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn ff_week(&self, week: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-ff_week");
+
+        let week = match week.trim().parse::<u8>() {
+            Ok(week) if week as u32 <= REGULAR_SEASON_WEEKS => week,
+            _ => {
+                let message = format!(
+                    "\"{}\" is not a week, use a week from 1 to {}",
+                    week, REGULAR_SEASON_WEEKS
+                );
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.ff_week = week;
+        self.admin_repo.update(settings).await?;
+        let message = match week {
+            0 => "the current week will follow the latest attendance recorded".to_string(),
+            week => format!("the current week is now week {}", week),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn max_stake(&self, value: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-max_stake");
+
+        let limit = match parse_money_limit(value) {
+            Ok(limit) => limit,
+            Err(response) => return Ok(*response),
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.max_stake = limit.clone();
+        self.admin_repo.update(settings).await?;
+        let message = match limit {
+            None => "bets no longer have a maximum stake".to_string(),
+            Some(limit) => format!("the most anyone can put up on a bet is now {}", limit),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn max_open_bets(&self, value: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-max_open_bets");
+
+        let count = match value.trim().parse::<u16>() {
+            Ok(count) => count,
+            Err(_) => {
+                let message = format!("\"{}\" is not a number of bets", value);
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.max_open_wagers = count;
+        self.admin_repo.update(settings).await?;
+        let message = match count {
+            0 => "users can have any number of open bets".to_string(),
+            count => format!("users can have at most {} open bets", count),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn max_exposure(&self, value: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-max_exposure");
+
+        let limit = match parse_money_limit(value) {
+            Ok(limit) => limit,
+            Err(response) => return Ok(*response),
+        };
+        let mut settings = self.admin_repo.get().await?;
+        settings.max_exposure = limit.clone();
+        self.admin_repo.update(settings).await?;
+        let message = match limit {
+            None => "users can have any amount at stake across their open bets".to_string(),
+            Some(limit) => format!(
+                "users can have at most {} at stake across their open bets",
+                limit
+            ),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn limit_exempt(&self, user_id: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-limit_exempt");
+
+        let user_id = match DiscordId::from_raw_str(user_id) {
+            Some(user_id) => user_id,
+            None => return Err(Error::UnresolvedDiscordUser),
+        };
+        let mut settings = self.admin_repo.get().await?;
+        let exempt = !settings.is_limit_exempt(&user_id);
+        match exempt {
+            true => settings.limit_exempt.push(user_id.str_value()),
+            false => settings
+                .limit_exempt
+                .retain(|exempt_id| exempt_id != &user_id.str_value()),
+        }
+        self.admin_repo.update(settings).await?;
+        let message = match exempt {
+            true => format!("{} is now exempt from betting limits", user_id),
+            false => format!("{} is no longer exempt from betting limits", user_id),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
+    async fn link_user(
+        &self,
+        name: Option<&String>,
+        user_id: Option<&String>,
+    ) -> Result<InteractionResponse, Error> {
+        counter("admin-link_user");
+
+        let (name, user_id) = match (name, user_id.and_then(|id| DiscordId::from_raw_str(id))) {
+            (Some(name), Some(user_id)) if !name.trim().is_empty() => (name.trim(), user_id),
+            _ => {
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    "Both link_name and link_user are needed to link a name to a user",
+                    vec![],
+                    vec![],
+                ))
+            }
+        };
+        let linked = self.wager_repo.link_user(name, &user_id).await?;
+        let message = format!(
+            "linked {} wagers placed against \"{}\" to {}",
+            linked, name, user_id
+        );
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            message.as_str(),
+            vec![],
+            vec![],
+        ))
+    }
 }
 
 const SET_USER_DESCRIPTION: &str = r###"`/pog_admin set_user` sets the expected landing page for new users.
@@ -64,6 +492,96 @@ After the command prompt, add the desired welcome channel (do not use a '#' befo
 
 Any new users will see a welcome message on this channel when they arrive.
 "###;
+const ADMIN_CHANNEL_DESCRIPTION: &str = r###"`/pog_admin admin_channel` sets the channel where disputed bets are announced.
+After the command prompt, add the desired admin channel (do not use a '#' before the name here).
+
+Admins can settle disputed bets with `/settle`.
+"###;
+const REMINDERS_DESCRIPTION: &str = r###"`/pog_admin betting_channel` sets the channel where reminders for overdue bets are posted.
+Bets still open after their expected settle date get a reminder with a `Settle` button.
+`/pog_admin reminder_days` sets how many days to wait before reminding again (0 to only remind once).
+"###;
+const OFFER_HOURS_DESCRIPTION: &str = r###"`/pog_admin offer_hours` sets how long an open offer from `/bet` can be taken before it expires.
+Open offers are posted to the betting channel, use 0 to keep them up until they are taken or withdrawn.
+"###;
+const LIMITS_DESCRIPTION: &str = r###"`/pog_admin max_stake` sets the most a user can put up on one bet, like `$50`.
+`/pog_admin max_open_bets` sets how many open bets a user can have at once.
+`/pog_admin max_exposure` sets the most a user can have at stake across their open bets.
+Use `none` or 0 to remove a limit. Money limits apply to bets in the same currency.
+`/pog_admin limit_exempt` exempts a user from the limits, use it again to remove the exemption.
+"###;
+const LINK_USER_DESCRIPTION: &str = r###"`/pog_admin link_name link_user` links bets placed against a plain name to a discord user.
+After the command prompt, add the name exactly as it was typed in the bets, and the user it belongs to.
+
+Their old bets will then show up in `/bets`, `/debts`, `/ledger` and `/betstats`.
+"###;
+const SEASON_DESCRIPTION: &str = r###"`/pog_admin season` sets the current season, like `2024`, and `/pog_admin week` sets the current week.
+`/attendance` reports on the current season through the current week, and settle dates like `week 14` use them.
+Use 0 to follow the calendar for the season, or the latest attendance recorded for the week.
+"###;
+const RSVP_DESCRIPTION: &str = r###"`/pog_admin rsvp` posts a message asking who is coming on a game day, like `sunday` or `11/24`.
+`/pog_admin rsvp_channel` sets where it is posted, and `/pog_admin rsvp_day` posts one every week on that day, use `none` to stop.
+Managers answer with a reaction, and `/attendance` compares who said they were coming to who showed up.
+"###;
+const WATCH_PARTY_DESCRIPTION: &str = r###"`/pog_admin watch_channel` sets a voice channel where being there during a game counts as attending.
+`/pog_admin watch_window` sets the game days and local hours, like `sunday monday 13-24`, use `none` to stop.
+`/pog_admin watch_minutes` sets how long someone has to stay for it to count, drive-by joins don't.
+Attendance goes to the current week, so keep `/pog_admin week` up to date.
+"###;
+const ATTENDANCE_DESCRIPTION: &str = r###"`/pog_admin attendance` records who came to a game day, like `10/20`, `yesterday` or `sunday`.
+The week defaults to the current week, add `attendance_week` for another week.
+Pick everyone who attended, picking again replaces the day and clearing the picks removes it.
+"###;
+
+// game days followed by the hours of the window, like `sunday monday 13-24`
+fn parse_watch_window(value: &str) -> Option<(Vec<Weekday>, (u8, u8))> {
+    let value = value.trim().to_lowercase();
+    if value == "none" {
+        return Some((vec![], (0, 0)));
+    }
+    let words: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .collect();
+    let (hours, days) = words.split_last()?;
+    let (start, end) = hours.split_once('-')?;
+    let (start, end) = (start.parse::<u8>().ok()?, end.parse::<u8>().ok()?);
+    if days.is_empty() || start >= end || end > 24 {
+        return None;
+    }
+    let mut weekdays: Vec<Weekday> = vec![];
+    for day in days {
+        let weekday = parse_weekday_name(day)?;
+        if !weekdays.contains(&weekday) {
+            weekdays.push(weekday);
+        }
+    }
+    weekdays.sort_by_key(|day| day.number_from_monday());
+    Some((weekdays, (start, end)))
+}
+
+fn parse_money_limit(value: &str) -> Result<Option<String>, Box<InteractionResponse>> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("none") || value == "0" {
+        return Ok(None);
+    }
+    match Stake::parse(value) {
+        Some(stake @ Stake::Money { .. }) => Ok(Some(stake.to_string())),
+        _ => {
+            let message = format!(
+                "\"{}\" is not an amount of money, use an amount like `$50` or `none` for no limit",
+                value
+            );
+            Err(Box::new(
+                InteractionResponse::channel_message_with_source_ephemeral(
+                    message.as_str(),
+                    vec![],
+                    vec![],
+                ),
+            ))
+        }
+    }
+}
 
 fn admin_help() -> Result<InteractionResponse, Error> {
     counter("admin-help");
@@ -82,6 +600,51 @@ fn admin_help() -> Result<InteractionResponse, Error> {
             value: WELCOME_CHANNEL_DESCRIPTION.to_string(),
             inline: false,
         },
+        EmbedField {
+            name: "Disputed bets".to_string(),
+            value: ADMIN_CHANNEL_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Overdue bet reminders".to_string(),
+            value: REMINDERS_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Open offers".to_string(),
+            value: OFFER_HOURS_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Betting limits".to_string(),
+            value: LIMITS_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Link a name to a user".to_string(),
+            value: LINK_USER_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Season and week".to_string(),
+            value: SEASON_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Record attendance".to_string(),
+            value: ATTENDANCE_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Who is coming".to_string(),
+            value: RSVP_DESCRIPTION.to_string(),
+            inline: false,
+        },
+        EmbedField {
+            name: "Watch parties".to_string(),
+            value: WATCH_PARTY_DESCRIPTION.to_string(),
+            inline: false,
+        },
     ];
     let flags: Option<u32> = Some(discord_api::interaction_response::message_flags::EPHEMERAL);
     let data = MessageCallbackData {
@@ -98,7 +661,7 @@ fn admin_help() -> Result<InteractionResponse, Error> {
     Ok(response)
 }
 
-pub fn open_set_user_modal(user_id: &str) -> InteractionResponse {
+pub fn open_set_user_modal(user_id: &str) -> Result<InteractionResponse, Error> {
     let human_name = Component::text_input(
         "human_name",
         "Human name?",
@@ -117,7 +680,7 @@ pub fn open_set_user_modal(user_id: &str) -> InteractionResponse {
         Some(30),
         false,
     );
-    let custom_id = format!("setuser|{}", user_id);
+    let custom_id = CustomId::new(Route::SetUserModal).with(user_id).encode()?;
     let modal_component = InteractionCallbackData::modal_callback_data(
         custom_id,
         "Add a user",
@@ -126,5 +689,5 @@ pub fn open_set_user_modal(user_id: &str) -> InteractionResponse {
             Component::action_row(vec![hash_name]),
         ],
     );
-    InteractionResponse::modal(modal_component)
+    Ok(InteractionResponse::modal(modal_component))
 }
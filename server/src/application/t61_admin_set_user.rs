@@ -4,14 +4,18 @@ use crate::discord_client::DiscordClient;
 use crate::error::Error;
 use discord_api::interaction_request::{ModalSubmitInteractionData, User};
 use discord_api::interaction_response::InteractionResponse;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::custom_id::CustomId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn set_user(
@@ -21,8 +25,7 @@ where
     ) -> Result<InteractionResponse, Error> {
         counter("set_user_modal_response");
 
-        let id_sec = &data.custom_id[8..];
-        let id = match id_sec.parse::<u64>() {
+        let id = match CustomId::decode(&data.custom_id).and_then(|id| id.parse_field::<u64>(0)) {
             Ok(id) => id,
             Err(_) => return Err("not a valid user id".into()),
         };
@@ -0,0 +1,171 @@
+use chrono::Local;
+use discord_api::interaction_request::{InteractionObject, MessageComponentInteractionData};
+use discord_api::interaction_response::{Component, InteractionCallbackData, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::parse_date::{parse_past_date, REGULAR_SEASON_WEEKS};
+use crate::application::t40_attendance::format_date;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::custom_id::{CustomId, Route};
+use pog_common::discord_id::DiscordId;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+
+// most users a discord select menu allows
+const MAX_ATTENDEES: u8 = 25;
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub(crate) async fn attendance_initiate(
+        &self,
+        date: &str,
+        week: Option<&String>,
+    ) -> Result<InteractionResponse, Error> {
+        counter("admin-attendance_initiate");
+
+        let settings = self.admin_repo.get().await?;
+        let date = match parse_past_date(date, Local::now().date_naive()) {
+            Some(date) => date,
+            None => {
+                let message = format!(
+                    "Unable to understand the date '{}', try `10/20`, `2024-10-20`, `today` or `sunday`",
+                    date.trim()
+                );
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let week = match week {
+            Some(week) => week.trim().parse::<u8>().unwrap_or_default(),
+            None => settings.ff_week,
+        };
+        if !(1..=REGULAR_SEASON_WEEKS).contains(&(week as u32)) {
+            let message = format!(
+                "Add an `attendance_week` from 1 to {} for {}",
+                REGULAR_SEASON_WEEKS,
+                format_date(&date.to_string())
+            );
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                &message,
+                vec![],
+                vec![],
+            ));
+        }
+        let season = settings.current_season(date);
+        let date = date.to_string();
+        let attended = self.attendance_repo.date_attendance(season, &date).await?;
+        let content = format!(
+            "Who attended on {} (week {})? The selection replaces what was recorded for the day, clear it to remove the day.",
+            format_date(&date),
+            week
+        );
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &content,
+            vec![attendance_select(season, week, &date, &attended)?],
+            vec![],
+        ))
+    }
+
+    pub async fn record_attendance(
+        &self,
+        data: MessageComponentInteractionData,
+        request: InteractionObject,
+    ) -> Result<InteractionResponse, Error> {
+        counter("admin-record_attendance");
+
+        if !request.expect_member()?.is_administrator() {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "Only admins can record attendance",
+                vec![],
+                vec![],
+            ));
+        }
+
+        let custom_id = CustomId::decode(&data.custom_id)?;
+        let (season, week, date) = match (
+            custom_id.parse_field::<u16>(0),
+            custom_id.parse_field::<u8>(1),
+            custom_id.field(2),
+        ) {
+            (Ok(season), Ok(week), Ok(date)) => (season, week, date),
+            _ => return Err("custom id was not recognized".into()),
+        };
+        let selected: Vec<DiscordId> = data
+            .values
+            .iter()
+            .filter_map(|value| DiscordId::from_raw_str(value))
+            .collect();
+        let recorded = self
+            .attendance_repo
+            .set_attendance(season, week, date, &selected)
+            .await?;
+
+        let mut content = match recorded.is_empty() {
+            true => format!("Cleared attendance for {}", format_date(date)),
+            false => format!(
+                "Recorded week {} attendance for {}: {}",
+                week,
+                format_date(date),
+                mentions(&recorded)
+            ),
+        };
+        let skipped: Vec<DiscordId> = selected
+            .into_iter()
+            .filter(|user| !recorded.contains(user))
+            .collect();
+        if !skipped.is_empty() {
+            content.push_str(&format!(
+                "\nNot in the league, so not recorded: {}",
+                mentions(&skipped)
+            ));
+        }
+        Ok(InteractionResponse::update_message(
+            InteractionCallbackData::ephemeral_message_callback(
+                Some(content),
+                vec![attendance_select(season, week, date, &recorded)?],
+                vec![],
+            ),
+        ))
+    }
+}
+
+fn attendance_select(
+    season: u16,
+    week: u8,
+    date: &str,
+    attended: &[DiscordId],
+) -> Result<Component, Error> {
+    let custom_id = CustomId::new(Route::Attendance)
+        .with(season)
+        .with(week)
+        .with(date)
+        .encode()?;
+    Ok(Component::action_row(vec![Component::user_select(
+        &custom_id,
+        "Who attended?",
+        0,
+        MAX_ATTENDEES,
+        attended.iter().map(DiscordId::str_value).collect(),
+    )]))
+}
+
+fn mentions(users: &[DiscordId]) -> String {
+    users
+        .iter()
+        .map(|user| user.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
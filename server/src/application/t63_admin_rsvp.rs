@@ -0,0 +1,103 @@
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use discord_api::interaction_response::InteractionResponse;
+
+use crate::application::app::counter;
+use crate::application::parse_date::{parse_date, upcoming};
+use crate::application::t40_attendance::format_date;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::repos::attendance_record::{RsvpPost, RSVP_NO, RSVP_YES};
+use pog_common::repos::{
+    AdminRepository, AdminSettings, AttendanceRepository, PoolRepository, WagerRepository,
+    WhoisRepository,
+};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub(crate) async fn rsvp_initiate(&self, date: &str) -> Result<InteractionResponse, Error> {
+        counter("admin-rsvp_initiate");
+
+        let settings = self.admin_repo.get().await?;
+        if settings.rsvp_channel.is_empty() {
+            return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                "Set an `rsvp_channel` before asking who is coming",
+                vec![],
+                vec![],
+            ));
+        }
+        let today = Local::now().date_naive();
+        let date = match parse_date(date, today, &settings) {
+            Ok(Some(date)) => date,
+            Ok(None) => next_game_day(today),
+            Err(_) => {
+                let message = format!(
+                    "Unable to understand the game day '{}', try `sunday`, `11/24` or `week 14`",
+                    date.trim()
+                );
+                return Ok(InteractionResponse::channel_message_with_source_ephemeral(
+                    &message,
+                    vec![],
+                    vec![],
+                ));
+            }
+        };
+        let message = match self.request_rsvp(&settings, date).await? {
+            true => format!(
+                "Asking who is coming on {} in <#{}>",
+                format_date(&date.to_string()),
+                settings.rsvp_channel
+            ),
+            false => format!(
+                "{} already has a post asking who is coming",
+                format_date(&date.to_string())
+            ),
+        };
+        Ok(InteractionResponse::channel_message_with_source_ephemeral(
+            &message,
+            vec![],
+            vec![],
+        ))
+    }
+
+    pub async fn send_rsvp_post(&self, today: NaiveDate) -> Result<bool, Error> {
+        counter("send_rsvp_post");
+
+        let settings = self.admin_repo.get().await?;
+        if settings.rsvp_channel.is_empty()
+            || settings.rsvp_day as u32 != today.weekday().number_from_monday()
+        {
+            return Ok(false);
+        }
+        self.request_rsvp(&settings, next_game_day(today)).await
+    }
+
+    // the gateway sends the post and records the reactions to it
+    async fn request_rsvp(&self, settings: &AdminSettings, date: NaiveDate) -> Result<bool, Error> {
+        let content = format!(
+            "Who's coming to watch on {}? React with {} if you'll be there or {} if you can't make it.",
+            format_date(&date.to_string()),
+            RSVP_YES,
+            RSVP_NO
+        );
+        let post = RsvpPost {
+            season: settings.current_season(date),
+            date: date.to_string(),
+            channel_id: settings.rsvp_channel.clone(),
+            content,
+            message_id: None,
+        };
+        Ok(self.attendance_repo.request_rsvp_post(&post).await?)
+    }
+}
+
+fn next_game_day(today: NaiveDate) -> NaiveDate {
+    upcoming(Weekday::Sun, today)
+}
@@ -4,14 +4,17 @@ use crate::discord_client::DiscordClient;
 use crate::error::Error;
 use discord_api::interaction_request::{ApplicationCommandInteractionData, User};
 use discord_api::interaction_response::InteractionResponse;
-use pog_common::repos::{AdminRepository, AttendanceRepository, WagerRepository, WhoisRepository};
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
 
-impl<WR, AR, SR, UR, C> Application<WR, AR, SR, UR, C>
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
 where
     WR: WagerRepository,
     AR: AttendanceRepository,
     SR: AdminRepository,
     UR: WhoisRepository,
+    PR: PoolRepository,
     C: DiscordClient,
 {
     pub async fn whois(
@@ -21,13 +24,20 @@ where
     ) -> Result<InteractionResponse, Error> {
         counter("whois");
 
-        let option = match data.options.first() {
-            None => return self.help().await,
-            Some(option) => option,
-        };
-        let user_id: u64 = match option.value.parse() {
-            Ok(id) => id,
-            Err(err) => return Err(Error::Unexpected(err.to_string())),
+        let options = data.option_key_values();
+        let user_id: u64 = match (options.get("whois"), options.get("name")) {
+            (Some(user_id), _) => match user_id.parse() {
+                Ok(id) => id,
+                Err(err) => return Err(Error::Unexpected(err.to_string())),
+            },
+            (None, Some(name)) => match name.parse() {
+                Ok(id) => id,
+                Err(_) => match self.whois_repo.search(name.trim()).await?.first() {
+                    Some(person) => person.discord_id,
+                    None => return no_known_user(),
+                },
+            },
+            (None, None) => return self.help().await,
         };
         let user_details = match self.whois_repo.get_by_discord_id(user_id).await? {
             Some(user) => user,
@@ -0,0 +1,146 @@
+use chrono::Local;
+use discord_api::interaction_request::{ApplicationCommandInteractionData, GuildMember};
+use discord_api::interaction_response::{AutocompleteChoice, InteractionResponse};
+
+use crate::application::app::counter;
+use crate::application::t30_settle_bet::wager_matches;
+use crate::application::Application;
+use crate::discord_client::DiscordClient;
+use crate::error::Error;
+use pog_common::repos::{
+    AdminRepository, AttendanceRepository, PoolRepository, WagerRepository, WhoisRepository,
+};
+
+impl<WR, AR, SR, UR, PR, C> Application<WR, AR, SR, UR, PR, C>
+where
+    WR: WagerRepository,
+    AR: AttendanceRepository,
+    SR: AdminRepository,
+    UR: WhoisRepository,
+    PR: PoolRepository,
+    C: DiscordClient,
+{
+    pub async fn autocomplete(
+        &self,
+        data: ApplicationCommandInteractionData,
+        member: &GuildMember,
+    ) -> Result<InteractionResponse, Error> {
+        counter("autocomplete");
+
+        let focused = match data.focused_option() {
+            Some(focused) => focused,
+            None => return Ok(InteractionResponse::autocomplete_result(vec![])),
+        };
+        let choices = match (data.name.as_str(), focused.name.as_str()) {
+            (pog_common::SETTLE_BET_COMMAND, "wager") => {
+                self.wager_choices(&focused.value, member).await?
+            }
+            (pog_common::EDIT_BET_COMMAND, "wager") => {
+                self.editable_wager_choices(&focused.value, member).await?
+            }
+            (pog_common::BET_HISTORY_COMMAND, "wager") => {
+                self.history_choices(&focused.value, member).await?
+            }
+            (pog_common::ATTENDANCE_BET_COMMAND, "week") => {
+                let options = data.option_key_values();
+                self.week_choices(&focused.value, options.get("season"))
+                    .await?
+            }
+            (pog_common::WHOIS_COMMAND, "name") => self.whois_choices(&focused.value).await?,
+            (command, option) => {
+                return Err(Error::Invalid(format!(
+                    "no autocomplete for {} option: {}",
+                    command, option
+                )))
+            }
+        };
+        Ok(InteractionResponse::autocomplete_result(choices))
+    }
+
+    async fn wager_choices(
+        &self,
+        search: &str,
+        member: &GuildMember,
+    ) -> Result<Vec<AutocompleteChoice>, Error> {
+        Ok(self
+            .settleable_wagers(member)
+            .await?
+            .iter()
+            .filter(|wager| wager_matches(wager, search))
+            .map(|wager| {
+                AutocompleteChoice::new(&wager.simplified_string(), &wager.wager_id.to_string())
+            })
+            .collect())
+    }
+
+    async fn editable_wager_choices(
+        &self,
+        search: &str,
+        member: &GuildMember,
+    ) -> Result<Vec<AutocompleteChoice>, Error> {
+        Ok(self
+            .editable_wagers(member)
+            .await?
+            .iter()
+            .filter(|wager| wager_matches(wager, search))
+            .map(|wager| {
+                AutocompleteChoice::new(&wager.simplified_string(), &wager.wager_id.to_string())
+            })
+            .collect())
+    }
+
+    async fn history_choices(
+        &self,
+        search: &str,
+        member: &GuildMember,
+    ) -> Result<Vec<AutocompleteChoice>, Error> {
+        Ok(self
+            .wager_history(member)
+            .await?
+            .iter()
+            .filter(|wager| wager_matches(wager, search))
+            .map(|wager| {
+                AutocompleteChoice::new(&wager.simplified_string(), &wager.wager_id.to_string())
+            })
+            .collect())
+    }
+
+    async fn week_choices(
+        &self,
+        search: &str,
+        season: Option<&String>,
+    ) -> Result<Vec<AutocompleteChoice>, Error> {
+        let season = match season.and_then(|season| season.trim().parse::<u16>().ok()) {
+            Some(season) => season,
+            None => {
+                let settings = self.admin_repo.get().await?;
+                settings.current_season(Local::now().date_naive())
+            }
+        };
+        Ok(self
+            .attendance_repo
+            .weeks(season)
+            .await?
+            .iter()
+            .map(|week| week.to_string())
+            .filter(|week| week.starts_with(search.trim()))
+            .map(|week| AutocompleteChoice::new(&format!("Week {}", week), &week))
+            .collect())
+    }
+
+    async fn whois_choices(&self, search: &str) -> Result<Vec<AutocompleteChoice>, Error> {
+        if search.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(self
+            .whois_repo
+            .search(search.trim())
+            .await?
+            .iter()
+            .map(|person| {
+                let name = format!("{} ({})", person.human_name, person.hash_name);
+                AutocompleteChoice::new(&name, &person.discord_id.to_string())
+            })
+            .collect())
+    }
+}
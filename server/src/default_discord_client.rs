@@ -1,6 +1,11 @@
 use crate::discord_client::DiscordClient;
-use pog_common::{discord_headers, Authorization, DeleteMessage, DiscordMessage};
+use discord_api::interaction_response::Component;
+#[cfg(feature = "gcp")]
+use pog_common::{discord_headers, DeleteMessage};
+use pog_common::{Authorization, CreateMessage, DiscordMessage};
+#[cfg(feature = "gcp")]
 use std::sync::Arc;
+#[cfg(feature = "gcp")]
 use tokio::sync::Mutex;
 
 use crate::error::Error;
@@ -35,17 +40,9 @@ impl AwsDefaultDiscordClient {
 }
 
 #[cfg(feature = "aws")]
-impl DiscordClient for AwsDefaultDiscordClient {
-    async fn delete_message(&self, message_id: &str, request_token: &str) -> Result<(), Error> {
-        let delete = pog_common::DeleteMessage {
-            authorization: self.authorization.clone(),
-            message_id: message_id.to_string(),
-            request_token: request_token.to_string(),
-        };
-        let message = DiscordMessage::Delete(delete);
+impl AwsDefaultDiscordClient {
+    async fn invoke(&self, message: DiscordMessage) -> Result<(), Error> {
         let payload = serde_json::to_vec(&message).unwrap();
-
-        let _timer = crate::observe::Timer::new("client_delete_time");
         match self
             .client
             .invoke()
@@ -61,6 +58,36 @@ impl DiscordClient for AwsDefaultDiscordClient {
     }
 }
 
+#[cfg(feature = "aws")]
+impl DiscordClient for AwsDefaultDiscordClient {
+    async fn delete_message(&self, message_id: &str, request_token: &str) -> Result<(), Error> {
+        let delete = pog_common::DeleteMessage {
+            authorization: self.authorization.clone(),
+            message_id: message_id.to_string(),
+            request_token: request_token.to_string(),
+        };
+        let _timer = crate::observe::Timer::new("client_delete_time");
+        self.invoke(DiscordMessage::Delete(delete)).await
+    }
+
+    async fn create_message(
+        &self,
+        channel_id: &str,
+        message: &str,
+        components: Vec<Component>,
+    ) -> Result<(), Error> {
+        let create = CreateMessage {
+            authorization: self.authorization.clone(),
+            channel_id: channel_id.to_string(),
+            message: message.to_string(),
+            message_reference: None,
+            components: to_values(components)?,
+        };
+        let _timer = crate::observe::Timer::new("client_create_time");
+        self.invoke(DiscordMessage::Create(create)).await
+    }
+}
+
 #[cfg(feature = "gcp")]
 #[derive(Debug, Clone)]
 pub struct GcpDefaultDiscordClient {
@@ -68,6 +95,7 @@ pub struct GcpDefaultDiscordClient {
     messages: Arc<Mutex<Vec<DiscordMessage>>>,
 }
 
+#[cfg(feature = "gcp")]
 impl GcpDefaultDiscordClient {
     pub async fn start(self) {
         loop {
@@ -97,21 +125,26 @@ impl GcpDefaultDiscordClient {
     }
 
     async fn send(&self, message: DiscordMessage) {
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            "Content-Type",
-            "application/json; charset=UTF-8".parse().unwrap(),
-        );
-        let delete_message = match &message {
-            DiscordMessage::Delete(delete_message) => delete_message,
+        let request = match &message {
+            DiscordMessage::Delete(delete_message) => {
+                crate::application::app::counter("delete_discord_message");
+                reqwest::Client::new().delete(delete_message.url())
+            }
+            DiscordMessage::Create(create_message) => {
+                crate::application::app::counter("create_discord_message");
+                reqwest::Client::new()
+                    .post(create_message.url())
+                    .json(&serde_json::json!({
+                        "content": create_message.message,
+                        "components": create_message.components,
+                    }))
+            }
             _ => {
                 println!("Processing unexpected message: {:?}", message);
                 return;
             }
         };
-        crate::application::app::counter("delete_discord_message");
-        match reqwest::Client::new()
-            .delete(delete_message.url())
+        match request
             .headers(discord_headers(&self.authorization))
             .send()
             .await
@@ -135,4 +168,30 @@ impl DiscordClient for GcpDefaultDiscordClient {
         self.queue(message).await;
         Ok(())
     }
+
+    async fn create_message(
+        &self,
+        channel_id: &str,
+        message: &str,
+        components: Vec<Component>,
+    ) -> Result<(), Error> {
+        let message = DiscordMessage::Create(CreateMessage {
+            authorization: self.authorization.clone(),
+            channel_id: channel_id.to_string(),
+            message: message.to_string(),
+            message_reference: None,
+            components: to_values(components)?,
+        });
+        self.queue(message).await;
+        Ok(())
+    }
+}
+
+fn to_values(components: Vec<Component>) -> Result<Vec<serde_json::Value>, Error> {
+    components
+        .into_iter()
+        .map(|component| {
+            serde_json::to_value(component).map_err(|err| Error::Unexpected(err.to_string()))
+        })
+        .collect()
 }
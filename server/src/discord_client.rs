@@ -1,18 +1,46 @@
 use crate::error::Error;
+use discord_api::interaction_response::Component;
+#[cfg(test)]
 use std::sync::{Arc, Mutex};
 
 pub trait DiscordClient: std::fmt::Debug {
     async fn delete_message(&self, message_id: &str, request_token: &str) -> Result<(), Error>;
+    async fn create_message(
+        &self,
+        channel_id: &str,
+        message: &str,
+        components: Vec<Component>,
+    ) -> Result<(), Error>;
 }
 
+#[cfg(test)]
 #[derive(Clone, Default, Debug)]
 pub struct TestDiscordClient {
     pub message: Arc<Mutex<Option<String>>>,
+    pub created_messages: Arc<Mutex<Vec<CreatedMessage>>>,
 }
 
+#[cfg(test)]
+pub type CreatedMessage = (String, String, Vec<Component>);
+
+#[cfg(test)]
 impl DiscordClient for TestDiscordClient {
     async fn delete_message(&self, _message_id: &str, _request_token: &str) -> Result<(), Error> {
         *self.message.lock().unwrap() = None;
         Ok(())
     }
+
+    async fn create_message(
+        &self,
+        channel_id: &str,
+        message: &str,
+        components: Vec<Component>,
+    ) -> Result<(), Error> {
+        self.created_messages.lock().unwrap().push((
+            channel_id.to_string(),
+            message.to_string(),
+            components,
+        ));
+        Ok(())
+    }
 }
@@ -23,6 +23,11 @@ impl From<pog_common::error::Error> for Error {
         match err {
             pog_common::error::Error::Unexpected(msg) => Error::Unexpected(msg),
             pog_common::error::Error::Database(msg) => Error::DatabaseFailure(msg),
+            pog_common::error::Error::Conflict(wager) => Error::Invalid(format!(
+                "wager {} was changed by another request",
+                wager.wager_id
+            )),
+            pog_common::error::Error::CustomId(msg) => Error::Invalid(msg),
         }
     }
 }
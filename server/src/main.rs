@@ -8,8 +8,8 @@ use discord_api::interaction_response::InteractionResponse;
 
 use crate::verify::VerifyTool;
 use pog_common::repos::{
-    new_db_pool, PostgresAdminRepository, PostgresAttendanceRepository, PostgresWagerRepo,
-    PostgresWhoisRepository,
+    new_db_pool, PostgresAdminRepository, PostgresAttendanceRepository, PostgresPoolRepo,
+    PostgresWagerRepo, PostgresWhoisRepository,
 };
 
 mod application;
@@ -31,7 +31,8 @@ pub static POG_METRIC: once_cell::sync::OnceCell<
 > = once_cell::sync::OnceCell::new();
 
 pub const ADD_BET_PLACEHOLDER_TEXT: &str = "Raiders make the playoffs";
-pub const CURRENT_FF_WEEK: u8 = 18;
+#[cfg(feature = "gcp")]
+const SETTLE_REMINDER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
 
 #[cfg(feature = "aws")]
 pub fn metric(f: impl Fn(std::sync::MutexGuard<crate::observe::Metrics>)) {
@@ -68,6 +69,9 @@ async fn main() -> Result<(), Error> {
         std::env::var("DISCORD_PUBLIC_KEY").expect("finding public key from environment");
     let application_id =
         std::env::var("DISCORD_APPLICATION_ID").expect("finding application id from environment");
+    if let Ok(signing_key) = std::env::var("CUSTOM_ID_SIGNING_KEY") {
+        pog_common::custom_id::set_signing_key(signing_key.as_bytes())?;
+    }
     let db_connection = format!(
         "postgresql://{}:{}@{}:5432/{}",
         db_user, db_pass, db_host, db_name
@@ -77,6 +81,7 @@ async fn main() -> Result<(), Error> {
     let attendance_repo = PostgresAttendanceRepository::new(db_pool.clone());
     let admin_repo = PostgresAdminRepository::new(db_pool.clone());
     let whois_repo = PostgresWhoisRepository::new(db_pool.clone());
+    let pool_repo = PostgresPoolRepo::new(db_pool.clone());
 
     #[cfg(feature = "aws")]
     {
@@ -90,22 +95,31 @@ async fn main() -> Result<(), Error> {
         POG_METRIC
             .set(std::sync::Arc::new(std::sync::Mutex::new(metrics)))
             .unwrap();
+        // cloned since builds with both features (make build_aws) still compile the gcp setup
         let client = crate::default_discord_client::AwsDefaultDiscordClient::new(
-            application_id,
-            application_token,
+            application_id.clone(),
+            application_token.clone(),
             client_lambda,
         )
         .await;
-        let application =
-            Application::new(wager_repo, attendance_repo, admin_repo, whois_repo, client);
+        let application = Application::new(
+            wager_repo.clone(),
+            attendance_repo.clone(),
+            admin_repo.clone(),
+            whois_repo.clone(),
+            pool_repo.clone(),
+            client,
+        );
         let state = AppState {
             verifier: VerifyTool::new(&public_key),
             application,
         };
-        let routes = Router::new().route(
-            "/interactions",
-            axum::routing::get(lambda_query_handler).post(lambda_command_handler),
-        );
+        let routes = Router::new()
+            .route(
+                "/interactions",
+                axum::routing::get(lambda_query_handler).post(lambda_command_handler),
+            )
+            .route("/reminders", axum::routing::post(lambda_reminder_handler));
         let app = Router::new().merge(routes).with_state(state);
 
         if let Err(err) = lambda_http::run(app).await {
@@ -122,8 +136,24 @@ async fn main() -> Result<(), Error> {
         tokio::spawn(async move {
             client_clone.start().await;
         });
-        let application =
-            Application::new(wager_repo, attendance_repo, admin_repo, whois_repo, client);
+        let application = Application::new(
+            wager_repo,
+            attendance_repo,
+            admin_repo,
+            whois_repo,
+            pool_repo,
+            client,
+        );
+        let reminder_application = application.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SETTLE_REMINDER_INTERVAL);
+            loop {
+                interval.tick().await;
+                settle_reminders(&reminder_application).await;
+                expire_offers(&reminder_application).await;
+                rsvp_post(&reminder_application).await;
+            }
+        });
         let state = AppState {
             verifier: VerifyTool::new(&public_key),
             application,
@@ -145,11 +175,7 @@ pub async fn lambda_query_handler() -> Result<Response, (StatusCode, String)> {
 }
 
 #[cfg(feature = "aws")]
-const APPLICATION_FAILURE: &str = "application_failure";
-#[cfg(feature = "aws")]
-const UNEXPECTED_REQUEST_PAYLOAD: &str = "unexpected request payload";
-#[cfg(feature = "aws")]
-const SUCCESS: &str = "success";
+const REMINDER_SECRET_HEADER: &str = "x-pog-reminder-secret";
 
 #[cfg(feature = "aws")]
 async fn lambda_command_handler<T: DiscordClient>(
@@ -161,6 +187,57 @@ async fn lambda_command_handler<T: DiscordClient>(
     reset_metric().await;
     Ok(result)
 }
+// the function url is public, so only the scheduled rule that knows the secret can run the jobs
+#[cfg(feature = "aws")]
+async fn lambda_reminder_handler<T: DiscordClient>(
+    State(state): State<AppState<T>>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    let secret = std::env::var("REMINDER_SECRET").unwrap_or_default();
+    if !reminder_authorized(&headers, &secret) {
+        return Ok(StatusCode::UNAUTHORIZED.into_response());
+    }
+    settle_reminders(&state.application).await;
+    expire_offers(&state.application).await;
+    rsvp_post(&state.application).await;
+    reset_metric().await;
+    Ok(StatusCode::OK.into_response())
+}
+
+#[cfg(feature = "aws")]
+fn reminder_authorized(headers: &HeaderMap, secret: &str) -> bool {
+    let given = headers
+        .get(REMINDER_SECRET_HEADER)
+        .and_then(|value| value.to_str().ok());
+    !secret.is_empty() && given == Some(secret)
+}
+
+async fn settle_reminders<T: DiscordClient>(application: &PogApplication<T>) {
+    let today = chrono::Utc::now().date_naive();
+    match application.send_settle_reminders(today).await {
+        Ok(0) => {}
+        Ok(sent) => println!("sent {} settle reminders", sent),
+        Err(err) => println!("ERROR sending settle reminders: {:?}", err),
+    }
+}
+
+async fn expire_offers<T: DiscordClient>(application: &PogApplication<T>) {
+    match application.expire_offers(chrono::Utc::now()).await {
+        Ok(0) => {}
+        Ok(expired) => println!("expired {} open offers", expired),
+        Err(err) => println!("ERROR expiring open offers: {:?}", err),
+    }
+}
+
+async fn rsvp_post<T: DiscordClient>(application: &PogApplication<T>) {
+    let today = chrono::Local::now().date_naive();
+    match application.send_rsvp_post(today).await {
+        Ok(false) => {}
+        Ok(true) => println!("requested the weekly rsvp post"),
+        Err(err) => println!("ERROR requesting the weekly rsvp post: {:?}", err),
+    }
+}
+
 pub(crate) async fn post_handler<T: DiscordClient>(
     State(state): State<AppState<T>>,
     headers: HeaderMap,
@@ -169,28 +246,28 @@ pub(crate) async fn post_handler<T: DiscordClient>(
     println!("request: {}", body);
     match route(state, headers, body).await {
         Ok(response) => {
-            // metric(|mut m| m.count(SUCCESS));
+            // metric(|mut m| m.count("success"));
             str_response(response).into_response()
         }
         Err(err) => match err {
             Error::NotAuthorized => (StatusCode::UNAUTHORIZED).into_response(),
             Error::Invalid(message) => {
                 println!("ERROR unexpected error: {}", message);
-                // metric(|mut m| m.count(UNEXPECTED_REQUEST_PAYLOAD));
+                // metric(|mut m| m.count("unexpected request payload"));
                 StatusCode::BAD_REQUEST.into_response()
             }
             Error::DatabaseFailure(message) => {
                 println!("ERROR db connection failure: {}", message);
-                // metric(|mut m| m.count(APPLICATION_FAILURE));
+                // metric(|mut m| m.count("application_failure"));
                 (StatusCode::INTERNAL_SERVER_ERROR).into_response()
             }
             Error::ClientFailure(message) => {
                 println!("ERROR Client failure: {}", message);
-                // metric(|mut m| m.count(APPLICATION_FAILURE));
+                // metric(|mut m| m.count("application_failure"));
                 StatusCode::INTERNAL_SERVER_ERROR.into_response()
             }
             Error::UnresolvedDiscordUser => {
-                // metric(|mut m| m.count(UNEXPECTED_REQUEST_PAYLOAD));
+                // metric(|mut m| m.count("unexpected request payload"));
                 str_response("not a user in this channel".into()).into_response()
             }
             Error::Unexpected(message) => {
@@ -233,11 +310,33 @@ async fn route<T: DiscordClient>(
 #[derive(Debug, Clone)]
 struct AppState<T: DiscordClient> {
     pub verifier: VerifyTool,
-    pub application: Application<
-        PostgresWagerRepo,
-        PostgresAttendanceRepository,
-        PostgresAdminRepository,
-        PostgresWhoisRepository,
-        T,
-    >,
+    pub application: PogApplication<T>,
+}
+
+type PogApplication<T> = Application<
+    PostgresWagerRepo,
+    PostgresAttendanceRepository,
+    PostgresAdminRepository,
+    PostgresWhoisRepository,
+    PostgresPoolRepo,
+    T,
+>;
+
+#[cfg(all(test, feature = "aws"))]
+mod test {
+    use crate::reminder_authorized;
+    use axum::http::Method;
+
+    // the input the SettleReminderSchedule rule in infrastructure/aws/instances.yaml sends
+    const SCHEDULED_REMINDER: &str = r#"{"version":"2.0","routeKey":"$default","rawPath":"/reminders","rawQueryString":"","headers":{"x-pog-reminder-secret":"shh"},"requestContext":{"http":{"method":"POST","path":"/reminders","protocol":"HTTP/1.1","sourceIp":"127.0.0.1","userAgent":"EventBridge"}},"isBase64Encoded":false}"#;
+
+    #[test]
+    fn scheduled_reminder() {
+        let request = lambda_http::request::from_str(SCHEDULED_REMINDER).unwrap();
+        assert_eq!(Method::POST, request.method());
+        assert_eq!("/reminders", request.uri().path());
+        assert!(reminder_authorized(request.headers(), "shh"));
+        assert!(!reminder_authorized(request.headers(), "other"));
+        assert!(!reminder_authorized(&Default::default(), ""));
+    }
 }